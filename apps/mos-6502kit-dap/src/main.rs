@@ -0,0 +1,31 @@
+//! MOS 6502 Debug Adapter Protocol 서버
+//! 표준 입출력으로 에디터(VS Code 등)와 통신
+
+mod protocol;
+mod server;
+
+use server::DapServer;
+use std::io::{self, BufReader};
+use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+    // 표준 출력은 프로토콜 전송로로 사용
+    mos_6502kit::common::trace::set_enabled(false);
+
+    // 실행 중에도 pause 요청을 받을 수 있도록 별도 스레드에서 읽기
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(io::stdin());
+        while let Ok(Some(message)) = protocol::read_message(&mut reader) {
+            if tx.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let server = DapServer::new(io::stdout(), rx);
+    if let Err(e) = server.run() {
+        eprintln!("[DAP] {}", e);
+    }
+}
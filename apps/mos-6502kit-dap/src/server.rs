@@ -0,0 +1,507 @@
+//! DAP 세션 처리 - 요청을 디버거 동작으로 변환
+use crate::protocol::write_message;
+use mos_6502kit::assembler::{Assembler, DebugInfo};
+use mos_6502kit::common::{Result, SymbolTable};
+use mos_6502kit::cpu::register::StatusRegister;
+use mos_6502kit::cpu::{CPU, CPUState, RegisterType, StopReason};
+use mos_6502kit::debugger::{AccessType, Debugger};
+use mos_6502kit::error::Error;
+use mos_6502kit::memory::Memory;
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+/// 6502는 단일 스레드
+const THREAD_ID: u64 = 1;
+/// 실행 중 pause 요청을 확인하는 간격 (명령어 수)
+const RUN_BATCH: usize = 1000;
+/// step over/out 최대 명령어 수 - 복귀하지 않는 서브루틴 대비
+const STEP_LIMIT: usize = 1_000_000;
+const JSR_OPCODE: u8 = 0x20;
+
+// scopes의 variablesReference
+const REGISTERS_REF: u64 = 1;
+const FLAGS_REF: u64 = 2;
+const ZERO_PAGE_REF: u64 = 3;
+const STACK_REF: u64 = 4;
+
+/// 플래그 표시 순서 (NV-BDIZC)
+const FLAGS: [(&str, StatusRegister); 7] = [
+    ("N", StatusRegister::NEGATIVE),
+    ("V", StatusRegister::OVERFLOW),
+    ("B", StatusRegister::BREAK),
+    ("D", StatusRegister::DECIMAL),
+    ("I", StatusRegister::INTERRUPT_DISABLE),
+    ("Z", StatusRegister::ZERO),
+    ("C", StatusRegister::CARRY),
+];
+
+pub struct DapServer<W: Write> {
+    writer: W,
+    requests: Receiver<Value>,
+    // 실행 중 도착해 정지 후 처리할 요청
+    pending: VecDeque<Value>,
+    seq: u64,
+    debugger: Debugger,
+    debug_info: DebugInfo,
+    source_path: String,
+    // setBreakpoints로 설정된 주소 - 다음 요청 시 교체
+    source_breakpoints: Vec<u16>,
+    stop_on_entry: bool,
+    terminated: bool,
+}
+
+impl<W: Write> DapServer<W> {
+    pub fn new(writer: W, requests: Receiver<Value>) -> Self {
+        Self {
+            writer,
+            requests,
+            pending: VecDeque::new(),
+            seq: 0,
+            debugger: Debugger::new(),
+            debug_info: DebugInfo::default(),
+            source_path: String::new(),
+            source_breakpoints: Vec::new(),
+            stop_on_entry: false,
+            terminated: false,
+        }
+    }
+
+    /// 요청 채널이 닫히거나 disconnect 요청이 올 때까지 처리
+    pub fn run(mut self) -> io::Result<()> {
+        while let Some(request) = self.next_request() {
+            if !self.handle_request(&request)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn next_request(&mut self) -> Option<Value> {
+        self.pending
+            .pop_front()
+            .or_else(|| self.requests.recv().ok())
+    }
+
+    /// 요청 하나 처리 - 세션을 끝내야 하면 false 반환
+    fn handle_request(&mut self, request: &Value) -> io::Result<bool> {
+        let command = request["command"].as_str().unwrap_or_default();
+        let args = &request["arguments"];
+
+        let result = match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsEvaluateForHovers": true,
+            })),
+            "launch" => self.launch(args),
+            "setBreakpoints" => self.set_breakpoints(args),
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "6502" }] })),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => Ok(scopes()),
+            "variables" => self.variables(args),
+            "evaluate" => self.evaluate(args),
+            "continue" => Ok(json!({ "allThreadsContinued": true })),
+            "configurationDone" | "next" | "stepIn" | "stepOut" | "disconnect" => Ok(Value::Null),
+            // 실행 중의 pause는 resume에서 처리 - 여기 오면 이미 정지 상태라 stopped를 보내지 않음
+            "pause" => Ok(Value::Null),
+            _ => Err(Error::Internal(format!("Unsupported request: {}", command))),
+        };
+        let launched = command == "launch" && result.is_ok();
+        self.respond(request, result)?;
+
+        // 응답 이후에 보내야 하는 이벤트와 실행
+        match command {
+            "launch" if launched => self.send_event("initialized", Value::Null)?,
+            "configurationDone" if self.stop_on_entry => self.send_stopped("entry")?,
+            "configurationDone" | "continue" => self.resume()?,
+            "next" => self.step_over()?,
+            "stepIn" => self.step_in()?,
+            "stepOut" => self.step_out()?,
+            "disconnect" => return Ok(false),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// 소스 파일을 어셈블해 메모리에 적재하고 CPU를 디버거에 연결
+    fn launch(&mut self, args: &Value) -> Result<Value> {
+        let path = args["program"].as_str().ok_or_else(|| {
+            Error::Internal("launch requires a \"program\" source path".to_string())
+        })?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| Error::Internal(format!("{}: {}", path, e)))?;
+        let org = args["org"].as_u64().map_or(0x8000, |org| org as u16);
+        let (machine_code, debug_info) = Assembler::new(org).assemble_with_debug_info(&source)?;
+
+        let mut memory = Memory::new();
+        for (offset, &byte) in machine_code.iter().enumerate() {
+            memory.data[(debug_info.origin as usize + offset) & 0xFFFF] = byte;
+        }
+        let mut cpu = CPU::new();
+        cpu.set_memory_bus(Arc::new(Mutex::new(memory)));
+        cpu.set_pc(debug_info.origin);
+
+        // 어셈블러 레이블에 외부 심볼 파일(예: ROM 진입점)을 더함
+        let mut symbols = SymbolTable::from_labels(&debug_info.labels);
+        if let Some(symbol_path) = args["symbols"].as_str() {
+            symbols.merge(&SymbolTable::load(symbol_path)?);
+        }
+
+        self.debugger.attach(cpu);
+        self.debugger.load_symbols(symbols);
+        self.debugger.enabled()?;
+        self.debug_info = debug_info;
+        self.source_path = path.to_string();
+        self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+        Ok(Value::Null)
+    }
+
+    /// 소스 줄 브레이크포인트 - 해당 줄 또는 다음 명령어 줄에 설정
+    fn set_breakpoints(&mut self, args: &Value) -> Result<Value> {
+        for address in self.source_breakpoints.drain(..) {
+            self.debugger.remove_breakpoint(address);
+        }
+
+        let lines = args["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints
+                    .iter()
+                    .filter_map(|bp| bp["line"].as_u64())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let mut breakpoints = Vec::new();
+        for line in lines {
+            match self.debug_info.resolve_line(line as usize) {
+                Some(location) => {
+                    let id = self
+                        .debugger
+                        .add_breakpoint(location.address, AccessType::Access);
+                    self.source_breakpoints.push(location.address);
+                    breakpoints.push(json!({
+                        "id": id,
+                        "verified": true,
+                        "line": location.line,
+                    }));
+                }
+                None => breakpoints.push(json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "No instruction at or after this line",
+                })),
+            }
+        }
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    /// 현재 PC와 스택의 JSR 호출 위치로 프레임 구성
+    fn stack_trace(&self) -> Result<Value> {
+        let mut addresses = vec![self.pc()?];
+        addresses.extend(self.debugger.walk_stack()?);
+
+        let frames: Vec<Value> = addresses
+            .iter()
+            .enumerate()
+            .map(|(id, &address)| self.stack_frame(id, address))
+            .collect();
+        Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+    }
+
+    fn stack_frame(&self, id: usize, address: u16) -> Value {
+        let name = match self.debug_info.label_before(address) {
+            Some((label, start)) if start == address => label.to_string(),
+            Some((label, start)) => format!("{}+{}", label, address - start),
+            None => format!("${:04X}", address),
+        };
+        let mut frame = json!({
+            "id": id,
+            "name": name,
+            "line": 0,
+            "column": 0,
+            "instructionPointerReference": format!("${:04X}", address),
+        });
+        if let Some(line) = self.debug_info.line_of(address) {
+            let file_name = Path::new(&self.source_path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            frame["source"] = json!({ "name": file_name, "path": self.source_path });
+            frame["line"] = json!(line);
+            frame["column"] = json!(1);
+        }
+        frame
+    }
+
+    fn variables(&self, args: &Value) -> Result<Value> {
+        let cpu = self.debugger.get_cpu()?;
+        let variables = match args["variablesReference"].as_u64() {
+            Some(REGISTERS_REF) => {
+                let mut registers: Vec<Value> = [
+                    ("A", RegisterType::A),
+                    ("X", RegisterType::X),
+                    ("Y", RegisterType::Y),
+                    ("SP", RegisterType::S),
+                    ("P", RegisterType::P),
+                ]
+                .iter()
+                .map(|&(name, reg)| variable(name, format!("${:02X}", cpu.get_value(reg).as_u8())))
+                .collect();
+                let pc = cpu.get_value(RegisterType::PC).as_u16();
+                registers.push(variable("PC", format!("${:04X}", pc)));
+                registers
+            }
+            Some(FLAGS_REF) => FLAGS
+                .iter()
+                .map(|&(name, flag)| variable(name, (cpu.get_flag(flag) as u8).to_string()))
+                .collect(),
+            Some(ZERO_PAGE_REF) => (0..16u16)
+                .map(|row| {
+                    let bytes = (0..16u16)
+                        .map(|col| self.debugger.read(row * 16 + col))
+                        .collect::<Result<Vec<u8>>>()?;
+                    Ok(variable(&format!("${:04X}", row * 16), hex_bytes(&bytes)))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Some(STACK_REF) => {
+                let sp = cpu.get_value(RegisterType::S).as_u8() as u16;
+                (sp + 1..=0xFF)
+                    .map(|offset| {
+                        let address = 0x0100 + offset;
+                        let value = self.debugger.read(address)?;
+                        Ok(variable(
+                            &format!("${:04X}", address),
+                            format!("${:02X}", value),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+            _ => {
+                return Err(Error::Internal("Unknown variablesReference".to_string()));
+            }
+        };
+        Ok(json!({ "variables": variables }))
+    }
+
+    /// 심볼 또는 주소($XXXX)의 메모리 값 조회
+    fn evaluate(&self, args: &Value) -> Result<Value> {
+        let expression = args["expression"].as_str().unwrap_or_default().trim();
+        let address = self
+            .debugger
+            .symbols()
+            .address_of(expression)
+            .or_else(|| parse_address(expression))
+            .ok_or_else(|| Error::Internal(format!("Cannot evaluate: {}", expression)))?;
+        let value = self.debugger.read(address)?;
+        Ok(json!({
+            "result": format!("${:02X} (${:04X})", value, address),
+            "variablesReference": 0,
+        }))
+    }
+
+    /// 정지할 때까지 실행 - 배치 사이마다 pause 요청 확인
+    fn resume(&mut self) -> io::Result<()> {
+        loop {
+            match self.debugger.run_batch(RUN_BATCH) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => return self.fail(e),
+            }
+            while let Ok(request) = self.requests.try_recv() {
+                if request["command"] == "pause" {
+                    // 다음 명령어 경계에서 UserPause로 정지
+                    let result = self.debugger.pause().map(|_| Value::Null);
+                    self.respond(&request, result)?;
+                } else {
+                    self.pending.push_back(request);
+                }
+            }
+        }
+        self.report_stop()
+    }
+
+    fn step_in(&mut self) -> io::Result<()> {
+        if let Err(e) = self.debugger.step() {
+            return self.fail(e);
+        }
+        self.report_stop()
+    }
+
+    /// JSR이면 복귀 지점까지 실행, 아니면 한 명령어 실행
+    fn step_over(&mut self) -> io::Result<()> {
+        let pc = match self.pc() {
+            Ok(pc) => pc,
+            Err(e) => return self.fail(e),
+        };
+        match self.debugger.read(pc) {
+            Ok(JSR_OPCODE) => self.step_until(pc.wrapping_add(3)),
+            Ok(_) => self.step_in(),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    /// 가장 최근 JSR 호출 위치의 다음 명령어까지 실행
+    fn step_out(&mut self) -> io::Result<()> {
+        match self.debugger.walk_stack() {
+            Ok(call_sites) => match call_sites.first() {
+                Some(&call_site) => self.step_until(call_site.wrapping_add(3)),
+                None => self.step_in(),
+            },
+            Err(e) => self.fail(e),
+        }
+    }
+
+    fn step_until(&mut self, target: u16) -> io::Result<()> {
+        for _ in 0..STEP_LIMIT {
+            match self.debugger.step() {
+                Ok(Some(StopReason::StepComplete)) if self.pc().ok() != Some(target) => {}
+                Ok(_) => break,
+                Err(e) => return self.fail(e),
+            }
+        }
+        self.report_stop()
+    }
+
+    /// CPU 상태에 따라 stopped 또는 종료 이벤트 전송
+    fn report_stop(&mut self) -> io::Result<()> {
+        let state = match self.debugger.get_cpu() {
+            Ok(cpu) => cpu.get_state(),
+            Err(e) => return self.fail(e),
+        };
+        match state {
+            CPUState::Paused(reason) => self.send_event("stopped", stopped_body(reason)),
+            CPUState::Halted(_) => self.terminate(),
+            CPUState::Running | CPUState::Ready => Ok(()),
+        }
+    }
+
+    fn send_stopped(&mut self, reason: &str) -> io::Result<()> {
+        self.send_event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        )
+    }
+
+    fn terminate(&mut self) -> io::Result<()> {
+        if self.terminated {
+            return Ok(());
+        }
+        self.terminated = true;
+        self.send_event("exited", json!({ "exitCode": 0 }))?;
+        self.send_event("terminated", Value::Null)
+    }
+
+    fn fail(&mut self, e: Error) -> io::Result<()> {
+        self.send_event(
+            "output",
+            json!({ "category": "stderr", "output": format!("{}\n", e) }),
+        )?;
+        self.terminate()
+    }
+
+    fn pc(&self) -> Result<u16> {
+        Ok(self
+            .debugger
+            .get_cpu()?
+            .get_value(RegisterType::PC)
+            .as_u16())
+    }
+
+    fn respond(&mut self, request: &Value, result: Result<Value>) -> io::Result<()> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+        });
+        match result {
+            Ok(body) => {
+                response["success"] = json!(true);
+                if !body.is_null() {
+                    response["body"] = body;
+                }
+            }
+            Err(e) => {
+                response["success"] = json!(false);
+                response["message"] = json!(e.to_string());
+            }
+        }
+        self.send(response)
+    }
+
+    fn send_event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        let mut message = json!({ "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+        self.send(message)
+    }
+
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        write_message(&mut self.writer, &message)
+    }
+}
+
+fn scopes() -> Value {
+    let scope = |name: &str, reference: u64| json!({ "name": name, "variablesReference": reference, "expensive": false });
+    json!({
+        "scopes": [
+            scope("Registers", REGISTERS_REF),
+            scope("Flags", FLAGS_REF),
+            scope("Zero Page", ZERO_PAGE_REF),
+            scope("Stack", STACK_REF),
+        ]
+    })
+}
+
+fn stopped_body(reason: StopReason) -> Value {
+    let mut body = json!({ "threadId": THREAD_ID, "allThreadsStopped": true });
+    match reason {
+        StopReason::Breakpoint(id) => {
+            body["reason"] = json!("breakpoint");
+            body["hitBreakpointIds"] = json!([id]);
+        }
+        StopReason::Watchpoint {
+            id,
+            address,
+            access,
+        } => {
+            body["reason"] = json!("data breakpoint");
+            body["hitBreakpointIds"] = json!([id]);
+            body["description"] = json!(format!("{:?} at ${:04X}", access, address));
+        }
+        StopReason::StepComplete => body["reason"] = json!("step"),
+        StopReason::IllegalOpcode(opcode) => {
+            body["reason"] = json!("exception");
+            body["text"] = json!(format!("Illegal opcode ${:02X}", opcode));
+        }
+        StopReason::UserPause => body["reason"] = json!("pause"),
+        StopReason::WriteProtected { address } => {
+            body["reason"] = json!("exception");
+            body["text"] = json!(format!("Write to ROM at ${:04X}", address));
+        }
+    }
+    body
+}
+
+fn variable(name: &str, value: String) -> Value {
+    json!({ "name": name, "value": value, "variablesReference": 0 })
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_address(text: &str) -> Option<u16> {
+    let hex = text.strip_prefix('$').or_else(|| text.strip_prefix("0x"))?;
+    u16::from_str_radix(hex, 16).ok()
+}
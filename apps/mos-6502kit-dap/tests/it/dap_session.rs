@@ -0,0 +1,150 @@
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+const PROGRAM: &str = "START:
+  LDX #$01
+  JSR SUB
+  LDA #$10
+  BRK
+SUB:
+  INX
+  RTS
+";
+
+/// 표준 입출력으로 DAP 서버와 대화하는 클라이언트
+struct DapClient {
+    child: Child,
+    reader: BufReader<ChildStdout>,
+    seq: u64,
+}
+
+impl DapClient {
+    fn spawn() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mos-6502kit-dap"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let reader = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            reader,
+            seq: 0,
+        }
+    }
+
+    fn send(&mut self, command: &str, arguments: Value) {
+        self.seq += 1;
+        let body = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        })
+        .to_string();
+        let stdin = self.child.stdin.as_mut().unwrap();
+        write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        stdin.flush().unwrap();
+    }
+
+    fn read(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            assert!(
+                self.reader.read_line(&mut line).unwrap() > 0,
+                "server closed"
+            );
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        self.reader.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// 요청을 보내고 성공 응답 본문 반환
+    fn request(&mut self, command: &str, arguments: Value) -> Value {
+        self.send(command, arguments);
+        let response = self.read();
+        assert_eq!(response["type"], "response");
+        assert_eq!(response["command"], command);
+        assert_eq!(response["success"], true, "{}", response);
+        response["body"].clone()
+    }
+
+    fn expect_event(&mut self, event: &str) -> Value {
+        let message = self.read();
+        assert_eq!(message["event"], event, "{}", message);
+        message["body"].clone()
+    }
+}
+
+#[test]
+fn test_dap_session_over_stdio() {
+    let path = std::env::temp_dir().join(format!("dap_session_{}.asm", std::process::id()));
+    std::fs::write(&path, PROGRAM).unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let mut client = DapClient::spawn();
+    let capabilities = client.request("initialize", json!({ "adapterID": "mos6502" }));
+    assert_eq!(capabilities["supportsConfigurationDoneRequest"], true);
+
+    client.request("launch", json!({ "program": path, "org": 0x0200 }));
+    client.expect_event("initialized");
+
+    // 6번 줄은 레이블만 있으므로 7번 줄(INX)로 설정
+    let body = client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": path }, "breakpoints": [{ "line": 6 }] }),
+    );
+    assert_eq!(body["breakpoints"][0]["verified"], true);
+    assert_eq!(body["breakpoints"][0]["line"], 7);
+
+    client.request("configurationDone", json!({}));
+    let stopped = client.expect_event("stopped");
+    assert_eq!(stopped["reason"], "breakpoint");
+
+    // 서브루틴 안에서 호출 위치까지 스택 추적
+    let trace = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frames = trace["stackFrames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0]["name"], "SUB");
+    assert_eq!(frames[0]["line"], 7);
+    assert_eq!(frames[1]["line"], 3);
+
+    let scopes = client.request("scopes", json!({ "frameId": 0 }));
+    let registers_ref = scopes["scopes"][0]["variablesReference"].clone();
+    let variables = client.request("variables", json!({ "variablesReference": registers_ref }));
+    let x = variables["variables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["name"] == "X")
+        .unwrap();
+    assert_eq!(x["value"], "$01");
+
+    // 서브루틴을 빠져나와 JSR 다음 줄에서 정지
+    client.request("stepOut", json!({ "threadId": 1 }));
+    assert_eq!(client.expect_event("stopped")["reason"], "step");
+    let trace = client.request("stackTrace", json!({ "threadId": 1 }));
+    assert_eq!(trace["stackFrames"][0]["line"], 4);
+
+    client.request("next", json!({ "threadId": 1 }));
+    assert_eq!(client.expect_event("stopped")["reason"], "step");
+
+    // BRK로 프로그램 종료
+    client.request("continue", json!({ "threadId": 1 }));
+    client.expect_event("exited");
+    client.expect_event("terminated");
+
+    client.request("disconnect", json!({}));
+    assert!(client.child.wait().unwrap().success());
+    let _ = std::fs::remove_file(&path);
+}
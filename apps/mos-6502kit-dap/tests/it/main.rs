@@ -0,0 +1 @@
+pub mod dap_session;
@@ -0,0 +1,75 @@
+//! 명령어 기록 - 파일에 저장해 세션 사이에도 유지
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+pub struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// 기록 파일이 있으면 불러옴 (path가 None이면 메모리에만 보관)
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        Self { entries, path }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// `!!`(직전 명령어)와 `!n`(n번째 명령어)을 실제 명령어로 확장
+    /// 없는 기록을 참조하면 None
+    pub fn expand(&self, line: &str) -> Option<String> {
+        let trimmed = line.trim();
+        if trimmed == "!!" {
+            return self.entries.last().cloned();
+        }
+        match trimmed.strip_prefix('!') {
+            Some(index) if !index.is_empty() => {
+                let index = index.parse::<usize>().ok()?;
+                self.entries.get(index.checked_sub(1)?).cloned()
+            }
+            _ => Some(line.to_string()),
+        }
+    }
+
+    /// 기록 추가 - 빈 줄과 직전과 같은 명령어는 건너뜀
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() || self.entries.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.entries.push(line.to_string());
+
+        if let Some(path) = &self.path
+            && let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path)
+        {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_expand() {
+        let mut history = History::load(None);
+        history.push("m 0200");
+        history.push("r");
+        history.push("r");
+
+        assert_eq!(history.entries(), ["m 0200", "r"]);
+        assert_eq!(history.expand("!!").as_deref(), Some("r"));
+        assert_eq!(history.expand("!1").as_deref(), Some("m 0200"));
+        assert_eq!(history.expand("!9"), None);
+        assert_eq!(history.expand("d 0200").as_deref(), Some("d 0200"));
+    }
+}
@@ -0,0 +1,87 @@
+//! MOS 6502 터미널 모니터 (VICE/Apple II 스타일)
+//! 인자로 주어진 스크립트 파일을 먼저 실행한 뒤 대화형 모드로 진입
+
+mod history;
+mod monitor;
+
+use history::History;
+use monitor::{Monitor, describe_error};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = ".mos-6502kit_history";
+
+fn main() {
+    mos_6502kit::common::trace::set_enabled(false);
+
+    let mut monitor = match Monitor::new() {
+        Ok(monitor) => monitor,
+        Err(e) => {
+            eprintln!("? {}", describe_error(&e));
+            return;
+        }
+    };
+
+    // g 실행 중 Ctrl-C는 종료 대신 일시 정지 (프롬프트에서는 무시)
+    match monitor.pause_handle() {
+        Ok(handle) => {
+            if let Err(e) = ctrlc::set_handler(move || handle.pause()) {
+                eprintln!("? Ctrl-C handler: {}", e);
+            }
+        }
+        Err(e) => eprintln!("? {}", describe_error(&e)),
+    }
+
+    // 스크립트 파일 실행
+    for path in std::env::args().skip(1) {
+        let script = match std::fs::read_to_string(&path) {
+            Ok(script) => script,
+            Err(e) => {
+                eprintln!("? {}: {}", path, e);
+                return;
+            }
+        };
+        for line in script.lines() {
+            run(&mut monitor, line);
+            if monitor.should_quit() {
+                return;
+            }
+        }
+    }
+
+    let history_path = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE));
+    let mut history = History::load(history_path);
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("{}", monitor.prompt());
+        let _ = io::stdout().flush();
+
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        if line.trim() == "history" {
+            for (index, entry) in history.entries().iter().enumerate() {
+                println!("{:4}  {}", index + 1, entry);
+            }
+            continue;
+        }
+        let Some(line) = history.expand(&line) else {
+            println!("? No such history entry");
+            continue;
+        };
+        history.push(&line);
+
+        run(&mut monitor, &line);
+        if monitor.should_quit() {
+            break;
+        }
+    }
+}
+
+fn run(monitor: &mut Monitor, line: &str) {
+    match monitor.execute(line) {
+        Ok(output) if !output.is_empty() => println!("{}", output),
+        Ok(_) => {}
+        Err(e) => println!("? {}", describe_error(&e)),
+    }
+}
@@ -0,0 +1,1438 @@
+//! VICE/Apple II 스타일 모니터 명령어 처리
+use mos_6502kit::assembler::Assembler;
+use mos_6502kit::breadboard::{Machine, MachineDescription, Preset};
+use mos_6502kit::common::{
+    BitmapDump, BytePattern, Charset, Grouping, HexDump, MemoryBus, Result, SymbolTable,
+};
+use mos_6502kit::cpu::register::StatusRegister;
+use mos_6502kit::cpu::{
+    CPU, CPUEvent, CPUState, InterruptType, MemoryAccess, RegisterData, RegisterType, StopReason,
+};
+use mos_6502kit::debugger::{
+    AccessRecord, AccessType, Debugger, HeatmapKind, PauseHandle, ProgramImage, SaveState, Session,
+    SessionMachine,
+};
+use mos_6502kit::disassembler::{DInstruction, Disassembler};
+use mos_6502kit::error::Error;
+use mos_6502kit::memory::{Memory, MemorySnapshot};
+use mos_6502kit::script::ScriptEngine;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 끝 주소 없이 m 실행 시 출력 바이트 수
+const DUMP_SIZE: usize = 128;
+/// 끝 주소 없이 d 실행 시 명령어 수
+const DISASSEMBLE_LINES: usize = 16;
+/// prof 보고서 기본 항목 수
+const PROFILE_LINES: usize = 20;
+/// acc recent 기본 항목 수
+const RECENT_ACCESSES: usize = 16;
+/// g 최대 실행 명령어 수 - 무한 루프에서도 프롬프트로 돌아오기 위함
+const GO_LIMIT: usize = 10_000_000;
+
+pub const HELP: &str = "\
+m [start [end [opt..]]]  memory dump; options: w|d (words/dwords), petscii|atascii
+bm start end [width]     binary/bitmap view, width bytes per row (default 1)
+d [start [end]]          disassemble (default: from PC)
+r [reg=value ...]        show/set registers (a x y sp p pc)
+a start [instruction]    assemble one instruction, or enter assemble mode (empty line ends)
+g [address]              run until breakpoint/BRK (Ctrl-C pauses)
+t [count]                trace (single-step) count instructions
+bt                       backtrace from the shadow call stack
+prof [on|off|clear|n]    profiler: start/stop/reset, or report top n (default 20)
+prof save \"file\"         save collapsed stacks (flamegraph.pl, inferno)
+cov [on|off|clear]       coverage recording, or summary
+cov list start end       listing marked X(exec) R(read) W(write), branches T/N/B
+cov save \"file\"          save 64KB coverage bitmap (one flag byte per address)
+acc [on|off|clear]       memory access log
+acc address              access counts, last writer and recent accesses of address
+acc recent [n]           last n accesses (default 16)
+acc heat \"file\" [r|w]    save heatmap (.ppm image, otherwise CSV grid)
+bk [address]             list/add execution breakpoints
+w [address [r|w]]        list/add watchpoints (default: write)
+del address              delete breakpoint/watchpoint
+ll \"file\"                load labels (VICE, ca65 .dbg, or NAME = $addr list)
+al address name          add label
+shl                      show labels
+script \"file\"            run a Rhai script (read/write/patch, reg/set_reg, step/run,
+                         break_at(addr, || cond), watch, on_instruction(|addr| ..),
+                         symbol, disasm, hex, assert)
+eval code                run Rhai code; breakpoint actions apply while the script runs
+l \"file\" address         load binary file
+machine [ram|NAME \"rom\"|\"file\"]  show/replace machine: 64KB RAM, preset
+                         (apple1 kim1 ben-eater acia-sbc) with ROM, or description TOML
+session save \"file\" [state]  save machine, breakpoints, loaded files and labels (+ state)
+session load \"file\"       restore a saved session
+s \"file\" start end       save memory range to file
+f start end byte...      fill range with pattern
+h start end byte...      hunt for byte pattern (`??` matches any byte)
+c start end dest         compare range with dest, list differing addresses
+mv start end dest        copy range to dest (ranges may overlap)
+crc start end            CRC-32 of range
+snap                     take a memory snapshot
+diff                     show bytes changed since the snapshot (old -> new)
+history, !!, !n          command history
+x                        exit
+Numbers are hex; `$` or `0x` prefix is optional. Addresses may be labels (`.NAME`).
+`;` starts a comment.";
+
+pub struct Monitor {
+    // 64KB RAM 또는 머신의 주소 디코더
+    memory: Arc<Mutex<dyn MemoryBus>>,
+    debugger: Debugger,
+    next_dump: u16,
+    // None이면 PC부터 디스어셈블
+    next_disassemble: Option<u16>,
+    // 어셈블 모드일 때 다음 명령어 주소
+    assemble_address: Option<u16>,
+    // 세션 저장용 - 적재한 파일과 추가한 레이블 기록
+    session: Session,
+    // diff 명령의 비교 기준
+    snapshot: Option<MemorySnapshot>,
+    quit: bool,
+}
+
+impl Monitor {
+    pub fn new() -> Result<Self> {
+        let (memory, cpu) = Self::build(None)?;
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger.enabled()?;
+
+        Ok(Self {
+            memory,
+            debugger,
+            next_dump: 0,
+            next_disassemble: None,
+            assemble_address: None,
+            session: Session::new(),
+            snapshot: None,
+            quit: false,
+        })
+    }
+
+    /// 메모리와 CPU 구성 - 머신이면 명령어마다 실제 사이클만큼 장치 진행
+    fn build(machine: Option<&SessionMachine>) -> Result<(Arc<Mutex<dyn MemoryBus>>, CPU)> {
+        let machine = match machine {
+            None => {
+                let memory: Arc<Mutex<dyn MemoryBus>> = Arc::new(Mutex::new(Memory::new()));
+                let mut cpu = CPU::new();
+                cpu.set_memory_bus(memory.clone());
+                return Ok((memory, cpu));
+            }
+            Some(SessionMachine::Preset { name, rom }) => {
+                Machine::new(name.parse::<Preset>()?, rom)?
+            }
+            Some(SessionMachine::Description(path)) => {
+                MachineDescription::load(path)?.build_machine()?
+            }
+        };
+        let mut board = machine.board;
+        let bus = board.bus();
+        let mut cpu = std::mem::replace(&mut board.cpu, CPU::new());
+        let devices = bus.clone();
+        cpu.register_event_handler(Box::new(move |event| {
+            if let CPUEvent::InstructionExecuted { cycles, .. } = event {
+                devices.lock().unwrap().tick(*cycles as u64);
+            }
+        }));
+        Ok((bus, cpu))
+    }
+
+    /// 머신 교체 - 레이블과 브레이크포인트는 유지
+    fn set_machine(&mut self, machine: Option<SessionMachine>) -> Result<()> {
+        let (memory, cpu) = Self::build(machine.as_ref())?;
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger.enabled()?;
+        debugger.load_symbols(self.debugger.symbols().clone());
+        let mut carried = Session::new();
+        carried.capture_breakpoints(&self.debugger);
+        carried.apply(&mut debugger)?;
+
+        self.memory = memory;
+        self.debugger = debugger;
+        self.session.machine = machine;
+        self.next_disassemble = None;
+        self.snapshot = None;
+        Ok(())
+    }
+
+    pub fn prompt(&self) -> String {
+        match self.assemble_address {
+            Some(address) => format!("a {:04X}  ", address),
+            None => format!("(C:${:04X}) ", self.pc()),
+        }
+    }
+
+    /// g 실행 중 Ctrl-C로 멈추기 위한 핸들
+    pub fn pause_handle(&self) -> Result<PauseHandle> {
+        self.debugger.pause_handle()
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// 명령어 한 줄 실행 후 출력 텍스트 반환
+    pub fn execute(&mut self, line: &str) -> Result<String> {
+        // eval 뒤는 Rhai 코드 그대로 (`;`와 따옴표 포함)
+        if self.assemble_address.is_none()
+            && let Some(code) = line.trim_start().strip_prefix("eval ")
+        {
+            return self.run_script(code);
+        }
+        let line = line.split(';').next().unwrap_or_default();
+        if let Some(address) = self.assemble_address {
+            return self.assemble_line(address, line.trim(), true);
+        }
+
+        let args = split_args(line);
+        let Some((command, args)) = args.split_first() else {
+            return Ok(String::new());
+        };
+        match command.to_ascii_lowercase().as_str() {
+            "m" => self.memory_dump(args),
+            "bm" => self.bitmap_dump(args),
+            "d" => self.disassemble(args),
+            "r" => self.registers(args),
+            "a" => self.assemble(args),
+            "g" => self.go(args),
+            "t" => self.trace(args),
+            "bt" => self.backtrace(),
+            "prof" => self.profile(args),
+            "cov" => self.coverage(args),
+            "acc" => self.access_log(args),
+            "script" => {
+                let path = args
+                    .first()
+                    .ok_or_else(|| Error::Internal("Usage: script \"file\"".to_string()))?;
+                let script = fs::read_to_string(path)?;
+                self.run_script(&script)
+            }
+            "bk" => self.breakpoint(args),
+            "w" => self.watchpoint(args),
+            "del" => self.delete(args),
+            "ll" => self.load_labels(args),
+            "al" => self.add_label(args),
+            "shl" => Ok(self.show_labels()),
+            "l" => self.load(args),
+            "machine" => self.machine(args),
+            "session" => self.session_command(args),
+            "s" => self.save(args),
+            "f" => self.fill(args),
+            "h" => self.hunt(args),
+            "c" => self.compare(args),
+            "mv" => self.copy(args),
+            "crc" => self.checksum(args),
+            "snap" => {
+                self.snapshot = Some(MemorySnapshot::capture(&*self.memory.lock().unwrap()));
+                Ok("Snapshot taken".to_string())
+            }
+            "diff" => {
+                let snapshot = self
+                    .snapshot
+                    .as_ref()
+                    .ok_or_else(|| Error::Internal("No snapshot; use `snap` first".to_string()))?;
+                let diff = snapshot.diff_live(&*self.memory.lock().unwrap());
+                Ok(match diff.is_empty() {
+                    true => "No changes".to_string(),
+                    false => format!("{}\n{} bytes changed", diff, diff.changed_bytes()),
+                })
+            }
+            "x" | "q" => {
+                self.quit = true;
+                Ok(String::new())
+            }
+            "?" | "help" => Ok(HELP.to_string()),
+            other => Err(Error::Internal(format!("Unknown command: {}", other))),
+        }
+    }
+
+    fn memory_dump(&mut self, args: &[String]) -> Result<String> {
+        let start = self.optional_address(args, 0)?.unwrap_or(self.next_dump);
+        let size = match self.optional_address(args, 1)? {
+            Some(end) => range_size(start, end)?,
+            None => DUMP_SIZE,
+        };
+        let mut renderer = HexDump::new();
+        for option in args.iter().skip(2) {
+            renderer = match option.to_ascii_lowercase().as_str() {
+                "w" => renderer.with_grouping(Grouping::Word),
+                "d" => renderer.with_grouping(Grouping::DWord),
+                "petscii" => renderer.with_charset(Charset::Petscii),
+                "atascii" => renderer.with_charset(Charset::Atascii),
+                other => return Err(Error::Internal(format!("Unknown dump option: {}", other))),
+            };
+        }
+        self.next_dump = start.wrapping_add(size as u16);
+        let dump = self
+            .memory
+            .lock()
+            .unwrap()
+            .dump_with(start, size, &renderer);
+        Ok(dump.trim_end().to_string())
+    }
+
+    fn bitmap_dump(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let width = self.optional_address(args, 2)?.unwrap_or(1);
+        let size = range_size(*range.start(), *range.end())?;
+        let renderer = BitmapDump::new().with_width(width as usize);
+        let dump = self
+            .memory
+            .lock()
+            .unwrap()
+            .dump_with(*range.start(), size, &renderer);
+        Ok(dump.trim_end().to_string())
+    }
+
+    fn disassemble(&mut self, args: &[String]) -> Result<String> {
+        let start = match self.optional_address(args, 0)? {
+            Some(address) => address,
+            None => self.next_disassemble.unwrap_or(self.pc()),
+        };
+        let instructions = match self.optional_address(args, 1)? {
+            Some(end) => {
+                let bytes = self.read_range(start, range_size(start, end)?)?;
+                self.disassembler(bytes, start).disassemble()?
+            }
+            None => self.disassemble_at(start, DISASSEMBLE_LINES)?,
+        };
+
+        if let Some(last) = instructions.last() {
+            let length = 1 + last.operand.len() as u16;
+            self.next_disassemble = Some(last.address.wrapping_add(length));
+        }
+        Ok(instructions
+            .iter()
+            .map(format_instruction)
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn registers(&mut self, args: &[String]) -> Result<String> {
+        // 하나라도 잘못되면 아무 레지스터도 바꾸지 않도록 먼저 모두 해석
+        let mut assignments = Vec::with_capacity(args.len());
+        for assignment in args {
+            let (name, value) = assignment.split_once('=').ok_or_else(|| {
+                Error::Internal(format!("Expected reg=value, got {}", assignment))
+            })?;
+            let register = match name.to_ascii_lowercase().as_str() {
+                "a" => RegisterType::A,
+                "x" => RegisterType::X,
+                "y" => RegisterType::Y,
+                "sp" | "s" => RegisterType::S,
+                "p" => RegisterType::P,
+                "pc" => RegisterType::PC,
+                _ => return Err(Error::Internal(format!("Unknown register: {}", name))),
+            };
+            let value = self.parse_address(value)?;
+            let data = if matches!(register, RegisterType::PC) {
+                RegisterData::Bit16(value)
+            } else {
+                RegisterData::Bit8(
+                    u8::try_from(value)
+                        .map_err(|_| Error::Internal(format!("Not a byte: {}", assignment)))?,
+                )
+            };
+            assignments.push((register, data));
+        }
+        let cpu = self.debugger.get_cpu_mut()?;
+        for (register, data) in assignments {
+            cpu.set_value(register, data);
+        }
+        Ok(format!(
+            "  ADDR A  X  Y  SP NV-BDIZC\n{}",
+            self.register_line()?
+        ))
+    }
+
+    fn assemble(&mut self, args: &[String]) -> Result<String> {
+        let address = self.required_address(args, 0)?;
+        if args.len() == 1 {
+            // 주소만 주면 빈 줄이 입력될 때까지 어셈블 모드
+            self.assemble_address = Some(address);
+            return Ok(String::new());
+        }
+        self.assemble_line(address, &args[1..].join(" "), false)
+    }
+
+    fn assemble_line(&mut self, address: u16, source: &str, continuous: bool) -> Result<String> {
+        if source.is_empty() {
+            self.assemble_address = None;
+            return Ok(String::new());
+        }
+        let machine_code = Assembler::new(address).assemble(source)?;
+        for (offset, &byte) in machine_code.iter().enumerate() {
+            self.debugger
+                .write(address.wrapping_add(offset as u16), byte)?;
+        }
+        if continuous {
+            self.assemble_address = Some(address.wrapping_add(machine_code.len() as u16));
+        }
+
+        let instructions = self.disassemble_at(address, 1)?;
+        Ok(instructions.iter().map(format_instruction).collect())
+    }
+
+    fn go(&mut self, args: &[String]) -> Result<String> {
+        self.prepare_run(args)?;
+        let still_running = self.debugger.run_batch(GO_LIMIT)?;
+        let mut output = String::new();
+        if still_running {
+            self.debugger.get_cpu_mut()?.pause(StopReason::UserPause);
+            output.push_str(&format!(
+                "Stopped after {} instructions (still running)\n",
+                GO_LIMIT
+            ));
+        }
+        output.push_str(&self.stop_description()?);
+        output.push('\n');
+        output.push_str(&self.register_line()?);
+        Ok(output)
+    }
+
+    fn trace(&mut self, args: &[String]) -> Result<String> {
+        let count = match args.first() {
+            Some(count) => count
+                .parse::<usize>()
+                .map_err(|_| Error::Internal(format!("Invalid count: {}", count)))?,
+            None => 1,
+        };
+        self.prepare_run(&[])?;
+
+        let mut lines = Vec::new();
+        for _ in 0..count {
+            let instruction = self.disassemble_at(self.pc(), 1)?;
+            let reason = self.debugger.step()?;
+            lines.push(format!(
+                "{:<32}{}",
+                instruction
+                    .iter()
+                    .map(format_instruction)
+                    .collect::<String>(),
+                self.register_summary()?
+            ));
+            if reason != Some(StopReason::StepComplete) {
+                lines.push(self.stop_description()?);
+                break;
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn backtrace(&self) -> Result<String> {
+        let mut lines = self.debugger.backtrace()?;
+        let anomalies = self.debugger.stack_anomalies();
+        if !anomalies.is_empty() {
+            lines.push("Stack manipulation detected:".to_string());
+            lines.extend(anomalies.iter().map(|anomaly| format!("  {}", anomaly)));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn profile(&mut self, args: &[String]) -> Result<String> {
+        match args.first().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+            Some("on") => self.debugger.set_profiling(true),
+            Some("off") => self.debugger.set_profiling(false),
+            Some("clear") => self.debugger.clear_profile(),
+            Some("save") => {
+                let path = args
+                    .get(1)
+                    .ok_or_else(|| Error::Internal("Usage: prof save \"file\"".to_string()))?;
+                fs::write(path, self.debugger.profile_collapsed() + "\n")?;
+            }
+            Some(count) => {
+                let limit = count
+                    .parse::<usize>()
+                    .map_err(|_| Error::Internal(format!("Invalid count: {}", count)))?;
+                return self.debugger.profile_report(limit);
+            }
+            None => return self.debugger.profile_report(PROFILE_LINES),
+        }
+        Ok(format!(
+            "Profiling {}",
+            if self.debugger.is_profiling() {
+                "on"
+            } else {
+                "off"
+            }
+        ))
+    }
+
+    fn coverage(&mut self, args: &[String]) -> Result<String> {
+        match args.first().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+            Some("on") => self.debugger.set_coverage(true),
+            Some("off") => self.debugger.set_coverage(false),
+            Some("clear") => self.debugger.clear_coverage(),
+            Some("list") => {
+                let start = self.required_address(args, 1)?;
+                let end = self.required_address(args, 2)?;
+                range_size(start, end)?;
+                return self.debugger.coverage_listing(start, end);
+            }
+            Some("save") => {
+                let path = args
+                    .get(1)
+                    .ok_or_else(|| Error::Internal("Usage: cov save \"file\"".to_string()))?;
+                fs::write(path, self.debugger.coverage_bitmap())?;
+            }
+            Some(other) => {
+                return Err(Error::Internal(format!(
+                    "Unknown coverage command: {}",
+                    other
+                )));
+            }
+            None => {
+                let summary = self.debugger.coverage_summary(0x0000, 0xFFFF);
+                return Ok(format!(
+                    "Executed: {} opcode bytes, {} operand bytes\n\
+                     Data: {} bytes read, {} bytes written\n\
+                     Branches: {} executed, {} taken both ways",
+                    summary.opcode_bytes,
+                    summary.operand_bytes,
+                    summary.read_bytes,
+                    summary.written_bytes,
+                    summary.branches,
+                    summary.full_branches
+                ));
+            }
+        }
+        Ok(format!(
+            "Coverage {}",
+            if self.debugger.is_coverage_enabled() {
+                "on"
+            } else {
+                "off"
+            }
+        ))
+    }
+
+    /// 디버거를 스크립트 엔진에 잠시 넘겨 실행
+    fn run_script(&mut self, script: &str) -> Result<String> {
+        let mut engine = ScriptEngine::new(std::mem::take(&mut self.debugger));
+        let result = engine.eval(script);
+        self.debugger = engine.into_debugger()?;
+        result
+    }
+
+    fn access_log(&mut self, args: &[String]) -> Result<String> {
+        match args.first().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+            Some("on") => self.debugger.set_access_log(true),
+            Some("off") => self.debugger.set_access_log(false),
+            Some("clear") => self.debugger.clear_access_log(),
+            Some("recent") => {
+                let count = match args.get(1) {
+                    Some(count) => count
+                        .parse::<usize>()
+                        .map_err(|_| Error::Internal(format!("Invalid count: {}", count)))?,
+                    None => RECENT_ACCESSES,
+                };
+                let recent = self.debugger.recent_accesses();
+                let skip = recent.len().saturating_sub(count);
+                return Ok(self.format_accesses(&recent[skip..]));
+            }
+            Some("heat") => {
+                let path = args
+                    .get(1)
+                    .ok_or_else(|| Error::Internal("Usage: acc heat \"file\" [r|w]".to_string()))?;
+                if path.to_ascii_lowercase().ends_with(".ppm") {
+                    fs::write(path, self.debugger.heatmap_ppm())?;
+                } else {
+                    let kind = match args.get(2).map(|kind| kind.to_ascii_lowercase()).as_deref() {
+                        Some("r") => HeatmapKind::Reads,
+                        Some("w") => HeatmapKind::Writes,
+                        _ => HeatmapKind::Total,
+                    };
+                    fs::write(path, self.debugger.heatmap_csv(kind))?;
+                }
+            }
+            Some(_) => {
+                let address = self.required_address(args, 0)?;
+                let stats = self.debugger.access_stats(address);
+                let mut lines = vec![format!(
+                    "${:04X}{}: {} reads, {} writes",
+                    address,
+                    self.label_suffix(address),
+                    stats.reads,
+                    stats.writes
+                )];
+                if let Some(writer) = stats.last_writer {
+                    lines.push(format!(
+                        "Last writer: ${:04X}  {}",
+                        writer,
+                        self.debugger.symbols().describe(writer)
+                    ));
+                }
+                if let Some(cycle) = stats.last_access_cycle {
+                    lines.push(format!("Last access: cycle {}", cycle));
+                }
+                let history = self.debugger.access_history(address);
+                if !history.is_empty() {
+                    lines.push(self.format_accesses(&history));
+                }
+                return Ok(lines.join("\n"));
+            }
+            None => {}
+        }
+        Ok(format!(
+            "Access log {}",
+            if self.debugger.is_access_log_enabled() {
+                "on"
+            } else {
+                "off"
+            }
+        ))
+    }
+
+    fn format_accesses(&self, records: &[AccessRecord]) -> String {
+        records
+            .iter()
+            .map(|record| {
+                let access = match record.access {
+                    MemoryAccess::Read => 'R',
+                    MemoryAccess::Write => 'W',
+                };
+                format!(
+                    "{:>10}  ${:04X}  {} ${:04X} ${:02X}",
+                    record.cycle, record.pc, access, record.address, record.value
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn breakpoint(&mut self, args: &[String]) -> Result<String> {
+        match self.optional_address(args, 0)? {
+            Some(address) => {
+                let id = self.debugger.add_breakpoint(address, AccessType::Access);
+                Ok(format!(
+                    "BREAK: {}  ${:04X}{}",
+                    id,
+                    address,
+                    self.label_suffix(address)
+                ))
+            }
+            None => Ok(self.list_breakpoints(true)),
+        }
+    }
+
+    fn watchpoint(&mut self, args: &[String]) -> Result<String> {
+        let Some(address) = self.optional_address(args, 0)? else {
+            return Ok(self.list_breakpoints(false));
+        };
+        let access_type = match args.get(1).map(|kind| kind.to_ascii_lowercase()) {
+            None => AccessType::Write,
+            Some(kind) if kind == "w" => AccessType::Write,
+            Some(kind) if kind == "r" => AccessType::Read,
+            Some(kind) => {
+                return Err(Error::Internal(format!(
+                    "Unknown watch type: {} (use r or w)",
+                    kind
+                )));
+            }
+        };
+        let id = self.debugger.add_breakpoint(address, access_type.clone());
+        Ok(format!("WATCH: {}  ${:04X} {:?}", id, address, access_type))
+    }
+
+    fn list_breakpoints(&self, execution: bool) -> String {
+        self.debugger
+            .breakpoints()
+            .iter()
+            .filter(|bp| (bp.access_type == AccessType::Access) == execution)
+            .map(|bp| {
+                let state = if bp.enabled { "" } else { " (disabled)" };
+                let label = self.label_suffix(bp.address);
+                if execution {
+                    format!("BREAK: {}  ${:04X}{}{}", bp.id, bp.address, label, state)
+                } else {
+                    format!(
+                        "WATCH: {}  ${:04X} {:?}{}{}",
+                        bp.id, bp.address, bp.access_type, label, state
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn delete(&mut self, args: &[String]) -> Result<String> {
+        let address = self.required_address(args, 0)?;
+        self.debugger.remove_breakpoint(address);
+        Ok(String::new())
+    }
+
+    fn load_labels(&mut self, args: &[String]) -> Result<String> {
+        let path = args
+            .first()
+            .ok_or_else(|| Error::Internal("Usage: ll \"file\"".to_string()))?;
+        let loaded = SymbolTable::load(path)?;
+        self.session.symbol_files.push(PathBuf::from(path));
+        let mut symbols = self.debugger.symbols().clone();
+        symbols.merge(&loaded);
+        self.debugger.load_symbols(symbols);
+        Ok(format!("Loaded {} labels", loaded.len()))
+    }
+
+    fn add_label(&mut self, args: &[String]) -> Result<String> {
+        let address = self.required_address(args, 0)?;
+        let name = args
+            .get(1)
+            .ok_or_else(|| Error::Internal("Usage: al address name".to_string()))?;
+        let name = name.trim_start_matches('.');
+        self.session.labels.insert(name.to_string(), address);
+        let mut symbols = self.debugger.symbols().clone();
+        symbols.insert(name, address);
+        self.debugger.load_symbols(symbols);
+        Ok(String::new())
+    }
+
+    fn show_labels(&self) -> String {
+        self.debugger
+            .symbols()
+            .iter()
+            .map(|(name, address)| format!("${:04X} .{}", address, name))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn load(&mut self, args: &[String]) -> Result<String> {
+        let path = args
+            .first()
+            .ok_or_else(|| Error::Internal("Usage: l \"file\" address".to_string()))?;
+        let address = self.required_address(args, 1)?;
+        let data = fs::read(path)?;
+        let size = data.len();
+        if address as usize + size > 0x10000 {
+            return Err(Error::InvalidMemoryRange(address));
+        }
+        self.memory.lock().unwrap().write_block(address, &data);
+        self.session.programs.push(ProgramImage {
+            path: PathBuf::from(path),
+            address,
+        });
+
+        let end = address.wrapping_add(size.saturating_sub(1) as u16);
+        Ok(format!(
+            "Loaded {} bytes: ${:04X}-${:04X}",
+            size, address, end
+        ))
+    }
+
+    fn machine(&mut self, args: &[String]) -> Result<String> {
+        let machine = match args {
+            [] => {
+                return Ok(match &self.session.machine {
+                    None => "Machine: 64KB RAM".to_string(),
+                    Some(SessionMachine::Preset { name, rom }) => {
+                        format!("Machine: {} ({})", name, rom.display())
+                    }
+                    Some(SessionMachine::Description(path)) => {
+                        format!("Machine: {}", path.display())
+                    }
+                });
+            }
+            [ram] if ram.eq_ignore_ascii_case("ram") => None,
+            [path] => Some(SessionMachine::Description(PathBuf::from(path))),
+            [name, rom] => Some(SessionMachine::Preset {
+                name: name.to_ascii_lowercase(),
+                rom: PathBuf::from(rom),
+            }),
+            _ => {
+                return Err(Error::Internal(
+                    "Usage: machine [ram|NAME \"rom\"|\"file\"]".to_string(),
+                ));
+            }
+        };
+        self.set_machine(machine)?;
+        // 이전 메모리에 적재한 프로그램은 새 머신에 없음
+        self.session.programs.clear();
+        self.machine(&[])
+    }
+
+    fn session_command(&mut self, args: &[String]) -> Result<String> {
+        let usage = || Error::Internal("Usage: session save|load \"file\"".to_string());
+        let path = Path::new(args.get(1).ok_or_else(usage)?);
+        match args[0].to_ascii_lowercase().as_str() {
+            "save" => {
+                let mut session = self.session.clone();
+                session.capture_breakpoints(&self.debugger);
+                if args
+                    .get(2)
+                    .is_some_and(|arg| arg.eq_ignore_ascii_case("state"))
+                {
+                    // 세이브 스테이트는 세션 파일 옆에 같은 이름으로
+                    let state_path = path.with_extension("state");
+                    SaveState::capture(&self.debugger)?.save(&state_path)?;
+                    session.state = Some(state_path);
+                }
+                session.save(path)?;
+                Ok(format!("Session saved: {}", path.display()))
+            }
+            "load" => {
+                let session = Session::load(path)?;
+                self.set_machine(session.machine.clone())?;
+                for breakpoint in self.debugger.breakpoints() {
+                    self.debugger.remove_breakpoint(breakpoint.address);
+                }
+                session.apply(&mut self.debugger)?;
+                let summary = format!(
+                    "Session loaded: {} programs, {} breakpoints",
+                    session.programs.len(),
+                    session.breakpoints.len()
+                );
+                self.session = session;
+                self.session.state = None;
+                Ok(summary)
+            }
+            _ => Err(usage()),
+        }
+    }
+
+    fn save(&mut self, args: &[String]) -> Result<String> {
+        let path = args
+            .first()
+            .ok_or_else(|| Error::Internal("Usage: s \"file\" start end".to_string()))?;
+        let start = self.required_address(args, 1)?;
+        let end = self.required_address(args, 2)?;
+        let data = self.read_range(start, range_size(start, end)?)?;
+        fs::write(path, &data)?;
+        Ok(format!(
+            "Saved {} bytes: ${:04X}-${:04X}",
+            data.len(),
+            start,
+            end
+        ))
+    }
+
+    fn fill(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let pattern = parse_pattern(&args[2..])?;
+        self.memory.lock().unwrap().fill(range, &pattern);
+        Ok(String::new())
+    }
+
+    fn hunt(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let pattern = parse_wildcard_pattern(&args[2..])?;
+        let found = self.memory.lock().unwrap().find(range, &pattern);
+        Ok(format_addresses(&found))
+    }
+
+    fn compare(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let dest = self.required_address(args, 2)?;
+        let differ = self.memory.lock().unwrap().compare(range, dest);
+        Ok(format_addresses(&differ))
+    }
+
+    fn copy(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let dest = self.required_address(args, 2)?;
+        self.memory.lock().unwrap().copy(range, dest);
+        Ok(String::new())
+    }
+
+    fn checksum(&mut self, args: &[String]) -> Result<String> {
+        let range = self.required_range(args)?;
+        let crc = self.memory.lock().unwrap().checksum(range);
+        Ok(format!("{:08X}", crc))
+    }
+
+    /// 실행 전 준비 - 주소가 주어지면 PC 설정, BRK로 정지된 CPU는 다시 실행 가능하게
+    fn prepare_run(&mut self, args: &[String]) -> Result<()> {
+        let address = self.optional_address(args, 0)?;
+        let cpu = self.debugger.get_cpu_mut()?;
+        if let Some(address) = address {
+            cpu.set_pc(address);
+        }
+        if let CPUState::Halted(_) = cpu.get_state() {
+            cpu.pause(StopReason::UserPause);
+        }
+        Ok(())
+    }
+
+    fn stop_description(&self) -> Result<String> {
+        let description = match self.debugger.get_cpu()?.get_state() {
+            CPUState::Paused(StopReason::Breakpoint(id)) => {
+                format!(
+                    "BREAK: {}  ${:04X}{}",
+                    id,
+                    self.pc(),
+                    self.label_suffix(self.pc())
+                )
+            }
+            CPUState::Paused(StopReason::Watchpoint {
+                id,
+                address,
+                access,
+            }) => format!("WATCH: {}  ${:04X} {:?}", id, address, access),
+            CPUState::Paused(StopReason::IllegalOpcode(opcode)) => {
+                format!("Illegal opcode ${:02X} at ${:04X}", opcode, self.pc())
+            }
+            CPUState::Paused(StopReason::StepComplete) => "Step".to_string(),
+            CPUState::Paused(StopReason::UserPause) => "Paused".to_string(),
+            CPUState::Paused(StopReason::WriteProtected { address }) => {
+                format!("ROM write: ${:04X} at ${:04X}", address, self.pc())
+            }
+            CPUState::Halted(InterruptType::BRK) => "BRK".to_string(),
+            CPUState::Halted(reason) => format!("Halted: {:?}", reason),
+            CPUState::Running | CPUState::Ready => "Ready".to_string(),
+        };
+        Ok(description)
+    }
+
+    fn register_line(&self) -> Result<String> {
+        let cpu = self.debugger.get_cpu()?;
+        Ok(format!(
+            ".;{:04X} {:02X} {:02X} {:02X} {:02X} {:08b}",
+            cpu.get_value(RegisterType::PC).as_u16(),
+            cpu.get_value(RegisterType::A).as_u8(),
+            cpu.get_value(RegisterType::X).as_u8(),
+            cpu.get_value(RegisterType::Y).as_u8(),
+            cpu.get_value(RegisterType::S).as_u8(),
+            cpu.status_flag().bits(),
+        ))
+    }
+
+    fn register_summary(&self) -> Result<String> {
+        let cpu = self.debugger.get_cpu()?;
+        let flags: String = [
+            (StatusRegister::NEGATIVE, 'N'),
+            (StatusRegister::OVERFLOW, 'V'),
+            (StatusRegister::UNUSED, '-'),
+            (StatusRegister::BREAK, 'B'),
+            (StatusRegister::DECIMAL, 'D'),
+            (StatusRegister::INTERRUPT_DISABLE, 'I'),
+            (StatusRegister::ZERO, 'Z'),
+            (StatusRegister::CARRY, 'C'),
+        ]
+        .iter()
+        .map(|&(flag, name)| if cpu.get_flag(flag) { name } else { '.' })
+        .collect();
+        Ok(format!(
+            "A={:02X} X={:02X} Y={:02X} SP={:02X} {}",
+            cpu.get_value(RegisterType::A).as_u8(),
+            cpu.get_value(RegisterType::X).as_u8(),
+            cpu.get_value(RegisterType::Y).as_u8(),
+            cpu.get_value(RegisterType::S).as_u8(),
+            flags,
+        ))
+    }
+
+    fn disassembler(&self, bytes: Vec<u8>, origin: u16) -> Disassembler<'_> {
+        let mut disassembler = Disassembler::with_origin(bytes, origin);
+        disassembler.set_symbols(self.debugger.symbols());
+        disassembler
+    }
+
+    fn disassemble_at(&self, address: u16, count: usize) -> Result<Vec<DInstruction>> {
+        // 명령어는 최대 3바이트
+        let bytes = self.read_range(address, count * 3)?;
+        let mut instructions = self.disassembler(bytes, address).disassemble()?;
+        instructions.truncate(count);
+        Ok(instructions)
+    }
+
+    /// 16진수 주소 또는 심볼 이름 (`.NAME`은 항상 심볼로 해석)
+    fn parse_address(&self, text: &str) -> Result<u16> {
+        let symbols = self.debugger.symbols();
+        if let Some(name) = text.strip_prefix('.') {
+            return symbols
+                .address_of(name)
+                .ok_or_else(|| Error::UnknownSymbol(name.to_string()));
+        }
+        parse_address(text).or_else(|_| {
+            symbols
+                .address_of(text)
+                .ok_or_else(|| Error::UnknownSymbol(text.to_string()))
+        })
+    }
+
+    fn optional_address(&self, args: &[String], index: usize) -> Result<Option<u16>> {
+        args.get(index)
+            .map(|arg| self.parse_address(arg))
+            .transpose()
+    }
+
+    fn required_address(&self, args: &[String], index: usize) -> Result<u16> {
+        self.optional_address(args, index)?
+            .ok_or_else(|| Error::Internal("Missing address argument".to_string()))
+    }
+
+    /// 첫 두 인자를 start..=end 범위로
+    fn required_range(&self, args: &[String]) -> Result<RangeInclusive<u16>> {
+        let start = self.required_address(args, 0)?;
+        let end = self.required_address(args, 1)?;
+        range_size(start, end)?;
+        Ok(start..=end)
+    }
+
+    /// 주소에 해당하는 심볼이 있으면 `  NAME` 형태로
+    fn label_suffix(&self, address: u16) -> String {
+        match self.debugger.symbols().name_at(address) {
+            Some(name) => format!("  {}", name),
+            None => String::new(),
+        }
+    }
+
+    fn read_range(&self, start: u16, size: usize) -> Result<Vec<u8>> {
+        (0..size)
+            .map(|offset| self.debugger.read(start.wrapping_add(offset as u16)))
+            .collect()
+    }
+
+    fn pc(&self) -> u16 {
+        self.debugger
+            .get_cpu()
+            .map(|cpu| cpu.get_value(RegisterType::PC).as_u16())
+            .unwrap_or_default()
+    }
+}
+
+/// 모니터 출력용 오류 메시지
+pub fn describe_error(error: &Error) -> String {
+    match error {
+        Error::Internal(message) => message.clone(),
+        Error::Io(e) => e.to_string(),
+        Error::UnknownSymbol(name) => format!("Unknown label: {}", name),
+        Error::InvalidSymbolFile { line } => format!("Invalid label file (line {})", line),
+        Error::Script(message) => message.clone(),
+        Error::InvalidSession(message) => format!("Invalid session: {}", message),
+        other => other.to_string(),
+    }
+}
+
+fn format_instruction(instruction: &DInstruction) -> String {
+    let bytes = std::iter::once(instruction.opcode)
+        .chain(instruction.operand.iter().copied())
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        ".{:04X}  {:<9} {}",
+        instruction.address, bytes, instruction.mnemonic
+    )
+}
+
+/// 공백으로 인자 분리 - 큰따옴표로 묶인 부분은 하나로 취급
+fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+fn parse_address(text: &str) -> Result<u16> {
+    let hex = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0x"))
+        .unwrap_or(text);
+    u16::from_str_radix(hex, 16).map_err(|_| Error::Internal(format!("Invalid number: {}", text)))
+}
+
+/// 바이트 목록 - `??`는 아무 바이트
+fn parse_wildcard_pattern(args: &[String]) -> Result<BytePattern> {
+    if args.is_empty() {
+        return Err(Error::Internal("Missing byte pattern".to_string()));
+    }
+    let mut bytes = Vec::new();
+    let mut mask = Vec::new();
+    for arg in args {
+        if arg.chars().all(|c| c == '?') {
+            bytes.push(0);
+            mask.push(0);
+        } else {
+            bytes.extend(parse_pattern(std::slice::from_ref(arg))?);
+            mask.push(0xFF);
+        }
+    }
+    Ok(BytePattern::new(bytes, mask))
+}
+
+/// 주소 목록을 한 줄에 8개씩
+fn format_addresses(addresses: &[u16]) -> String {
+    addresses
+        .iter()
+        .map(|address| format!("{:04X}", address))
+        .collect::<Vec<_>>()
+        .chunks(8)
+        .map(|row| row.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_pattern(args: &[String]) -> Result<Vec<u8>> {
+    if args.is_empty() {
+        return Err(Error::Internal("Missing byte pattern".to_string()));
+    }
+    args.iter()
+        .map(|arg| {
+            let value = parse_address(arg)?;
+            u8::try_from(value).map_err(|_| Error::Internal(format!("Not a byte: {}", arg)))
+        })
+        .collect()
+}
+
+/// start..=end 범위의 바이트 수
+fn range_size(start: u16, end: u16) -> Result<usize> {
+    if end < start {
+        return Err(Error::Internal(format!(
+            "End ${:04X} is before start ${:04X}",
+            end, start
+        )));
+    }
+    Ok((end - start) as usize + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(monitor: &mut Monitor, line: &str) -> String {
+        monitor.execute(line).unwrap()
+    }
+
+    #[test]
+    fn test_assemble_and_disassemble() {
+        let mut monitor = Monitor::new().unwrap();
+
+        assert_eq!(
+            run(&mut monitor, "a 0200 LDA #$10"),
+            ".0200  A9 10     LDA #$10"
+        );
+        // 어셈블 모드는 빈 줄까지 이어짐
+        run(&mut monitor, "a 0202");
+        run(&mut monitor, "INX");
+        run(&mut monitor, "BRK");
+        run(&mut monitor, "");
+
+        let listing = run(&mut monitor, "d 0200 0203");
+        assert_eq!(
+            listing,
+            ".0200  A9 10     LDA #$10\n.0202  E8        INX\n.0203  00        BRK"
+        );
+    }
+
+    #[test]
+    fn test_go_breakpoint_and_trace() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "a 0200");
+        for line in ["LDX #$01", "INX", "INX", "BRK", ""] {
+            run(&mut monitor, line);
+        }
+
+        assert_eq!(run(&mut monitor, "bk 0203"), "BREAK: 1  $0203");
+        let output = run(&mut monitor, "g 0200");
+        assert!(output.starts_with("BREAK: 1  $0203"), "{}", output);
+        assert!(
+            output.ends_with(".;0203 00 02 00 FD 00100000"),
+            "{}",
+            output
+        );
+
+        assert_eq!(run(&mut monitor, "bt"), "#0  $0203  $0203");
+
+        let trace = run(&mut monitor, "t 2");
+        assert!(trace.contains("INX") && trace.contains("X=03"), "{}", trace);
+        assert!(trace.ends_with("BRK"), "{}", trace);
+    }
+
+    #[test]
+    fn test_fill_hunt_and_registers() {
+        let mut monitor = Monitor::new().unwrap();
+
+        run(&mut monitor, "f 1000 100f aa 55");
+        assert_eq!(
+            run(&mut monitor, "h 1000 100f 55 aa"),
+            "1001 1003 1005 1007 1009 100B 100D"
+        );
+
+        let registers = run(&mut monitor, "r a=42 pc=$c000");
+        assert!(
+            registers.ends_with(".;C000 42 00 00 FD 00100000"),
+            "{}",
+            registers
+        );
+
+        // 8비트 레지스터에 $FF 초과 값은 거부하고 앞의 대입도 적용하지 않음
+        assert!(monitor.execute("r x=10 a=100").is_err());
+        assert!(monitor.execute("r sp=1ff").is_err());
+        let registers = run(&mut monitor, "r");
+        assert!(
+            registers.ends_with(".;C000 42 00 00 FD 00100000"),
+            "{}",
+            registers
+        );
+
+        assert!(monitor.execute("z").is_err());
+        assert!(monitor.execute("m .NOWHERE").is_err());
+        assert!(monitor.execute("f 1000 0fff 00").is_err());
+    }
+
+    #[test]
+    fn test_dump_formats() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "f 1000 1003 34 12 c1 3c");
+        assert_eq!(
+            run(&mut monitor, "m 1000 1003 w petscii"),
+            "1000: 1234 3cc1                                |4.A<|"
+        );
+        assert_eq!(
+            run(&mut monitor, "bm 1002 1003"),
+            "1002: 11000001  ##.....#\n1003: 00111100  ..####.."
+        );
+        assert!(monitor.execute("m 1000 1003 x").is_err());
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let mut monitor = Monitor::new().unwrap();
+        assert!(monitor.execute("diff").is_err());
+        run(&mut monitor, "snap");
+        assert_eq!(run(&mut monitor, "diff"), "No changes");
+        run(&mut monitor, "f 0300 0301 ea");
+        assert_eq!(
+            run(&mut monitor, "diff"),
+            "0300: 00 00 -> ea ea\n2 bytes changed"
+        );
+    }
+
+    #[test]
+    fn test_compare_copy_and_checksum() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "f 2000 2007 a9 01 8d");
+        assert_eq!(run(&mut monitor, "h 2000 2007 a9 ?? 8d"), "2000 2003");
+
+        run(&mut monitor, "mv 2000 2007 3000");
+        assert_eq!(run(&mut monitor, "c 2000 2007 3000"), "");
+        run(&mut monitor, "f 3002 3002 ff");
+        assert_eq!(run(&mut monitor, "c 2000 2007 3000"), "2002");
+
+        assert_eq!(
+            run(&mut monitor, "crc 2000 2007"),
+            format!(
+                "{:08X}",
+                mos_6502kit::common::crc32(&[0xA9, 0x01, 0x8D, 0xA9, 0x01, 0x8D, 0xA9, 0x01])
+            )
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut monitor = Monitor::new().unwrap();
+        let path = std::env::temp_dir().join(format!("monitor-labels-{}.lbl", std::process::id()));
+        fs::write(&path, "al C:0210 .PRINT_STRING\n").unwrap();
+        assert_eq!(
+            run(&mut monitor, &format!("ll \"{}\"", path.display())),
+            "Loaded 1 labels"
+        );
+        fs::remove_file(&path).unwrap();
+        run(&mut monitor, "al 0200 START");
+
+        assert_eq!(
+            run(&mut monitor, "a 0200 JSR $0210"),
+            ".0200  20 10 02  JSR PRINT_STRING"
+        );
+        assert!(matches!(
+            monitor.execute("bk print_string"),
+            Err(Error::UnknownSymbol(_))
+        ));
+        assert_eq!(
+            run(&mut monitor, "bk PRINT_STRING"),
+            "BREAK: 1  $0210  PRINT_STRING"
+        );
+        assert_eq!(
+            run(&mut monitor, "shl"),
+            "$0200 .START\n$0210 .PRINT_STRING"
+        );
+    }
+
+    #[test]
+    fn test_profile() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "a 0200");
+        for line in ["LDX #$05", "DEX", "BNE $0202", "BRK", ""] {
+            run(&mut monitor, line);
+        }
+        run(&mut monitor, "al 0202 LOOP");
+
+        assert_eq!(run(&mut monitor, "prof on"), "Profiling on");
+        run(&mut monitor, "g 0200");
+        let report = run(&mut monitor, "prof 2");
+        assert!(report.starts_with("Instructions: 12"), "{}", report);
+        // DEX 5회 10사이클, BNE 4회 분기 + 1회 통과
+        assert!(
+            report.contains("$0202   LOOP                      5           10"),
+            "{}",
+            report
+        );
+        assert!(report.contains("BNE LOOP"), "{}", report);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "a 0200");
+        for line in ["LDX #$02", "DEX", "BNE $0202", "STX $10", "BRK", "NOP", ""] {
+            run(&mut monitor, line);
+        }
+
+        assert_eq!(run(&mut monitor, "cov on"), "Coverage on");
+        run(&mut monitor, "g 0200");
+        let summary = run(&mut monitor, "cov");
+        assert!(
+            summary.contains("Branches: 1 executed, 1 taken both ways"),
+            "{}",
+            summary
+        );
+        assert_eq!(
+            run(&mut monitor, "cov list 0207 0209"),
+            "X    .0207  00        BRK\n     .0208  EA        .BYTE $EA\n     .0209  00        .BYTE $00"
+        );
+    }
+
+    #[test]
+    fn test_access_log() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "a 0200");
+        for line in ["LDA #$07", "STA $10", "LDX $10", "STX $10", "BRK", ""] {
+            run(&mut monitor, line);
+        }
+        run(&mut monitor, "al 0206 STORE");
+
+        assert_eq!(run(&mut monitor, "acc on"), "Access log on");
+        run(&mut monitor, "g 0200");
+        assert_eq!(
+            run(&mut monitor, "acc 10"),
+            "$0010: 1 reads, 2 writes\n\
+             Last writer: $0206  STORE\n\
+             Last access: cycle 8\n\
+             \u{20}        2  $0202  W $0010 $07\n\
+             \u{20}        5  $0204  R $0010 $07\n\
+             \u{20}        8  $0206  W $0010 $07"
+        );
+    }
+
+    #[test]
+    fn test_eval_script() {
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, "a 0200");
+        for line in ["LDY #$00", "INY", "CPY #$03", "BNE $0202", "BRK", ""] {
+            run(&mut monitor, line);
+        }
+        run(&mut monitor, "r pc=0200");
+
+        let output = run(
+            &mut monitor,
+            "eval break_at(0x0202, || reg(\"y\") == 2); print(run()); print(hex(reg(\"y\"), 2));",
+        );
+        assert_eq!(output, "break\n02");
+        // 스크립트가 끝나도 디버거 상태는 유지
+        assert_eq!(run(&mut monitor, "bk"), "BREAK: 1  $0202");
+    }
+
+    #[test]
+    fn test_session_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("monitor-session-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let program = dir.join("prog.bin");
+        let session = dir.join("bug.toml");
+        // LDX #$07 / BRK
+        fs::write(&program, [0xA2, 0x07, 0x00]).unwrap();
+
+        let mut monitor = Monitor::new().unwrap();
+        run(&mut monitor, &format!("l \"{}\" 0300", program.display()));
+        run(&mut monitor, "al 0302 DONE");
+        run(&mut monitor, "bk DONE");
+        run(&mut monitor, "w 10 r");
+        run(&mut monitor, "r x=42 pc=0300");
+        run(
+            &mut monitor,
+            &format!("session save \"{}\" state", session.display()),
+        );
+
+        let mut restored = Monitor::new().unwrap();
+        assert_eq!(
+            run(
+                &mut restored,
+                &format!("session load \"{}\"", session.display())
+            ),
+            "Session loaded: 1 programs, 2 breakpoints"
+        );
+        assert_eq!(run(&mut restored, "bk"), "BREAK: 1  $0302  DONE");
+        assert_eq!(run(&mut restored, "w"), "WATCH: 2  $0010 Read");
+        assert!(run(&mut restored, "r").ends_with(".;0300 00 42 00 FD 00100000"));
+        assert_eq!(
+            run(&mut restored, "d 0300 0301"),
+            ".0300  A2 07     LDX #$07"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_rebuilds_machine() {
+        let dir = std::env::temp_dir().join(format!("monitor-machine-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let rom = dir.join("woz.rom");
+        let session = dir.join("apple1.toml");
+        // $FF00: LDA #$01 / BRK, 리셋 벡터 $FF00
+        let mut image = vec![0xEA; 0x100];
+        image[..3].copy_from_slice(&[0xA9, 0x01, 0x00]);
+        image[0xFC..0xFE].copy_from_slice(&[0x00, 0xFF]);
+        fs::write(&rom, &image).unwrap();
+
+        let mut monitor = Monitor::new().unwrap();
+        assert_eq!(run(&mut monitor, "machine"), "Machine: 64KB RAM");
+        run(
+            &mut monitor,
+            &format!("machine apple1 \"{}\"", rom.display()),
+        );
+        run(&mut monitor, "bk ff02");
+        run(
+            &mut monitor,
+            &format!("session save \"{}\"", session.display()),
+        );
+        let saved = fs::read_to_string(&session).unwrap();
+        assert!(saved.contains("rom = \"woz.rom\""), "{}", saved);
+
+        let mut restored = Monitor::new().unwrap();
+        run(
+            &mut restored,
+            &format!("session load \"{}\"", session.display()),
+        );
+        assert_eq!(
+            run(&mut restored, "machine"),
+            format!("Machine: apple1 ({})", rom.display())
+        );
+        assert_eq!(
+            run(&mut restored, "d ff00 ff01"),
+            ".FF00  A9 01     LDA #$01"
+        );
+        run(&mut restored, "g");
+        assert!(run(&mut restored, "r").contains(";FF02 01"));
+
+        run(&mut restored, "machine ram");
+        assert_eq!(run(&mut restored, "machine"), "Machine: 64KB RAM");
+        assert_eq!(run(&mut restored, "bk"), "BREAK: 1  $FF02");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::debug_info::DebugInfo;
 use crate::generator::Generator;
 use crate::lexer::{Lexer, TokenInfo};
 use crate::parser::Parser;
@@ -20,7 +21,13 @@ impl Assembler {
     }
 
     pub fn assemble(&self, source: &str) -> Result<Vec<u8>> {
-        println!("[DEBUG] Assembling source code: \n{}", source);
+        let (machine_code, _) = self.assemble_with_debug_info(source)?;
+        Ok(machine_code)
+    }
+
+    /// 기계어와 함께 소스 줄/레이블 디버그 정보 생성
+    pub fn assemble_with_debug_info(&self, source: &str) -> Result<(Vec<u8>, DebugInfo)> {
+        common::trace!("[DEBUG] Assembling source code: \n{}", source);
 
         // 1. 어휘 분석 (Lexical Analysis)
         let lexer = Lexer::new(source);
@@ -33,16 +40,21 @@ impl Assembler {
         parser.set_org(self.org); // 초기 .ORG 값 설정
         let instructions = parser.parse()?;
 
-        println!("[DEBUG] Instructions: {:?}", instructions);
-        println!("[DEBUG] Final ORG: ${:04X}", parser.get_org());
+        common::trace!("[DEBUG] Instructions: {:?}", instructions);
+        common::trace!("[DEBUG] Final ORG: ${:04X}", parser.get_org());
 
         // 3. 코드 생성 (Code Generation)
         let mut generator = Generator::new(parser.get_org()); // 파서에서 최종 .ORG 값 가져오기
         let machine_code = generator.generate(instructions)?;
 
-        println!("[DEBUG] Machine code: {:?}", machine_code);
+        common::trace!("[DEBUG] Machine code: {:?}", machine_code);
 
-        Ok(machine_code)
+        let debug_info = DebugInfo {
+            origin: parser.get_org(),
+            lines: parser.get_source_lines().to_vec(),
+            labels: parser.get_labels().clone(),
+        };
+        Ok((machine_code, debug_info))
     }
 
     pub fn tokenize(&self, source: &str) -> Result<Vec<TokenInfo>> {
@@ -197,4 +209,20 @@ mod tests {
         // LDA #$01 + STA $80 + LDX #$02 + TAY = [0xA9, 0x01, 0x85, 0x80, 0xA2, 0x02, 0xA8]
         assert_eq!(machine_code, vec![0xA9, 0x01, 0x85, 0x80, 0xA2, 0x02, 0xA8]);
     }
+
+    #[test]
+    fn test_assemble_with_debug_info() {
+        let assembler = Assembler::new(0x0200);
+        let source = "START:\n  JSR SUB\n  BRK\nSUB:\n  INX\n  RTS";
+
+        let (machine_code, debug_info) = assembler.assemble_with_debug_info(source).unwrap();
+
+        assert_eq!(machine_code, vec![0x20, 0x04, 0x02, 0x00, 0xE8, 0x60]);
+        assert_eq!(debug_info.origin, 0x0200);
+        assert_eq!(debug_info.labels["SUB"], 0x0204);
+        assert_eq!(debug_info.line_of(0x0203), Some(3));
+        // 레이블만 있는 줄은 다음 명령어로 연결
+        assert_eq!(debug_info.resolve_line(4).unwrap().address, 0x0204);
+        assert_eq!(debug_info.label_before(0x0205), Some(("SUB", 0x0204)));
+    }
 }
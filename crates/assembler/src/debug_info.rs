@@ -0,0 +1,50 @@
+//! 어셈블 결과의 디버그 정보 (소스 줄 ↔ 주소, 레이블)
+use std::collections::HashMap;
+
+/// 명령어 하나의 소스 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLine {
+    /// 소스 줄 번호 (1부터 시작)
+    pub line: usize,
+    /// 명령어 시작 주소
+    pub address: u16,
+}
+
+/// 디버거가 소스 단위로 동작하기 위한 정보
+#[derive(Debug, Clone, Default)]
+pub struct DebugInfo {
+    /// 기계어가 적재될 시작 주소
+    pub origin: u16,
+    /// 명령어별 소스 위치 (소스 순서)
+    pub lines: Vec<SourceLine>,
+    /// 레이블 이름과 주소
+    pub labels: HashMap<String, u16>,
+}
+
+impl DebugInfo {
+    /// 주어진 줄 또는 그 이후 첫 명령어의 위치 (브레이크포인트 설정용)
+    pub fn resolve_line(&self, line: usize) -> Option<SourceLine> {
+        self.lines
+            .iter()
+            .filter(|entry| entry.line >= line)
+            .min_by_key(|entry| entry.line)
+            .copied()
+    }
+
+    /// 주소에서 시작하는 명령어의 소스 줄 번호
+    pub fn line_of(&self, address: u16) -> Option<usize> {
+        self.lines
+            .iter()
+            .find(|entry| entry.address == address)
+            .map(|entry| entry.line)
+    }
+
+    /// 주소를 포함하는 가장 가까운 앞쪽 레이블 (서브루틴 이름 표시용)
+    pub fn label_before(&self, address: u16) -> Option<(&str, u16)> {
+        self.labels
+            .iter()
+            .filter(|(_, label_address)| **label_address <= address)
+            .max_by_key(|(_, label_address)| **label_address)
+            .map(|(name, &label_address)| (name.as_str(), label_address))
+    }
+}
@@ -142,20 +142,20 @@ impl Lexer<'_> {
         let token = self.handle_identifier(chars)?;
 
         // .MACRO 디렉티브 처리
-        if let Token::Directive(ref d) = token.token {
-            if d == ".MACRO" {
-                return self.process_macro_directive(chars, token);
-            }
+        if let Token::Directive(ref d) = token.token
+            && d == ".MACRO"
+        {
+            return self.process_macro_directive(chars, token);
         }
 
         // 매크로 이름 처리
-        if self.last_token_was_macro {
-            if let Token::Label(name) = token.token {
-                return Ok(TokenizeResult::Token(TokenInfo {
-                    token: Token::MacroName(name),
-                    ..token
-                }));
-            }
+        if self.last_token_was_macro
+            && let Token::Label(name) = token.token
+        {
+            return Ok(TokenizeResult::Token(TokenInfo {
+                token: Token::MacroName(name),
+                ..token
+            }));
         }
 
         Ok(TokenizeResult::Token(token))
@@ -1,3 +1,4 @@
+pub mod debug_info;
 pub mod generator;
 pub mod lexer;
 pub mod parser;
@@ -5,3 +6,4 @@ pub mod parser;
 mod assembler;
 
 pub use assembler::Assembler;
+pub use debug_info::{DebugInfo, SourceLine};
@@ -35,9 +35,10 @@ impl BranchParser {
             }
             Token::Label(label) => {
                 let target = addressing_parser.resolve_label(label)?;
-                println!(
+                common::trace!(
                     "[DEBUG] Resolved label '{}' to address ${:04X}",
-                    label, target
+                    label,
+                    target
                 );
                 Ok(AddressModeValue::Absolute(target))
             }
@@ -49,11 +50,11 @@ impl BranchParser {
         let next_pc = self.current_address.wrapping_add(2);
         let offset = target_address as i32 - next_pc as i32;
 
-        println!("[DEBUG] Branch offset calculation:");
-        println!("[DEBUG]   Current address: ${:04X}", self.current_address);
-        println!("[DEBUG]   Next PC: ${:04X}", next_pc);
-        println!("[DEBUG]   Target address: ${:04X}", target_address);
-        println!(
+        common::trace!("[DEBUG] Branch offset calculation:");
+        common::trace!("[DEBUG]   Current address: ${:04X}", self.current_address);
+        common::trace!("[DEBUG]   Next PC: ${:04X}", next_pc);
+        common::trace!("[DEBUG]   Target address: ${:04X}", target_address);
+        common::trace!(
             "[DEBUG]   Offset: {} (0x{:02X})",
             offset,
             (offset as i8) as u8
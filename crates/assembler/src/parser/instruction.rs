@@ -39,10 +39,13 @@ impl InstructionParser {
         let next_pc = self.current_address.wrapping_add(2);
         let offset = target_address as i32 - next_pc as i32;
 
-        println!(
+        common::trace!(
             "[parse_branch] offset: 0x{:0x}, target_address: 0x{:0x}, 0xnext_pc: {:0x},
         current: 0x{:0x}",
-            offset, target_address, next_pc, self.current_address
+            offset,
+            target_address,
+            next_pc,
+            self.current_address
         );
 
         if !(-128..=127).contains(&offset) {
@@ -41,7 +41,7 @@ impl LabelCollector {
 
                     match token_parser.peek()?.token {
                         Token::HexNumber(addr) | Token::Number(addr) => {
-                            println!("[DEBUG] Found .ORG directive: ${:04X}", addr);
+                            common::trace!("[DEBUG] Found .ORG directive: ${:04X}", addr);
                             self.set_org(addr);
                             token_parser.advance();
                         }
@@ -49,18 +49,21 @@ impl LabelCollector {
                     }
                 }
                 Token::Label(name) => {
-                    println!(
+                    common::trace!(
                         "[DEBUG] Found label '{}' at ${:04X}",
-                        name, self.current_address
+                        name,
+                        self.current_address
                     );
                     addressing_parser.add_label(name.clone(), self.current_address);
                     token_parser.advance();
                 }
                 Token::Mnemonic(ref m) => {
                     let size = InstructionSizeCalculator::estimate_instruction_size(m);
-                    println!(
+                    common::trace!(
                         "[DEBUG] Estimating size for mnemonic '{}' at ${:04X}: {} bytes",
-                        m, self.current_address, size
+                        m,
+                        self.current_address,
+                        size
                     );
                     self.current_address = self.current_address.wrapping_add(size);
 
@@ -83,7 +86,7 @@ impl LabelCollector {
         }
 
         // Debug output: all collected labels
-        println!(
+        common::trace!(
             "[DEBUG] Collected labels: {:?}",
             addressing_parser.get_labels()
         );
@@ -1,3 +1,4 @@
+use crate::debug_info::SourceLine;
 use crate::lexer::{Token, TokenInfo};
 use crate::parser::addressing_mode_parser::AddressingModeParser;
 use crate::parser::branch_instruction_parser::BranchInstructionParser;
@@ -22,6 +23,7 @@ pub struct Parser<'a> {
     org: u16,
     current_address: u16,
     strategy_registry: StrategyRegistry<'a>,
+    source_lines: Vec<SourceLine>,
 }
 
 impl Parser<'_> {
@@ -47,6 +49,7 @@ impl Parser<'_> {
             org: default_org,
             current_address: default_org,
             strategy_registry,
+            source_lines: Vec::new(),
         }
     }
 
@@ -62,6 +65,16 @@ impl Parser<'_> {
         self.org
     }
 
+    /// 수집된 레이블 (parse 이후 유효)
+    pub fn get_labels(&self) -> &HashMap<String, u16> {
+        self.addressing_parser.get_labels()
+    }
+
+    /// 명령어별 소스 위치 (parse 이후 유효)
+    pub fn get_source_lines(&self) -> &[SourceLine] {
+        &self.source_lines
+    }
+
     pub fn parse(&mut self) -> Result<Vec<Instruction>> {
         // First pass: collect labels and .ORG
         self.label_collector
@@ -90,7 +103,10 @@ impl Parser<'_> {
                     self.token_parser.advance();
                 }
                 Token::Mnemonic(_) => {
+                    let line = self.token_parser.peek()?.line;
+                    let address = self.current_address;
                     if let Some(instruction) = self.parse_next_instruction()? {
+                        self.source_lines.push(SourceLine { line, address });
                         instructions.push(instruction);
                     }
                 }
@@ -257,6 +273,7 @@ impl Parser<'_> {
 
     fn reset_for_second_pass(&mut self) {
         self.token_parser.reset();
+        self.source_lines.clear();
         self.current_address = self.org;
         self.instruction_parser.set_current_address(self.org);
         self.branch_instruction_parser.set_current_address(self.org);
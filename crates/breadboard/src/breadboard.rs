@@ -1,7 +1,16 @@
-use common::MemoryBus;
-use common::Result;
-use cpu::CPU;
-use memory::Memory;
+use crate::machine::Console;
+use crate::recording::{InputEvent, Recording, Snapshot, TimedInput};
+use common::memory_bus::range_len;
+use common::{Device, MemoryBus, Result};
+use cpu::register::{RegisterData, RegisterType};
+use cpu::{CPU, CPUState, InterruptType};
+use error::Error;
+use memory::{
+    InterruptLine, Memory, MemoryDiff, MemoryMap, MemorySnapshot, SharedDevice, WritePolicy,
+};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::path::Path;
 use std::sync::{Arc, Mutex, RwLock};
 
 /// 브레드보드 - CPU와 메모리를 연결하는 간단한 구현
@@ -10,29 +19,38 @@ pub struct BreadBoard {
     pub cpu: CPU,
     /// 메모리 인스턴스 - 공유 참조로 사용
     memory: Arc<RwLock<Memory>>,
+    /// CPU가 접근하는 주소 디코더
+    bus: Arc<Mutex<MemoryMap>>,
+    /// 시리얼 입력을 받는 콘솔 장치
+    pub(crate) console: Option<Console>,
+    /// 기록 중인 입력 (시작 스냅샷 포함)
+    recording: Option<Recording>,
+    /// 재생할 남은 입력
+    replay: VecDeque<TimedInput>,
 }
 
-/// 메모리 버스 구현체 - 공유 메모리 참조 사용
+/// 주소 디코더에 RAM으로 연결하는 공유 메모리
 #[derive(Debug)]
-struct SharedMemoryBus {
+struct SharedMemory {
     /// 메모리 공유 참조
     memory: Arc<RwLock<Memory>>,
 }
 
-impl MemoryBus for SharedMemoryBus {
-    fn read(&self, address: u16) -> u8 {
+impl Device for SharedMemory {
+    fn peek(&self, address: u16) -> u8 {
         // 읽기 락 사용
         let memory = self.memory.read().unwrap();
         match memory.get(address) {
             Ok(value) => {
-                println!(
+                common::trace!(
                     "[MemoryBus] Read: addr=0x{:04x}, data=0x{:02x}",
-                    address, value
+                    address,
+                    value
                 );
                 value
             }
             Err(_) => {
-                println!("[MemoryBus] Read error at 0x{:04x}, returning 0", address);
+                common::trace!("[MemoryBus] Read error at 0x{:04x}, returning 0", address);
                 0
             }
         }
@@ -42,15 +60,20 @@ impl MemoryBus for SharedMemoryBus {
         // 쓰기 락 사용
         let mut memory = self.memory.write().unwrap();
         if let Err(e) = memory.set(address, value) {
-            println!("[MemoryBus] Write error at 0x{:04x}: {}", address, e);
+            common::trace!("[MemoryBus] Write error at 0x{:04x}: {}", address, e);
         } else {
             // TODO: DEBUG 일때만 출력하게 수정 필요
-            println!(
+            common::trace!(
                 "[MemoryBus] Write: addr=0x{:04x}, data=0x{:02x}",
-                address, value
+                address,
+                value
             );
         }
     }
+
+    fn try_write(&mut self, address: u16, value: u8) -> Result<()> {
+        self.memory.write().unwrap().set(address, value)
+    }
 }
 
 impl Default for BreadBoard {
@@ -65,16 +88,30 @@ impl BreadBoard {
         // 메모리 생성 및 공유
         let memory = Arc::new(RwLock::new(Memory::new()));
 
-        // 메모리 버스 생성
-        let memory_bus = Arc::new(Mutex::new(SharedMemoryBus {
-            memory: memory.clone(),
-        }));
+        // 주소 디코더 생성 - 64KB 전체를 RAM으로 채우고 장치는 그 위에 매핑
+        let mut bus = MemoryMap::new();
+        bus.map(
+            "ram",
+            0x0000..=0xFFFF,
+            Arc::new(Mutex::new(SharedMemory {
+                memory: memory.clone(),
+            })),
+        )
+        .expect("full address range is valid");
+        let bus = Arc::new(Mutex::new(bus));
 
         // CPU 생성 및 메모리 버스 연결
         let mut cpu = CPU::new();
-        cpu.set_memory_bus(memory_bus.clone());
+        cpu.set_memory_bus(bus.clone());
 
-        Self { cpu, memory }
+        Self {
+            cpu,
+            memory,
+            bus,
+            console: None,
+            recording: None,
+            replay: VecDeque::new(),
+        }
     }
 
     /// 메모리 직접 접근 (임시)
@@ -82,13 +119,114 @@ impl BreadBoard {
         self.memory.clone()
     }
 
-    /// 메모리에 프로그램 로드
-    pub fn load_program(&self, address: u16, program: &[u8]) -> Result<()> {
-        let mut memory = self.memory.write().unwrap();
-        for (i, &byte) in program.iter().enumerate() {
-            memory.set(address.wrapping_add(i as u16), byte)?;
+    /// 주소 디코더 - 장치 매핑, 오픈 버스 설정
+    pub fn bus(&self) -> Arc<Mutex<MemoryMap>> {
+        self.bus.clone()
+    }
+
+    /// RAM 위에 장치 연결 (mask로 미러링)
+    pub fn map_device(
+        &self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        mask: u16,
+        device: SharedDevice,
+    ) -> Result<()> {
+        self.bus
+            .lock()
+            .unwrap()
+            .map_mirrored(name, range, mask, device)
+    }
+
+    /// 범위 시작을 장치 오프셋 base로 하여 연결
+    pub fn map_device_at(
+        &self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        mask: u16,
+        base: u16,
+        device: SharedDevice,
+    ) -> Result<()> {
+        self.bus
+            .lock()
+            .unwrap()
+            .map_at(name, range, mask, base, device)
+    }
+
+    /// 메모리의 base부터를 range에 연결 - 기본 64KB RAM 매핑("ram")을 걷어낸 뒤 RAM/ROM 창을 만들 때 사용
+    pub fn map_memory(&self, name: &str, range: RangeInclusive<u16>, base: u16) -> Result<()> {
+        self.bus.lock().unwrap().map_at(
+            name,
+            range,
+            0xFFFF,
+            base,
+            Arc::new(Mutex::new(SharedMemory {
+                memory: self.memory.clone(),
+            })),
+        )
+    }
+
+    /// 이미지를 메모리 base부터의 range 크기 영역 끝에 맞춰 적재하고 보호한 뒤 range에 연결
+    /// 리셋 벡터가 있는 ROM 끝이 이미지 끝과 맞도록 함
+    pub fn map_rom(
+        &self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        base: u16,
+        image: &[u8],
+    ) -> Result<()> {
+        let limit = range_len(&range);
+        if image.len() > limit {
+            return Err(Error::RomTooLarge {
+                size: image.len(),
+                limit,
+            });
         }
-        Ok(())
+        let end = base as usize + limit - 1;
+        if end > 0xFFFF {
+            return Err(Error::InvalidMemoryRange(base));
+        }
+        {
+            let mut memory = self.memory.write().unwrap();
+            memory.load((end + 1 - image.len()) as u16, image)?;
+            memory.protect(base..=end as u16);
+        }
+        self.map_memory(name, range, base)
+    }
+
+    /// 매핑한 장치의 인터럽트 출력을 IRQ/NMI 핀에 연결하거나 끊음
+    pub fn set_interrupt_line(&self, name: &str, line: InterruptLine) -> Result<()> {
+        self.bus.lock().unwrap().set_interrupt_line(name, line)
+    }
+
+    /// 이름이 같은 매핑 모두 제거 - 이후 그 범위는 아래 매핑이나 오픈 버스
+    pub fn unmap(&self, name: &str) {
+        self.bus.lock().unwrap().unmap(name);
+    }
+
+    /// 메모리에 프로그램 로드 (ROM 영역에도 적재)
+    pub fn load_program(&self, address: u16, program: &[u8]) -> Result<()> {
+        self.memory.write().unwrap().load(address, program)
+    }
+
+    /// 파일을 address부터 적재 - 적재한 바이트 수
+    pub fn load_file(&self, address: u16, path: impl AsRef<Path>) -> Result<usize> {
+        self.memory.write().unwrap().load_file(address, path)
+    }
+
+    /// 메모리 범위를 파일로 저장
+    pub fn save_file(&self, range: RangeInclusive<u16>, path: impl AsRef<Path>) -> Result<()> {
+        self.memory.read().unwrap().save_file(range, path)
+    }
+
+    /// 파일을 ROM으로 적재 - 이후 CPU 쓰기는 정책에 따라 처리
+    pub fn load_rom_file(&self, address: u16, path: impl AsRef<Path>) -> Result<()> {
+        self.memory.write().unwrap().load_rom_file(address, path)
+    }
+
+    /// ROM 쓰기 처리 방법 설정
+    pub fn set_write_policy(&self, policy: WritePolicy) {
+        self.memory.write().unwrap().set_write_policy(policy);
     }
 
     /// CPU PC 설정
@@ -99,16 +237,177 @@ impl BreadBoard {
         );
     }
 
-    /// CPU 실행 사이클
+    /// CPU 실행 사이클 - 정지할 때까지 실행하며 장치도 함께 진행
     pub fn run_cpu_cycle(&mut self) -> Result<()> {
-        self.cpu.run()?;
+        loop {
+            self.step()?;
+            if self.cpu.get_state() != CPUState::Running {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 명령어 하나 실행 - 재생 중이면 실행 후 시각이 된 입력, 아니면 콘솔 포트 입력 적용
+    pub fn step(&mut self) -> Result<()> {
+        let before = self.cpu.total_cycles();
+        self.cpu.step()?;
+        self.tick_devices(before);
+        self.apply_due_inputs()?;
+        self.poll_console()
+    }
+
+    // 호스트 포트 입력도 input을 거쳐야 기록됨 - 재생 중에는 기록된 입력만 사용
+    fn poll_console(&mut self) -> Result<()> {
+        if self.is_replaying() {
+            return Ok(());
+        }
+        match self.console.as_ref().and_then(Console::poll_port) {
+            Some(byte) => self.input(InputEvent::Serial(byte)),
+            None => Ok(()),
+        }
+    }
+
+    // before 이후 경과한 사이클만큼 장치 진행
+    fn tick_devices(&mut self, before: u64) {
+        let elapsed = self.cpu.total_cycles() - before;
+        if elapsed > 0 {
+            self.bus.lock().unwrap().tick(elapsed);
+        }
+    }
+
+    // 실시간 입력과 같은 명령어 경계에서 적용되도록 실행 직후에 처리
+    fn apply_due_inputs(&mut self) -> Result<()> {
+        while let Some(input) = self.replay.front() {
+            if input.cycle > self.cpu.total_cycles() {
+                break;
+            }
+            let event = input.event;
+            self.replay.pop_front();
+            self.input(event)?;
+        }
         Ok(())
     }
 
-    /// 메모리 내용 덤프
-    pub fn dump_memory(&self, start: u16, length: usize) -> String {
+    /// 누적 사이클이 `cycle`에 이르거나 CPU가 멈출 때까지 실행
+    pub fn run_until(&mut self, cycle: u64) -> Result<()> {
+        while self.cpu.total_cycles() < cycle {
+            self.step()?;
+            if !matches!(self.cpu.get_state(), CPUState::Running) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// 외부 입력을 지금(명령어 경계) 적용 - 기록 중이면 현재 사이클과 함께 저장
+    pub fn input(&mut self, event: InputEvent) -> Result<()> {
+        let cycle = self.cpu.total_cycles();
+        match event {
+            InputEvent::Interrupt(interrupt) => {
+                let before = self.cpu.total_cycles();
+                self.cpu.handle_interrupt(interrupt)?;
+                self.tick_devices(before);
+            }
+            InputEvent::Device { address, value } => {
+                self.bus.lock().unwrap().write(address, value);
+            }
+            InputEvent::Serial(byte) => match &self.console {
+                Some(console) => console.receive(byte),
+                None => return Err(Error::ComponentNotFound("console".to_string())),
+            },
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.inputs.push(TimedInput { cycle, event });
+        }
+        Ok(())
+    }
+
+    /// 리셋 - 레지스터를 초기화하고 리셋 벡터($FFFC)로 이동, 기록 중이면 입력으로 남음
+    pub fn reset(&mut self) -> Result<()> {
+        self.input(InputEvent::Interrupt(InterruptType::Reset))
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
         let memory = self.memory.read().unwrap();
-        memory.dump(start, length)
+        Snapshot {
+            a: self.cpu.get_value(RegisterType::A).as_u8(),
+            x: self.cpu.get_value(RegisterType::X).as_u8(),
+            y: self.cpu.get_value(RegisterType::Y).as_u8(),
+            s: self.cpu.get_value(RegisterType::S).as_u8(),
+            p: self.cpu.get_value(RegisterType::P).as_u8(),
+            pc: self.cpu.get_value(RegisterType::PC).as_u16(),
+            cycles: self.cpu.total_cycles(),
+            state: self.cpu.get_state(),
+            nmi_line: self.cpu.nmi_line(),
+            memory: memory.data.to_vec(),
+            devices: self.bus.lock().unwrap().save_states(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<()> {
+        self.memory
+            .write()
+            .unwrap()
+            .data
+            .copy_from_slice(&snapshot.memory);
+        self.cpu
+            .set_value(RegisterType::A, RegisterData::Bit8(snapshot.a));
+        self.cpu
+            .set_value(RegisterType::X, RegisterData::Bit8(snapshot.x));
+        self.cpu
+            .set_value(RegisterType::Y, RegisterData::Bit8(snapshot.y));
+        self.cpu
+            .set_value(RegisterType::S, RegisterData::Bit8(snapshot.s));
+        self.cpu
+            .set_value(RegisterType::P, RegisterData::Bit8(snapshot.p));
+        self.cpu.set_pc(snapshot.pc);
+        self.cpu.set_total_cycles(snapshot.cycles);
+        self.cpu.set_state(snapshot.state);
+        self.cpu.set_nmi_line(snapshot.nmi_line);
+        self.bus.lock().unwrap().load_states(&snapshot.devices)
+    }
+
+    /// 현재 상태를 스냅샷으로 남기고 이후 입력 기록 시작
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Recording {
+            snapshot: self.snapshot(),
+            inputs: Vec::new(),
+        });
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// 기록의 스냅샷으로 되돌리고 입력을 같은 사이클에 다시 적용하도록 예약
+    pub fn replay(&mut self, recording: &Recording) -> Result<()> {
+        self.restore(&recording.snapshot)?;
+        self.replay = recording.inputs.iter().copied().collect();
+        self.apply_due_inputs()
+    }
+
+    /// 아직 적용하지 않은 재생 입력이 있는지
+    pub fn is_replaying(&self) -> bool {
+        !self.replay.is_empty()
+    }
+
+    /// 메모리 내용 덤프 - 장치 레지스터도 부수 효과 없이 표시
+    pub fn dump_memory(&self, start: u16, length: usize) -> String {
+        self.bus.lock().unwrap().dump(start, length)
+    }
+
+    /// 버스에서 보이는 주소 공간 스냅샷 (장치 레지스터 포함, 부수 효과 없음)
+    pub fn memory_snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot::capture(&*self.bus.lock().unwrap())
+    }
+
+    /// snapshot 이후 바뀐 곳
+    pub fn diff_memory(&self, snapshot: &MemorySnapshot) -> MemoryDiff {
+        snapshot.diff_live(&*self.bus.lock().unwrap())
     }
 
     /// CPU 상태 덤프
@@ -171,4 +470,195 @@ mod tests {
         assert_eq!(memory.get(0x0202).unwrap(), 0x85);
         assert_eq!(memory.get(0x0203).unwrap(), 0x10);
     }
+
+    #[derive(Debug, Default)]
+    struct CycleCounter {
+        cycles: u64,
+    }
+
+    impl Device for CycleCounter {
+        fn peek(&self, _offset: u16) -> u8 {
+            self.cycles as u8
+        }
+
+        fn write(&mut self, _offset: u16, _value: u8) {}
+
+        fn tick(&mut self, cycles: u64) {
+            self.cycles += cycles;
+        }
+    }
+
+    #[test]
+    fn test_mapped_device() {
+        let mut board = BreadBoard::new();
+        // LDA $D000 / STA $10 / BRK
+        let program = [0xAD, 0x00, 0xD0, 0x85, 0x10, 0x00];
+        board.load_program(0x0200, &program).unwrap();
+        board.set_pc(0x0200);
+        let counter = Arc::new(Mutex::new(CycleCounter::default()));
+        board
+            .map_device("counter", 0xD000..=0xD0FF, 0x0000, counter.clone())
+            .unwrap();
+
+        board.run_cpu_cycle().unwrap();
+        assert_eq!(board.memory.read().unwrap().get(0x10).unwrap(), 0);
+        assert_eq!(counter.lock().unwrap().cycles, board.cpu.total_cycles());
+
+        // 장치 레지스터 쓰기는 RAM에 닿지 않음
+        board.cpu.write_memory(0xD000, 0x99).unwrap();
+        assert_eq!(board.memory.read().unwrap().get(0xD000).unwrap(), 0);
+    }
+
+    #[derive(Debug, Default)]
+    struct Latch {
+        value: u8,
+    }
+
+    impl Device for Latch {
+        fn peek(&self, _offset: u16) -> u8 {
+            self.value
+        }
+
+        fn write(&mut self, _offset: u16, value: u8) {
+            self.value = value;
+        }
+    }
+
+    #[test]
+    fn test_device_input_goes_through_bus() {
+        let mut board = BreadBoard::new();
+        let latch = Arc::new(Mutex::new(Latch::default()));
+        board
+            .map_device("keyboard", 0xC000..=0xC000, 0x0000, latch.clone())
+            .unwrap();
+
+        board
+            .input(InputEvent::Device {
+                address: 0xC000,
+                value: 0x41,
+            })
+            .unwrap();
+        assert_eq!(latch.lock().unwrap().value, 0x41);
+        assert_eq!(board.memory.read().unwrap().get(0xC000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_restores_cpu_state() {
+        let mut board = BreadBoard::new();
+        // BRK에서 정지
+        board.load_program(0x0200, &[0xEA, 0x00]).unwrap();
+        board.set_pc(0x0200);
+        while !matches!(board.cpu.get_state(), CPUState::Halted(_)) {
+            board.step().unwrap();
+        }
+        board.cpu.set_nmi_line(true);
+        let snapshot = board.snapshot();
+
+        let mut restored = BreadBoard::new();
+        restored.restore(&snapshot).unwrap();
+        assert_eq!(
+            restored.cpu.get_state(),
+            CPUState::Halted(InterruptType::BRK)
+        );
+        assert!(restored.cpu.nmi_line());
+        assert_eq!(restored.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_rom_write_error() {
+        let mut board = BreadBoard::new();
+        // STA $F000 / BRK
+        board
+            .load_program(0x0200, &[0x8D, 0x00, 0xF0, 0x00])
+            .unwrap();
+        board
+            .memory
+            .write()
+            .unwrap()
+            .load_rom(0xF000, &[0x60])
+            .unwrap();
+        board.set_pc(0x0200);
+
+        // 기본 정책은 무시
+        board.step().unwrap();
+        assert_eq!(board.memory.read().unwrap().get(0xF000).unwrap(), 0x60);
+
+        board.set_write_policy(WritePolicy::Error);
+        board.set_pc(0x0200);
+        assert!(matches!(
+            board.step(),
+            Err(error::Error::InvalidMemoryAddress(0xF000))
+        ));
+    }
+
+    // 키 입력을 기다리며 세고, IRQ 핸들러는 $11을 증가
+    fn interactive_board() -> BreadBoard {
+        let program = [
+            0x58, // $0200 CLI
+            0xE8, // $0201 INX
+            0xAD, 0x00, 0xC0, // $0202 LDA $C000
+            0xF0, 0xFA, // $0205 BEQ $0201
+            0x85, 0x10, // $0207 STA $10
+            0x00, // $0209 BRK
+        ];
+        let handler = [
+            0xE6, 0x11, // $0300 INC $11
+            0x40, // $0302 RTI
+        ];
+        let mut board = BreadBoard::new();
+        board.load_program(0x0200, &program).unwrap();
+        board.load_program(0x0300, &handler).unwrap();
+        board.load_program(0xFFFE, &[0x00, 0x03]).unwrap();
+        board.set_pc(0x0200);
+        board
+    }
+
+    fn trace(board: &mut BreadBoard, until: u64) -> Vec<u16> {
+        let mut pcs = Vec::new();
+        while board.cpu.total_cycles() < until
+            && !matches!(board.cpu.get_state(), CPUState::Halted(_))
+        {
+            pcs.push(board.cpu.get_value(RegisterType::PC).as_u16());
+            board.step().unwrap();
+        }
+        pcs
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut board = interactive_board();
+        board.run_until(40).unwrap();
+        board.start_recording();
+        let mut recorded = trace(&mut board, 100);
+        board
+            .input(InputEvent::Interrupt(InterruptType::IRQ))
+            .unwrap();
+        recorded.extend(trace(&mut board, 200));
+        board
+            .input(InputEvent::Device {
+                address: 0xC000,
+                value: 0x41,
+            })
+            .unwrap();
+        assert!(
+            board.input(InputEvent::Serial(b'Z')).is_err(),
+            "no console device"
+        );
+        recorded.extend(trace(&mut board, 1000));
+        let recording = board.stop_recording().unwrap();
+        assert_eq!(recording.inputs.len(), 2);
+        {
+            let memory = board.memory.read().unwrap();
+            assert_eq!(memory.get(0x10).unwrap(), 0x41, "key seen");
+            assert_eq!(memory.get(0x11).unwrap(), 1, "IRQ handled");
+        }
+
+        let mut replayed = interactive_board();
+        replayed
+            .replay(&Recording::from_bytes(&recording.to_bytes()).unwrap())
+            .unwrap();
+        assert_eq!(trace(&mut replayed, 1000), recorded);
+        assert!(!replayed.is_replaying());
+        assert_eq!(replayed.snapshot(), board.snapshot());
+    }
 }
@@ -0,0 +1,519 @@
+//! 머신 기술 파일 (TOML) - CPU, RAM/ROM 영역과 이미지, 장치와 주소, 인터럽트 연결, 시작 방법
+use crate::machine::{Console, PiaTerminal, ViaLcd};
+use crate::{BreadBoard, Machine};
+use common::Result;
+use common::memory_bus::range_len;
+use error::Error;
+use memory::{InterruptLine, SharedDevice};
+use peripherals::{Acia, Pia, Riot, Via};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// CPU 코어 종류
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CpuVariant {
+    /// NMOS 6502 (현재 에뮬레이터가 구현한 코어)
+    #[default]
+    #[serde(rename = "6502")]
+    Nmos6502,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuDescription {
+    #[serde(default)]
+    pub variant: CpuVariant,
+    /// 클록 (Hz) - ACIA 보레이트 타이밍에 사용
+    #[serde(default = "default_clock")]
+    pub clock: u64,
+    /// 시작 PC - 없으면 리셋 벡터
+    #[serde(
+        default,
+        with = "hex_address::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pc: Option<u16>,
+}
+
+impl Default for CpuDescription {
+    fn default() -> Self {
+        Self {
+            variant: CpuVariant::default(),
+            clock: default_clock(),
+            pc: None,
+        }
+    }
+}
+
+fn default_clock() -> u64 {
+    1_000_000
+}
+
+/// RAM/ROM 영역 - start-end에 메모리의 base부터를 연결 (base가 다르면 미러)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(with = "hex_address")]
+    pub start: u16,
+    #[serde(with = "hex_address")]
+    pub end: u16,
+    #[serde(
+        default,
+        with = "hex_address::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base: Option<u16>,
+    /// RAM은 base부터, ROM은 영역 끝에 맞춰 적재
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<PathBuf>,
+}
+
+/// 장치 종류 - pia-terminal은 Apple I 키보드/화면, via-lcd는 Ben Eater LCD 배선
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeviceKind {
+    Via,
+    Acia,
+    Riot,
+    Pia,
+    PiaTerminal,
+    ViaLcd,
+}
+
+/// 장치 인터럽트 출력 연결
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interrupt {
+    #[default]
+    Irq,
+    Nmi,
+    None,
+}
+
+impl From<Interrupt> for InterruptLine {
+    fn from(interrupt: Interrupt) -> Self {
+        match interrupt {
+            Interrupt::Irq => InterruptLine::Irq,
+            Interrupt::Nmi => InterruptLine::Nmi,
+            Interrupt::None => InterruptLine::None,
+        }
+    }
+}
+
+/// 장치 매핑 - 같은 이름을 다시 쓰면 같은 칩의 다른 창 (RIOT의 RAM/I-O 등)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceDescription {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: DeviceKind,
+    #[serde(with = "hex_address")]
+    pub start: u16,
+    #[serde(with = "hex_address")]
+    pub end: u16,
+    #[serde(default = "full_mask", with = "hex_address")]
+    pub mask: u16,
+    #[serde(default, with = "hex_address")]
+    pub base: u16,
+    /// 장치 단위 설정 - 창 하나에만 적어도 되고, 어디에도 없으면 IRQ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupt: Option<Interrupt>,
+}
+
+fn full_mask() -> u16 {
+    0xFFFF
+}
+
+/// 머신 기술 파일 - 경로는 파일 위치 기준 상대 경로 가능
+/// 나중에 나온 항목이 앞의 항목을 가림 (RAM, ROM, 장치 순)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineDescription {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub cpu: CpuDescription,
+    #[serde(default)]
+    pub ram: Vec<Region>,
+    #[serde(default)]
+    pub rom: Vec<Region>,
+    #[serde(default, rename = "device")]
+    pub devices: Vec<DeviceDescription>,
+}
+
+impl MachineDescription {
+    pub fn parse(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| Error::InvalidMachine(e.to_string()))
+    }
+
+    /// 기술 파일 읽기 - 이미지 경로는 기술 파일 위치 기준으로 바꿈
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut description = Self::parse(&std::fs::read_to_string(path)?)?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        for region in description.ram.iter_mut().chain(&mut description.rom) {
+            if let Some(image) = &mut region.image {
+                *image = base.join(&*image);
+            }
+        }
+        Ok(description)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let text = toml::to_string(self).map_err(|e| Error::InvalidMachine(e.to_string()))?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// 보드만 필요할 때
+    pub fn build(&self) -> Result<BreadBoard> {
+        Ok(self.build_machine()?.board)
+    }
+
+    /// 보드 구성 후 리셋 - 첫 ACIA/pia-terminal이 콘솔, 첫 via-lcd가 LCD
+    pub fn build_machine(&self) -> Result<Machine> {
+        let board = BreadBoard::new();
+        board.unmap("ram");
+        let mut machine = Machine {
+            board,
+            preset: None,
+            lcd: None,
+            riots: Vec::new(),
+        };
+
+        for region in &self.ram {
+            let base = region.base.unwrap_or(region.start);
+            machine.board.map_memory(
+                region.name.as_deref().unwrap_or("ram"),
+                region.start..=region.end,
+                base,
+            )?;
+            if let Some(path) = &region.image {
+                let image = std::fs::read(path)?;
+                if image.len() > range_len(&(region.start..=region.end)) {
+                    return Err(Error::InvalidMachine(format!(
+                        "{} does not fit in RAM at ${:04X}",
+                        path.display(),
+                        region.start
+                    )));
+                }
+                machine.board.load_program(base, &image)?;
+            }
+        }
+        for region in &self.rom {
+            let image = match &region.image {
+                Some(path) => std::fs::read(path)?,
+                None => Vec::new(),
+            };
+            machine.board.map_rom(
+                region.name.as_deref().unwrap_or("rom"),
+                region.start..=region.end,
+                region.base.unwrap_or(region.start),
+                &image,
+            )?;
+        }
+
+        let mut chips: Vec<Chip> = Vec::new();
+        for description in &self.devices {
+            let device = match chips.iter_mut().find(|chip| chip.name == description.name) {
+                Some(chip) if chip.kind != description.kind => {
+                    return Err(Error::InvalidMachine(format!(
+                        "device {} redefined with another type",
+                        description.name
+                    )));
+                }
+                Some(chip) => {
+                    match (chip.interrupt, description.interrupt) {
+                        (Some(wired), Some(interrupt)) if wired != interrupt => {
+                            return Err(Error::InvalidMachine(format!(
+                                "device {} wired to both {:?} and {:?}",
+                                description.name, wired, interrupt
+                            )));
+                        }
+                        (None, interrupt) => chip.interrupt = interrupt,
+                        _ => {}
+                    }
+                    chip.device.clone()
+                }
+                None => {
+                    let device = machine.create_device(description.kind, self.cpu.clock);
+                    chips.push(Chip {
+                        name: &description.name,
+                        kind: description.kind,
+                        device: device.clone(),
+                        interrupt: description.interrupt,
+                    });
+                    device
+                }
+            };
+            machine.board.map_device_at(
+                &description.name,
+                description.start..=description.end,
+                description.mask,
+                description.base,
+                device,
+            )?;
+        }
+        for chip in &chips {
+            machine
+                .board
+                .set_interrupt_line(chip.name, chip.interrupt.unwrap_or_default().into())?;
+        }
+
+        machine.board.reset()?;
+        if let Some(pc) = self.cpu.pc {
+            machine.board.set_pc(pc);
+        }
+        Ok(machine)
+    }
+}
+
+// 이름마다 하나인 칩과 창들에서 지정한 인터럽트 연결
+struct Chip<'a> {
+    name: &'a str,
+    kind: DeviceKind,
+    device: SharedDevice,
+    interrupt: Option<Interrupt>,
+}
+
+impl Machine {
+    // 장치 생성 - 콘솔, LCD, RIOT 핸들도 함께 등록
+    fn create_device(&mut self, kind: DeviceKind, clock: u64) -> SharedDevice {
+        match kind {
+            DeviceKind::Via => Arc::new(Mutex::new(Via::new())),
+            DeviceKind::Pia => Arc::new(Mutex::new(Pia::new())),
+            DeviceKind::Acia => {
+                let acia = Arc::new(Mutex::new(Acia::new().with_clock(clock)));
+                self.board
+                    .console
+                    .get_or_insert_with(|| Console::Acia(acia.clone()));
+                acia
+            }
+            DeviceKind::PiaTerminal => {
+                let terminal = Arc::new(Mutex::new(PiaTerminal::new()));
+                self.board
+                    .console
+                    .get_or_insert_with(|| Console::Terminal(terminal.clone()));
+                terminal
+            }
+            DeviceKind::ViaLcd => {
+                let lcd = Arc::new(Mutex::new(ViaLcd::default()));
+                self.lcd.get_or_insert_with(|| lcd.clone());
+                lcd
+            }
+            DeviceKind::Riot => {
+                let riot = Arc::new(Mutex::new(Riot::new()));
+                self.riots.push(riot.clone());
+                riot
+            }
+        }
+    }
+}
+
+/// 주소는 숫자 또는 "$C000"/"0xC000" 문자열, 저장할 때는 "$C000"
+mod hex_address {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(address: &u16, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("${:04X}", address))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Address {
+            Number(u16),
+            Text(String),
+        }
+        match Address::deserialize(deserializer)? {
+            Address::Number(address) => Ok(address),
+            Address::Text(text) => {
+                let hex = text
+                    .strip_prefix('$')
+                    .or_else(|| text.strip_prefix("0x"))
+                    .unwrap_or(&text);
+                u16::from_str_radix(hex, 16)
+                    .map_err(|_| de::Error::custom(format!("invalid address: {}", text)))
+            }
+        }
+    }
+
+    pub mod option {
+        use serde::{Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            address: &Option<u16>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match address {
+                Some(address) => super::serialize(address, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<u16>, D::Error> {
+            super::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryBus;
+    use cpu::register::RegisterType;
+
+    const KIM_LIKE: &str = r#"
+        name = "kim-like"
+
+        [cpu]
+        variant = "6502"
+        pc = "$1C00"
+
+        [[ram]]
+        start = 0
+        end = "$03FF"
+
+        [[rom]]
+        start = "$1800"
+        end = "$1FFF"
+
+        [[rom]]
+        name = "vectors"
+        start = "$FFFA"
+        end = "$FFFF"
+        base = "$1FFA"
+
+        [[device]]
+        name = "riot"
+        type = "riot"
+        start = "$1780"
+        end = "$17BF"
+        mask = "$003F"
+
+        [[device]]
+        name = "riot"
+        type = "riot"
+        start = "$1700"
+        end = "$173F"
+        mask = "$000F"
+        base = "$0090"
+        interrupt = "none"
+    "#;
+
+    #[test]
+    fn test_parse_and_round_trip() {
+        let description = MachineDescription::parse(KIM_LIKE).unwrap();
+        assert_eq!(description.cpu.clock, 1_000_000);
+        assert_eq!(description.cpu.pc, Some(0x1C00));
+        assert_eq!(description.rom[1].base, Some(0x1FFA));
+        assert_eq!(description.devices[1].interrupt, Some(Interrupt::None));
+        assert_eq!(description.devices[0].interrupt, None);
+
+        let saved = toml::to_string(&description).unwrap();
+        assert!(saved.contains("end = \"$17BF\""), "{}", saved);
+        assert_eq!(MachineDescription::parse(&saved).unwrap(), description);
+
+        for text in [
+            "[cpu]\nvariant = \"65c02\"",
+            "[[device]]\nname = \"x\"\ntype = \"sid\"\nstart = 0\nend = 1",
+            "[[ram]]\nstart = \"$G000\"\nend = 0",
+        ] {
+            assert!(matches!(
+                MachineDescription::parse(text),
+                Err(Error::InvalidMachine(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_build_machine() {
+        let machine = MachineDescription::parse(KIM_LIKE)
+            .unwrap()
+            .build_machine()
+            .unwrap();
+        assert_eq!(machine.preset(), None);
+        assert_eq!(
+            machine.board.cpu.get_value(RegisterType::PC).as_u16(),
+            0x1C00
+        );
+        assert_eq!(machine.riots().len(), 1, "one chip, two windows");
+
+        let bus = machine.board.bus();
+        let mut bus = bus.lock().unwrap();
+        bus.write(0x1781, 0x42);
+        assert_eq!(machine.riots()[0].lock().unwrap().ram()[1], 0x42);
+        bus.write(0x1703, 0xFF);
+        bus.write(0x1702, 0x5A);
+        assert_eq!(machine.riots()[0].lock().unwrap().port_b(), 0x5A);
+        bus.write(0x1800, 0x00);
+        assert_eq!(bus.peek(0x1800), 0x00, "empty ROM stays unwritten");
+        assert_eq!(bus.peek(0x4000), 0xFF, "open bus");
+    }
+
+    #[test]
+    fn test_device_redefined_with_another_type() {
+        let text = r#"
+            [[device]]
+            name = "io"
+            type = "via"
+            start = "$6000"
+            end = "$600F"
+
+            [[device]]
+            name = "io"
+            type = "acia"
+            start = "$7000"
+            end = "$7003"
+        "#;
+        assert!(matches!(
+            MachineDescription::parse(text).unwrap().build(),
+            Err(Error::InvalidMachine(_))
+        ));
+    }
+
+    #[test]
+    fn test_interrupt_wired_per_device() {
+        let riot = |interrupt: &str| {
+            format!(
+                r#"
+                [[device]]
+                name = "riot"
+                type = "riot"
+                start = "$1780"
+                end = "$17BF"
+                interrupt = "nmi"
+
+                [[device]]
+                name = "riot"
+                type = "riot"
+                start = "$1700"
+                end = "$173F"
+                {}
+            "#,
+                interrupt
+            )
+        };
+        let board = MachineDescription::parse(&riot(""))
+            .unwrap()
+            .build()
+            .unwrap();
+        let bus = board.bus();
+        let lines: Vec<_> = bus
+            .lock()
+            .unwrap()
+            .mappings()
+            .iter()
+            .map(|mapping| mapping.line)
+            .collect();
+        assert_eq!(lines, [InterruptLine::Nmi, InterruptLine::Nmi]);
+
+        assert!(matches!(
+            MachineDescription::parse(&riot("interrupt = \"irq\""))
+                .unwrap()
+                .build(),
+            Err(Error::InvalidMachine(_))
+        ));
+    }
+}
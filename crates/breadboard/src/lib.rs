@@ -5,5 +5,11 @@
 
 // 핵심 모듈 정의
 mod breadboard;
+pub mod description;
+pub mod machine;
+mod recording;
 
 pub use breadboard::BreadBoard;
+pub use description::MachineDescription;
+pub use machine::{Machine, Preset};
+pub use recording::{InputEvent, Recording, Snapshot, TimedInput};
@@ -0,0 +1,486 @@
+//! 프리셋 머신 - 잘 알려진 기판의 메모리 맵, 사용자 ROM 이미지, I/O 장치, 리셋 벡터
+use crate::BreadBoard;
+use common::{Device, Result};
+use display::Hd44780;
+use error::Error;
+use peripherals::pia::{CR_IRQ1, CR_PORT, CRA, CRB, PRB};
+use peripherals::{Acia, Pia, Riot, SerialPort, Via, riot};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// 기판 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// RAM $0000-$7FFF/$E000-$EFFF, PIA 키보드/화면 $D010, ROM $FF00 (Woz Monitor)
+    Apple1,
+    /// RAM $0000-$03FF, 6530 두 개($1700/$1740, RAM $1780-$17FF), ROM $1800-$1FFF
+    Kim1,
+    /// RAM $0000-$3FFF, VIA $6000 (HD44780 LCD 16x2), ROM $8000-$FFFF
+    BenEater,
+    /// RAM $0000-$7FFF, 6551 ACIA $8000, ROM $C000-$FFFF
+    AciaSbc,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::Apple1,
+        Preset::Kim1,
+        Preset::BenEater,
+        Preset::AciaSbc,
+    ];
+
+    /// 세션/설정 파일에서 쓰는 이름
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Apple1 => "apple1",
+            Preset::Kim1 => "kim1",
+            Preset::BenEater => "ben-eater",
+            Preset::AciaSbc => "acia-sbc",
+        }
+    }
+
+    /// ROM 이미지를 놓는 창 - 이미지는 창 끝에 맞춰 적재
+    pub fn rom_window(self) -> RangeInclusive<u16> {
+        match self {
+            Preset::Apple1 => 0xFF00..=0xFFFF,
+            Preset::Kim1 => 0x1800..=0x1FFF,
+            Preset::BenEater => 0x8000..=0xFFFF,
+            Preset::AciaSbc => 0xC000..=0xFFFF,
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::UnknownMachine(name.to_string()))
+    }
+}
+
+/// Apple I 키보드/화면 - PIA 포트 A는 키보드(CA1 스트로브), 포트 B는 화면(PB7 low면 준비)
+/// 키보드 입력과 화면 출력은 시리얼 포트로 연결, IRQ 선은 CPU에 연결되지 않음
+/// 키 입력은 보드가 `poll_port`로 꺼내 시리얼 입력으로 적용
+#[derive(Debug)]
+pub struct PiaTerminal {
+    pia: Pia,
+    port: Option<Box<dyn SerialPort>>,
+}
+
+impl Default for PiaTerminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PiaTerminal {
+    pub fn new() -> Self {
+        let mut pia = Pia::new();
+        pia.set_port_b(0x00);
+        Self { pia, port: None }
+    }
+
+    pub fn set_port(&mut self, port: impl SerialPort + 'static) {
+        self.port = Some(Box::new(port));
+    }
+
+    pub fn pia(&self) -> &Pia {
+        &self.pia
+    }
+
+    /// 키 입력 - 소문자는 대문자, LF는 CR로 바꾸고 비트 7을 세워 스트로브
+    pub fn key(&mut self, key: u8) {
+        let key = match key.to_ascii_uppercase() {
+            b'\n' => b'\r',
+            key => key,
+        };
+        self.pia.set_port_a(key | 0x80);
+        self.pia.set_ca1(false);
+        self.pia.set_ca1(true);
+    }
+
+    /// 호스트 포트에 도착한 키 - 앞 키를 읽어 간 뒤에만
+    pub fn poll_port(&mut self) -> Option<u8> {
+        if self.pia.peek(CRA) & CR_IRQ1 != 0 {
+            return None;
+        }
+        self.port.as_mut().and_then(|port| port.receive())
+    }
+
+    fn display(&mut self, value: u8) {
+        let Some(port) = &mut self.port else {
+            return;
+        };
+        match value & 0x7F {
+            b'\r' => {
+                port.send(b'\r');
+                port.send(b'\n');
+            }
+            char @ 0x20..=0x5F => port.send(char),
+            _ => {}
+        }
+    }
+}
+
+impl Device for PiaTerminal {
+    fn peek(&self, offset: u16) -> u8 {
+        self.pia.peek(offset)
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        self.pia.read(offset)
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        self.pia.write(offset, value);
+        if offset & 0x03 == PRB && self.pia.peek(CRB) & CR_PORT != 0 {
+            self.display(value);
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        self.pia.tick(cycles);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        self.pia.save_state()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        self.pia.load_state(state)
+    }
+}
+
+/// LCD 제어선 (포트 A)
+pub const LCD_E: u8 = 0x80;
+pub const LCD_RW: u8 = 0x40;
+pub const LCD_RS: u8 = 0x20;
+
+/// Ben Eater 배선 - VIA 포트 B는 LCD 데이터, PA7 E, PA6 RW, PA5 RS
+/// 쓰기는 E 하강 엣지, 읽기는 E 상승 엣지에서 LCD가 포트 B를 구동
+#[derive(Debug)]
+pub struct ViaLcd {
+    via: Via,
+    lcd: Hd44780,
+    enable: bool,
+}
+
+impl Default for ViaLcd {
+    fn default() -> Self {
+        Self::new(Hd44780::new(16, 2))
+    }
+}
+
+impl ViaLcd {
+    pub fn new(lcd: Hd44780) -> Self {
+        Self {
+            via: Via::new(),
+            lcd,
+            enable: false,
+        }
+    }
+
+    pub fn via(&self) -> &Via {
+        &self.via
+    }
+
+    pub fn via_mut(&mut self) -> &mut Via {
+        &mut self.via
+    }
+
+    pub fn lcd(&self) -> &Hd44780 {
+        &self.lcd
+    }
+
+    // 포트 A 출력이 바뀐 뒤 LCD 제어선 반영
+    fn sync(&mut self) {
+        let control = self.via.port_a();
+        let enable = control & LCD_E != 0;
+        let register = (control & LCD_RS != 0) as u16;
+        if control & LCD_RW != 0 {
+            if enable && !self.enable {
+                let value = self.lcd.read(register);
+                self.via.set_port_b(value);
+            }
+        } else if !enable && self.enable {
+            self.lcd.write(register, self.via.port_b());
+        }
+        self.enable = enable;
+    }
+}
+
+impl Device for ViaLcd {
+    fn peek(&self, offset: u16) -> u8 {
+        self.via.peek(offset)
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        self.via.read(offset)
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        self.via.write(offset, value);
+        self.sync();
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        self.via.tick(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        self.via.irq()
+    }
+
+    /// VIA 상태 길이(u16), VIA 상태, LCD 상태, E 레벨
+    fn save_state(&self) -> Vec<u8> {
+        let via = self.via.save_state();
+        let mut state = (via.len() as u16).to_le_bytes().to_vec();
+        state.extend_from_slice(&via);
+        state.extend_from_slice(&self.lcd.save_state());
+        state.push(self.enable as u8);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let (&enable, rest) = state.split_last().ok_or(Error::InvalidSaveState)?;
+        let (len, rest) = rest.split_at_checked(2).ok_or(Error::InvalidSaveState)?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        let (via, lcd) = rest.split_at_checked(len).ok_or(Error::InvalidSaveState)?;
+        self.via.load_state(via)?;
+        self.lcd.load_state(lcd)?;
+        self.enable = enable != 0;
+        Ok(())
+    }
+}
+
+// 호스트 시리얼 연결이 있는 장치
+#[derive(Debug)]
+pub(crate) enum Console {
+    Terminal(Arc<Mutex<PiaTerminal>>),
+    Acia(Arc<Mutex<Acia>>),
+}
+
+impl Console {
+    /// 시리얼 입력 한 바이트 - Apple I는 키 입력, ACIA는 수신 대기열
+    pub(crate) fn poll_port(&self) -> Option<u8> {
+        match self {
+            Console::Terminal(terminal) => terminal.lock().unwrap().poll_port(),
+            Console::Acia(acia) => acia.lock().unwrap().poll_port(),
+        }
+    }
+
+    pub(crate) fn receive(&self, byte: u8) {
+        match self {
+            Console::Terminal(terminal) => terminal.lock().unwrap().key(byte),
+            Console::Acia(acia) => acia.lock().unwrap().receive(&[byte]),
+        }
+    }
+}
+
+/// 프리셋이나 기술 파일로 구성한 보드와 외부에서 다룰 장치 핸들
+/// 64KB RAM 대신 RAM/ROM 창과 장치만 매핑하므로 나머지 주소는 오픈 버스
+pub struct Machine {
+    pub board: BreadBoard,
+    pub(crate) preset: Option<Preset>,
+    pub(crate) lcd: Option<Arc<Mutex<ViaLcd>>>,
+    pub(crate) riots: Vec<Arc<Mutex<Riot>>>,
+}
+
+impl Machine {
+    /// ROM 파일로 기판 구성 후 리셋 벡터에서 시작
+    pub fn new(preset: Preset, rom: impl AsRef<Path>) -> Result<Self> {
+        Self::with_rom(preset, &std::fs::read(rom)?)
+    }
+
+    /// ROM 이미지로 기판 구성 후 리셋 벡터에서 시작
+    pub fn with_rom(preset: Preset, rom: &[u8]) -> Result<Self> {
+        let window = preset.rom_window();
+        let board = BreadBoard::new();
+        board.unmap("ram");
+        board.map_rom("rom", window.clone(), *window.start(), rom)?;
+
+        let mut machine = Self {
+            board,
+            preset: Some(preset),
+            lcd: None,
+            riots: Vec::new(),
+        };
+        match preset {
+            Preset::Apple1 => machine.build_apple1()?,
+            Preset::Kim1 => machine.build_kim1()?,
+            Preset::BenEater => machine.build_ben_eater()?,
+            Preset::AciaSbc => machine.build_acia_sbc()?,
+        }
+        machine.board.reset()?;
+        Ok(machine)
+    }
+
+    fn build_apple1(&mut self) -> Result<()> {
+        self.board.map_memory("ram", 0x0000..=0x7FFF, 0x0000)?;
+        // Integer BASIC 적재 영역
+        self.board.map_memory("ram-e000", 0xE000..=0xEFFF, 0xE000)?;
+        let terminal = Arc::new(Mutex::new(PiaTerminal::new()));
+        self.board
+            .map_device("pia", 0xD010..=0xD01F, 0x0003, terminal.clone())?;
+        self.board.console = Some(Console::Terminal(terminal));
+        Ok(())
+    }
+
+    // 6530은 레지스터 배치가 6532와 같아 Riot으로 대신함 (I/O 창은 A4 없이 타이머 선택)
+    fn build_kim1(&mut self) -> Result<()> {
+        self.board.map_memory("ram", 0x0000..=0x03FF, 0x0000)?;
+        for (name, io, ram) in [("riot-003", 0x1700, 0x1780), ("riot-002", 0x1740, 0x17C0)] {
+            let riot = Arc::new(Mutex::new(Riot::new()));
+            self.board
+                .map_device(name, ram..=ram + 0x3F, 0x003F, riot.clone())?;
+            self.board.map_device_at(
+                &format!("{}-io", name),
+                io..=io + 0x3F,
+                0x000F,
+                riot::IO | 0x10,
+                riot.clone(),
+            )?;
+            self.riots.push(riot);
+        }
+        // A13-A15를 디코딩하지 않아 벡터는 ROM 끝($1FFA-$1FFF)에서 읽힘
+        self.board.map_memory("vectors", 0xFFFA..=0xFFFF, 0x1FFA)
+    }
+
+    fn build_ben_eater(&mut self) -> Result<()> {
+        self.board.map_memory("ram", 0x0000..=0x3FFF, 0x0000)?;
+        let lcd = Arc::new(Mutex::new(ViaLcd::default()));
+        self.board
+            .map_device("via", 0x6000..=0x7FFF, 0x000F, lcd.clone())?;
+        self.lcd = Some(lcd);
+        Ok(())
+    }
+
+    fn build_acia_sbc(&mut self) -> Result<()> {
+        self.board.map_memory("ram", 0x0000..=0x7FFF, 0x0000)?;
+        let acia = Arc::new(Mutex::new(Acia::new()));
+        self.board
+            .map_device("acia", 0x8000..=0x8FFF, 0x0003, acia.clone())?;
+        self.board.console = Some(Console::Acia(acia));
+        Ok(())
+    }
+
+    /// 기술 파일로 구성했으면 None
+    pub fn preset(&self) -> Option<Preset> {
+        self.preset
+    }
+
+    /// 키보드/화면(Apple I) 또는 시리얼(ACIA)을 호스트 포트에 연결
+    pub fn attach_console(&mut self, port: impl SerialPort + 'static) -> Result<()> {
+        match &self.board.console {
+            Some(Console::Terminal(terminal)) => terminal.lock().unwrap().set_port(port),
+            Some(Console::Acia(acia)) => acia.lock().unwrap().set_port(port),
+            None => return Err(Error::ComponentNotFound("console".to_string())),
+        }
+        Ok(())
+    }
+
+    /// LCD에 보이는 행 (Ben Eater, via-lcd 장치)
+    pub fn lcd_lines(&self) -> Option<Vec<String>> {
+        self.lcd
+            .as_ref()
+            .map(|lcd| lcd.lock().unwrap().lcd().lines())
+    }
+
+    /// KIM-1의 6530 (003, 002 순서) 또는 기술 파일의 RIOT - 키패드/LED는 포트를 직접 구동
+    pub fn riots(&self) -> &[Arc<Mutex<Riot>>] {
+        &self.riots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputEvent;
+    use common::MemoryBus;
+
+    #[test]
+    fn test_preset_names() {
+        for preset in Preset::ALL {
+            assert_eq!(preset.name().parse::<Preset>().unwrap(), preset);
+        }
+        assert_eq!("Apple1".parse::<Preset>().unwrap(), Preset::Apple1);
+        assert!(matches!(
+            "c64".parse::<Preset>(),
+            Err(Error::UnknownMachine(_))
+        ));
+    }
+
+    #[test]
+    fn test_rom_window_and_reset_vector() {
+        // KIM-1: 2KB ROM, 리셋 벡터는 $1FFC
+        let mut rom = vec![0xEA; 0x800];
+        rom[0x7FC..].copy_from_slice(&[0x00, 0x1C, 0x00, 0x1C]);
+        let machine = Machine::with_rom(Preset::Kim1, &rom).unwrap();
+        assert_eq!(
+            machine
+                .board
+                .cpu
+                .get_value(cpu::register::RegisterType::PC)
+                .as_u16(),
+            0x1C00
+        );
+
+        let bus = machine.board.bus();
+        let mut bus = bus.lock().unwrap();
+        assert_eq!(bus.peek(0xFFFC), 0x00, "vectors mirrored from ROM");
+        assert_eq!(bus.peek(0x2000), 0xFF, "open bus");
+        bus.write(0x17C5, 0x42);
+        assert_eq!(machine.riots()[1].lock().unwrap().ram()[5], 0x42);
+        bus.write(0x1800, 0x00);
+        assert_eq!(bus.peek(0x1800), 0xEA, "ROM");
+
+        assert!(matches!(
+            Machine::with_rom(Preset::Apple1, &[0; 0x101]),
+            Err(Error::RomTooLarge { limit: 0x100, .. })
+        ));
+    }
+
+    #[test]
+    fn test_serial_input_reaches_console() {
+        let mut machine = Machine::with_rom(Preset::Apple1, &[]).unwrap();
+        // KBDCR - 포트 A 데이터 레지스터 선택
+        machine.board.bus().lock().unwrap().write(0xD011, CR_PORT);
+        machine.board.input(InputEvent::Serial(b'a')).unwrap();
+        let bus = machine.board.bus();
+        let bus = bus.lock().unwrap();
+        assert_eq!(bus.peek(0xD010), b'A' | 0x80);
+        assert_ne!(bus.peek(0xD011) & CR_IRQ1, 0, "key strobe");
+
+        let mut lcd = Machine::with_rom(Preset::BenEater, &[]).unwrap();
+        assert!(matches!(
+            lcd.board.input(InputEvent::Serial(b'a')),
+            Err(Error::ComponentNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_console_only_on_serial_machines() {
+        let mut machine = Machine::with_rom(Preset::BenEater, &[]).unwrap();
+        assert!(
+            machine
+                .attach_console(peripherals::serial::BufferPort::new())
+                .is_err()
+        );
+        assert_eq!(
+            machine.lcd_lines().unwrap(),
+            ["", ""].map(|_| " ".repeat(16))
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! 외부 입력 기록/재생 - 인터럽트, 입력 장치, 시리얼 입력을 사이클 시각과 함께 저장
+use common::Result;
+use cpu::{CPUState, InterruptType, MemoryAccess, StopReason};
+use error::Error;
+use std::path::Path;
+
+/// 기록 파일 식별자
+const RECORDING_MAGIC: &[u8; 8] = b"M6502RC3";
+/// 레지스터(A X Y S P PCL PCH) + 누적 사이클 + CPU 상태(최대) + NMI 선 + 64KB 메모리
+const SNAPSHOT_SIZE: usize = 7 + 8 + 9 + 1 + 0x10000;
+/// 사이클 + 종류 + 데이터 3바이트
+const INPUT_SIZE: usize = 8 + 1 + 3;
+
+/// 머신 외부에서 들어오는 입력
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    /// IRQ/NMI/리셋 요청
+    Interrupt(InterruptType),
+    /// 키보드 등 메모리 맵 입력 장치 레지스터에 값 쓰기
+    Device { address: u16, value: u8 },
+    /// 콘솔 장치(ACIA 수신, Apple I 키보드)에 들어오는 바이트
+    Serial(u8),
+}
+
+/// 입력과 입력이 적용된 누적 사이클
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedInput {
+    pub cycle: u64,
+    pub event: InputEvent,
+}
+
+/// 머신 전체 상태 - 재생 시작점
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    pub s: u8,
+    pub p: u8,
+    pub pc: u16,
+    pub cycles: u64,
+    /// 실행/정지/일시 정지 상태
+    pub state: CPUState,
+    /// 직전에 본 NMI 선 레벨 - 복원 후 엣지 검출이 이어지도록
+    pub nmi_line: bool,
+    pub memory: Vec<u8>,
+    /// 주소 디코더에 매핑된 장치 상태 (매핑 이름, 상태)
+    pub devices: Vec<(String, Vec<u8>)>,
+}
+
+/// 시작 스냅샷과 그 이후의 입력
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub snapshot: Snapshot,
+    pub inputs: Vec<TimedInput>,
+}
+
+impl Recording {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let snapshot = &self.snapshot;
+        let mut bytes = Vec::with_capacity(
+            RECORDING_MAGIC.len() + SNAPSHOT_SIZE + 8 + self.inputs.len() * INPUT_SIZE,
+        );
+        bytes.extend_from_slice(RECORDING_MAGIC);
+        bytes.extend_from_slice(&[snapshot.a, snapshot.x, snapshot.y, snapshot.s, snapshot.p]);
+        bytes.extend_from_slice(&snapshot.pc.to_le_bytes());
+        bytes.extend_from_slice(&snapshot.cycles.to_le_bytes());
+        encode_state(snapshot.state, &mut bytes);
+        bytes.push(snapshot.nmi_line as u8);
+        bytes.extend_from_slice(&snapshot.memory);
+        bytes.extend_from_slice(&(snapshot.devices.len() as u32).to_le_bytes());
+        for (name, state) in &snapshot.devices {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&(state.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(state);
+        }
+        bytes.extend_from_slice(&(self.inputs.len() as u32).to_le_bytes());
+        for input in &self.inputs {
+            bytes.extend_from_slice(&input.cycle.to_le_bytes());
+            bytes.extend_from_slice(&encode_event(input.event));
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take(RECORDING_MAGIC.len())? != RECORDING_MAGIC {
+            return Err(Error::InvalidRecording);
+        }
+        let registers = reader.take(7)?;
+        let cycles = reader.u64()?;
+        let state = decode_state(&mut reader)?;
+        let nmi_line = match reader.take(1)?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::InvalidRecording),
+        };
+        let memory = reader.take(0x10000)?.to_vec();
+        let mut devices = Vec::new();
+        for _ in 0..reader.u32()? {
+            let name_len = reader.u32()? as usize;
+            let name = String::from_utf8(reader.take(name_len)?.to_vec())
+                .map_err(|_| Error::InvalidRecording)?;
+            let state_len = reader.u32()? as usize;
+            devices.push((name, reader.take(state_len)?.to_vec()));
+        }
+        let snapshot = Snapshot {
+            a: registers[0],
+            x: registers[1],
+            y: registers[2],
+            s: registers[3],
+            p: registers[4],
+            pc: u16::from_le_bytes([registers[5], registers[6]]),
+            cycles,
+            state,
+            nmi_line,
+            memory,
+            devices,
+        };
+
+        let count = reader.u32()? as usize;
+        let mut inputs = Vec::with_capacity(count);
+        for _ in 0..count {
+            let cycle = reader.u64()?;
+            let event = decode_event(reader.take(4)?)?;
+            inputs.push(TimedInput { cycle, event });
+        }
+        if !reader.0.is_empty() {
+            return Err(Error::InvalidRecording);
+        }
+        Ok(Self { snapshot, inputs })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+fn encode_interrupt(interrupt: InterruptType) -> u8 {
+    match interrupt {
+        InterruptType::Reset => 0,
+        InterruptType::IRQ => 1,
+        InterruptType::NMI => 2,
+        InterruptType::BRK => 3,
+        InterruptType::IllegalOpcode => 4,
+        InterruptType::Breakpoint => 5,
+    }
+}
+
+fn decode_interrupt(code: u8) -> Result<InterruptType> {
+    Ok(match code {
+        0 => InterruptType::Reset,
+        1 => InterruptType::IRQ,
+        2 => InterruptType::NMI,
+        3 => InterruptType::BRK,
+        4 => InterruptType::IllegalOpcode,
+        5 => InterruptType::Breakpoint,
+        _ => return Err(Error::InvalidRecording),
+    })
+}
+
+// 상태 1바이트 + 정지 이유에 따라 가변 (최대 9바이트)
+fn encode_state(state: CPUState, bytes: &mut Vec<u8>) {
+    match state {
+        CPUState::Running => bytes.push(0),
+        CPUState::Ready => bytes.push(1),
+        CPUState::Halted(interrupt) => bytes.extend_from_slice(&[2, encode_interrupt(interrupt)]),
+        CPUState::Paused(reason) => {
+            bytes.push(3);
+            match reason {
+                StopReason::Breakpoint(id) => {
+                    bytes.push(0);
+                    bytes.extend_from_slice(&(id as u32).to_le_bytes());
+                }
+                StopReason::Watchpoint {
+                    id,
+                    address,
+                    access,
+                } => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&(id as u32).to_le_bytes());
+                    bytes.extend_from_slice(&address.to_le_bytes());
+                    bytes.push(matches!(access, MemoryAccess::Write) as u8);
+                }
+                StopReason::StepComplete => bytes.push(2),
+                StopReason::IllegalOpcode(opcode) => bytes.extend_from_slice(&[3, opcode]),
+                StopReason::UserPause => bytes.push(4),
+                StopReason::WriteProtected { address } => {
+                    bytes.push(5);
+                    bytes.extend_from_slice(&address.to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+fn decode_state(reader: &mut Reader) -> Result<CPUState> {
+    let state = match reader.take(1)?[0] {
+        0 => CPUState::Running,
+        1 => CPUState::Ready,
+        2 => CPUState::Halted(decode_interrupt(reader.take(1)?[0])?),
+        3 => CPUState::Paused(match reader.take(1)?[0] {
+            0 => StopReason::Breakpoint(reader.u32()? as usize),
+            1 => StopReason::Watchpoint {
+                id: reader.u32()? as usize,
+                address: reader.u16()?,
+                access: match reader.take(1)?[0] {
+                    0 => MemoryAccess::Read,
+                    1 => MemoryAccess::Write,
+                    _ => return Err(Error::InvalidRecording),
+                },
+            },
+            2 => StopReason::StepComplete,
+            3 => StopReason::IllegalOpcode(reader.take(1)?[0]),
+            4 => StopReason::UserPause,
+            5 => StopReason::WriteProtected {
+                address: reader.u16()?,
+            },
+            _ => return Err(Error::InvalidRecording),
+        }),
+        _ => return Err(Error::InvalidRecording),
+    };
+    Ok(state)
+}
+
+// 종류 1바이트 + 데이터 3바이트
+fn encode_event(event: InputEvent) -> [u8; 4] {
+    match event {
+        InputEvent::Interrupt(interrupt) => [0, encode_interrupt(interrupt), 0, 0],
+        InputEvent::Device { address, value } => {
+            let [low, high] = address.to_le_bytes();
+            [1, low, high, value]
+        }
+        InputEvent::Serial(byte) => [2, byte, 0, 0],
+    }
+}
+
+fn decode_event(bytes: &[u8]) -> Result<InputEvent> {
+    let event = match bytes[0] {
+        0 => InputEvent::Interrupt(decode_interrupt(bytes[1])?),
+        1 => InputEvent::Device {
+            address: u16::from_le_bytes([bytes[1], bytes[2]]),
+            value: bytes[3],
+        },
+        2 => InputEvent::Serial(bytes[1]),
+        _ => return Err(Error::InvalidRecording),
+    };
+    Ok(event)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::InvalidRecording);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_bytes() {
+        let mut memory = vec![0; 0x10000];
+        memory[0xC000] = 0x41;
+        let recording = Recording {
+            snapshot: Snapshot {
+                a: 1,
+                x: 2,
+                y: 3,
+                s: 0xFD,
+                p: 0x24,
+                pc: 0x0200,
+                cycles: 1234,
+                state: CPUState::Paused(StopReason::Watchpoint {
+                    id: 7,
+                    address: 0xC010,
+                    access: MemoryAccess::Write,
+                }),
+                nmi_line: true,
+                memory,
+                devices: vec![("via".to_string(), vec![0x12, 0x34])],
+            },
+            inputs: vec![
+                TimedInput {
+                    cycle: 1300,
+                    event: InputEvent::Interrupt(InterruptType::IRQ),
+                },
+                TimedInput {
+                    cycle: 1400,
+                    event: InputEvent::Device {
+                        address: 0xC010,
+                        value: 0x80,
+                    },
+                },
+                TimedInput {
+                    cycle: 1500,
+                    event: InputEvent::Serial(b'A'),
+                },
+            ],
+        };
+        let bytes = recording.to_bytes();
+        assert_eq!(Recording::from_bytes(&bytes).unwrap(), recording);
+        assert!(Recording::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_cpu_state_bytes() {
+        let states = [
+            CPUState::Running,
+            CPUState::Ready,
+            CPUState::Halted(InterruptType::BRK),
+            CPUState::Paused(StopReason::Breakpoint(3)),
+            CPUState::Paused(StopReason::StepComplete),
+            CPUState::Paused(StopReason::IllegalOpcode(0x02)),
+            CPUState::Paused(StopReason::UserPause),
+            CPUState::Paused(StopReason::WriteProtected { address: 0xF000 }),
+        ];
+        for state in states {
+            let mut bytes = Vec::new();
+            encode_state(state, &mut bytes);
+            let mut reader = Reader(&bytes);
+            assert_eq!(decode_state(&mut reader).unwrap(), state);
+            assert!(reader.0.is_empty());
+        }
+        assert!(decode_state(&mut Reader(&[3, 9])).is_err());
+    }
+}
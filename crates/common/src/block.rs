@@ -0,0 +1,103 @@
+//! 블록 메모리 연산 도구 - 와일드카드 패턴 검색, CRC-32 체크섬
+
+/// 마스크가 있는 바이트 패턴 - 마스크 비트가 0인 곳은 비교하지 않음
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytePattern {
+    bytes: Vec<u8>,
+    mask: Vec<u8>,
+}
+
+impl BytePattern {
+    /// bytes와 mask는 길이가 같아야 함
+    pub fn new(bytes: Vec<u8>, mask: Vec<u8>) -> Self {
+        assert_eq!(bytes.len(), mask.len(), "pattern and mask length differ");
+        Self { bytes, mask }
+    }
+
+    /// 모든 비트를 비교하는 패턴
+    pub fn exact(bytes: &[u8]) -> Self {
+        Self::new(bytes.to_vec(), vec![0xFF; bytes.len()])
+    }
+
+    /// `A9 ?? 8D` 형식 - `??`(또는 `?`)는 아무 바이트
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut bytes = Vec::new();
+        let mut mask = Vec::new();
+        for token in text.split_whitespace() {
+            if token.chars().all(|c| c == '?') {
+                bytes.push(0);
+                mask.push(0);
+            } else {
+                let hex = token.strip_prefix('$').unwrap_or(token);
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                mask.push(0xFF);
+            }
+        }
+        (!bytes.is_empty()).then(|| Self::new(bytes, mask))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// data가 패턴으로 시작하는지
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() >= self.len()
+            && self
+                .bytes
+                .iter()
+                .zip(&self.mask)
+                .zip(data)
+                .all(|((&byte, &mask), &value)| value & mask == byte & mask)
+    }
+
+    /// data에서 패턴이 시작하는 위치 목록
+    pub fn find_in(&self, data: &[u8]) -> Vec<usize> {
+        if self.is_empty() || data.len() < self.len() {
+            return Vec::new();
+        }
+        (0..=data.len() - self.len())
+            .filter(|&offset| self.matches(&data[offset..]))
+            .collect()
+    }
+}
+
+/// CRC-32 (IEEE 802.3, zip/PNG와 같은 값)
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_with_wildcards() {
+        let pattern = BytePattern::parse("A9 ?? $8D").unwrap();
+        let data = [0xA9, 0x01, 0x8D, 0xA9, 0xFF, 0x8D, 0xA9, 0x00, 0x8E];
+        assert_eq!(pattern.find_in(&data), vec![0, 3]);
+        assert!(BytePattern::parse("A9 XYZ").is_none());
+        assert_eq!(BytePattern::exact(&[0x8D]).find_in(&data), vec![2, 5]);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(&[]), 0);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::Result;
+use std::fmt::Debug;
+
+/// 버스에 연결하는 장치 (ROM, RAM, I/O 칩)
+/// 주소는 매핑 시작 기준 오프셋으로 전달됨
+pub trait Device: Debug + Send {
+    /// 부수 효과 없이 1바이트 읽기
+    fn peek(&self, offset: u16) -> u8;
+
+    /// CPU 읽기 - 상태 레지스터 클리어 등 부수 효과가 있을 수 있음
+    fn read(&mut self, offset: u16) -> u8 {
+        self.peek(offset)
+    }
+
+    /// 1바이트 쓰기
+    fn write(&mut self, offset: u16, value: u8);
+
+    /// CPU 쓰기 - 보호된 영역이면 오류를 돌려줄 수 있음
+    fn try_write(&mut self, offset: u16, value: u8) -> Result<()> {
+        self.write(offset, value);
+        Ok(())
+    }
+
+    /// CPU 사이클 경과 알림 (타이머, 시리얼 전송 등)
+    fn tick(&mut self, _cycles: u64) {}
+
+    /// IRQ 출력 - 레벨 트리거, 장치가 요청하는 동안 true
+    fn irq(&self) -> bool {
+        false
+    }
+
+    /// 세이브 스테이트용 내부 상태 - 저장할 것이 없으면 비어 있음
+    fn save_state(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// save_state로 저장한 상태 복원
+    fn load_state(&mut self, _state: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
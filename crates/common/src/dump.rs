@@ -0,0 +1,275 @@
+//! 메모리 덤프 렌더러 - 16진수/문자 배치, 비트맵, GUI용 구조화된 줄
+
+/// 덤프 한 줄 - GUI는 문자열 대신 이 값을 그대로 사용
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpRow {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    /// 값 칸 (바이트/워드 16진수, 2진수 등)
+    pub cells: Vec<String>,
+    /// 오른쪽 텍스트 (문자, 픽셀, 디스어셈블)
+    pub text: String,
+}
+
+/// 바이트를 줄로 나누고 표시하는 방법
+pub trait DumpRenderer {
+    /// address부터 data를 줄 단위로 나눔
+    fn rows(&self, address: u16, data: &[u8]) -> Vec<DumpRow>;
+
+    /// 한 줄 문자열 (줄바꿈 없음)
+    fn format_row(&self, row: &DumpRow) -> String;
+
+    /// 줄마다 줄바꿈을 붙인 전체 문자열
+    fn render(&self, address: u16, data: &[u8]) -> String {
+        self.rows(address, data)
+            .iter()
+            .map(|row| self.format_row(row) + "\n")
+            .collect()
+    }
+}
+
+/// 16진수 칸 하나에 묶는 단위 - 워드/더블워드는 리틀 엔디언 값으로 표시
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    #[default]
+    Byte,
+    Word,
+    DWord,
+}
+
+impl Grouping {
+    pub fn size(self) -> usize {
+        match self {
+            Grouping::Byte => 1,
+            Grouping::Word => 2,
+            Grouping::DWord => 4,
+        }
+    }
+}
+
+/// 오른쪽 문자 열의 해석 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    #[default]
+    Ascii,
+    /// Commodore 대문자/그래픽 모드
+    Petscii,
+    /// Atari - 비트 7은 반전 표시라 무시
+    Atascii,
+    /// 문자 열 없음
+    None,
+}
+
+impl Charset {
+    /// 표시할 수 없는 값은 '.'
+    pub fn decode(self, value: u8) -> char {
+        match self {
+            Charset::Ascii | Charset::None => match value {
+                0x20..=0x7E => value as char,
+                _ => '.',
+            },
+            Charset::Petscii => match value {
+                0x5C => '£',
+                0x5E => '↑',
+                0x5F => '←',
+                0x20..=0x5D => value as char,
+                // 대문자 모드에서 $C1-$DA는 $41-$5A와 같은 글자
+                0xC1..=0xDA => (value - 0x80) as char,
+                0xA0 => ' ',
+                _ => '.',
+            },
+            Charset::Atascii => match value & 0x7F {
+                0x60 => '♦',
+                0x7B => '♠',
+                low @ 0x20..=0x7C => low as char,
+                _ => '.',
+            },
+        }
+    }
+}
+
+/// 16진수 칸과 문자 열 - 기본값은 16바이트, 바이트 단위, ASCII
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexDump {
+    width: usize,
+    grouping: Grouping,
+    charset: Charset,
+}
+
+impl Default for HexDump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HexDump {
+    pub fn new() -> Self {
+        Self {
+            width: 16,
+            grouping: Grouping::Byte,
+            charset: Charset::Ascii,
+        }
+    }
+
+    /// 한 줄의 바이트 수 - 묶음 단위의 배수로 올림
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    pub fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    fn row_width(&self) -> usize {
+        self.width.div_ceil(self.grouping.size()) * self.grouping.size()
+    }
+}
+
+impl DumpRenderer for HexDump {
+    fn rows(&self, address: u16, data: &[u8]) -> Vec<DumpRow> {
+        data.chunks(self.row_width())
+            .enumerate()
+            .map(|(i, bytes)| DumpRow {
+                address: address.wrapping_add((i * self.row_width()) as u16),
+                bytes: bytes.to_vec(),
+                cells: bytes
+                    .chunks(self.grouping.size())
+                    .map(|group| group.iter().rev().map(|b| format!("{:02x}", b)).collect())
+                    .collect(),
+                text: match self.charset {
+                    Charset::None => String::new(),
+                    charset => bytes.iter().map(|&b| charset.decode(b)).collect(),
+                },
+            })
+            .collect()
+    }
+
+    fn format_row(&self, row: &DumpRow) -> String {
+        let cell_width = self.grouping.size() * 2;
+        let mut line = format!("{:04x}: ", row.address);
+        for i in 0..self.row_width() / self.grouping.size() {
+            let cell = row.cells.get(i).map_or("", String::as_str);
+            line.push_str(&format!("{:<width$} ", cell, width = cell_width));
+        }
+        if self.charset != Charset::None {
+            line.push_str(&format!(" |{}|", row.text));
+        }
+        line
+    }
+}
+
+/// 2진수와 픽셀 - 스프라이트/폰트 데이터용 (C64 스프라이트는 너비 3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitmapDump {
+    width: usize,
+    on: char,
+    off: char,
+}
+
+impl Default for BitmapDump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapDump {
+    /// 한 줄 1바이트 (8x8 폰트)
+    pub fn new() -> Self {
+        Self {
+            width: 1,
+            on: '#',
+            off: '.',
+        }
+    }
+
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// 켜진/꺼진 픽셀 문자
+    pub fn with_pixels(mut self, on: char, off: char) -> Self {
+        self.on = on;
+        self.off = off;
+        self
+    }
+}
+
+impl DumpRenderer for BitmapDump {
+    fn rows(&self, address: u16, data: &[u8]) -> Vec<DumpRow> {
+        data.chunks(self.width)
+            .enumerate()
+            .map(|(i, bytes)| DumpRow {
+                address: address.wrapping_add((i * self.width) as u16),
+                bytes: bytes.to_vec(),
+                cells: bytes.iter().map(|b| format!("{:08b}", b)).collect(),
+                text: bytes
+                    .iter()
+                    .flat_map(|&b| {
+                        (0..8)
+                            .rev()
+                            .map(move |bit| if b >> bit & 1 != 0 { self.on } else { self.off })
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn format_row(&self, row: &DumpRow) -> String {
+        format!(
+            "{:04x}: {:<width$}  {}",
+            row.address,
+            row.cells.join(" "),
+            row.text,
+            width = self.width * 9 - 1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump_layouts() {
+        let data = b"HELLO\x01\x02";
+        assert_eq!(
+            HexDump::new().with_width(4).render(0x1000, data),
+            "1000: 48 45 4c 4c  |HELL|\n1004: 4f 01 02     |O..|\n"
+        );
+
+        let words = HexDump::new()
+            .with_width(3)
+            .with_grouping(Grouping::Word)
+            .with_charset(Charset::None);
+        let rows = words.rows(0x2000, &[0x34, 0x12, 0x78, 0x56, 0xFF]);
+        assert_eq!(rows[0].cells, vec!["1234", "5678"]);
+        assert_eq!(rows[1].address, 0x2004);
+        assert_eq!(words.format_row(&rows[1]), "2004: ff        ");
+    }
+
+    #[test]
+    fn test_charsets() {
+        assert_eq!(Charset::Petscii.decode(0xC1), 'A');
+        assert_eq!(Charset::Petscii.decode(0x5C), '£');
+        assert_eq!(Charset::Atascii.decode(0xC1), 'A', "inverse video");
+        assert_eq!(Charset::Atascii.decode(0x61), 'a');
+        assert_eq!(Charset::Ascii.decode(0xC1), '.');
+    }
+
+    #[test]
+    fn test_bitmap_dump() {
+        let sprite = BitmapDump::new().with_width(2).with_pixels('X', ' ');
+        assert_eq!(
+            sprite.render(0x3000, &[0x81, 0x3C, 0xFF]),
+            "3000: 10000001 00111100  X      X  XXXX  \n3002: 11111111           XXXXXXXX\n"
+        );
+    }
+}
@@ -1,6 +1,15 @@
+pub mod block;
+pub mod device;
+pub mod dump;
 pub mod memory_bus;
 pub mod position;
+pub mod symbols;
+pub mod trace;
 
+pub use block::{BytePattern, crc32};
+pub use device::Device;
+pub use dump::{BitmapDump, Charset, DumpRenderer, DumpRow, Grouping, HexDump};
 pub use error::error::Result;
 pub use memory_bus::MemoryBus;
 pub use position::Position;
+pub use symbols::{SymbolFormat, SymbolTable};
@@ -1,24 +1,125 @@
+use crate::Result;
+use crate::block::{BytePattern, crc32};
+use crate::dump::{DumpRenderer, DumpRow, HexDump};
 use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
 /// 메모리 버스 인터페이스 - CPU가 메모리에 접근하는 방법 정의
 pub trait MemoryBus: Debug + Send + Sync {
-    /// 메모리에서 1바이트 읽기
-    fn read(&self, address: u16) -> u8;
+    /// 부수 효과 없이 1바이트 읽기 (디버거 화면, 디스어셈블, 덤프)
+    fn peek(&self, address: u16) -> u8;
+
+    /// CPU 읽기 - 상태 레지스터 클리어, FIFO 꺼내기 등 부수 효과가 있을 수 있음
+    fn read(&mut self, address: u16) -> u8 {
+        self.peek(address)
+    }
 
     /// 메모리에 1바이트 쓰기
     fn write(&mut self, address: u16, value: u8);
 
-    /// 메모리에서 연속된 바이트 읽기
-    fn read_block(&self, address: u16, count: usize) -> Vec<u8> {
+    /// CPU 쓰기 - 보호된 영역이면 오류를 돌려줄 수 있음
+    fn try_write(&mut self, address: u16, value: u8) -> Result<()> {
+        self.write(address, value);
+        Ok(())
+    }
+
+    /// IRQ 선 - 연결된 장치가 인터럽트를 요청하는 동안 true
+    fn irq(&self) -> bool {
+        false
+    }
+
+    /// NMI 선 - CPU는 false에서 true로 바뀔 때 한 번 처리 (엣지 트리거)
+    fn nmi(&self) -> bool {
+        false
+    }
+
+    /// CPU 읽기로 연속된 바이트 읽기 - 바이트마다 read의 부수 효과가 일어남
+    fn read_block(&mut self, address: u16, count: usize) -> Vec<u8> {
         (0..count)
             .map(|i| self.read(address.wrapping_add(i as u16)))
             .collect()
     }
 
+    /// 부수 효과 없이 연속된 바이트 읽기
+    fn peek_block(&self, address: u16, count: usize) -> Vec<u8> {
+        (0..count)
+            .map(|i| self.peek(address.wrapping_add(i as u16)))
+            .collect()
+    }
+
     /// 메모리에 연속된 바이트 쓰기
     fn write_block(&mut self, address: u16, data: &[u8]) {
         for (i, &value) in data.iter().enumerate() {
             self.write(address.wrapping_add(i as u16), value);
         }
     }
+
+    /// 범위를 pattern 반복으로 채우기
+    fn fill(&mut self, range: RangeInclusive<u16>, pattern: &[u8]) {
+        if pattern.is_empty() {
+            return;
+        }
+        for (i, address) in range.enumerate() {
+            self.write(address, pattern[i % pattern.len()]);
+        }
+    }
+
+    /// range를 dest로 복사 - 범위가 겹쳐도 원본 그대로 복사됨
+    fn copy(&mut self, range: RangeInclusive<u16>, dest: u16) {
+        let data = self.peek_block(*range.start(), range_len(&range));
+        self.write_block(dest, &data);
+    }
+
+    /// range와 other부터 같은 길이를 비교해 값이 다른 range 쪽 주소 목록
+    fn compare(&self, range: RangeInclusive<u16>, other: u16) -> Vec<u16> {
+        let start = *range.start();
+        let len = range_len(&range);
+        let data = self.peek_block(start, len);
+        let other_data = self.peek_block(other, len);
+        data.iter()
+            .zip(&other_data)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(offset, _)| start.wrapping_add(offset as u16))
+            .collect()
+    }
+
+    /// range 안에서 패턴이 시작하는 주소 목록
+    fn find(&self, range: RangeInclusive<u16>, pattern: &BytePattern) -> Vec<u16> {
+        let start = *range.start();
+        pattern
+            .find_in(&self.peek_block(start, range_len(&range)))
+            .into_iter()
+            .map(|offset| start.wrapping_add(offset as u16))
+            .collect()
+    }
+
+    /// 범위의 CRC-32
+    fn checksum(&self, range: RangeInclusive<u16>) -> u32 {
+        crc32(&self.peek_block(*range.start(), range_len(&range)))
+    }
+
+    /// 16바이트씩 16진수와 ASCII로 표시 (peek 사용)
+    fn dump(&self, addr: u16, size: usize) -> String {
+        self.dump_with(addr, size, &HexDump::default())
+    }
+
+    /// renderer로 표시한 문자열
+    fn dump_with(&self, addr: u16, size: usize, renderer: &dyn DumpRenderer) -> String {
+        renderer.render(addr, &self.peek_block(addr, size))
+    }
+
+    /// renderer로 나눈 줄 - GUI용
+    fn dump_rows(&self, addr: u16, size: usize, renderer: &dyn DumpRenderer) -> Vec<DumpRow> {
+        renderer.rows(addr, &self.peek_block(addr, size))
+    }
+}
+
+/// 포함 범위의 바이트 수 (start > end면 0)
+pub fn range_len(range: &RangeInclusive<u16>) -> usize {
+    if range.start() > range.end() {
+        0
+    } else {
+        (*range.end() - *range.start()) as usize + 1
+    }
 }
@@ -0,0 +1,286 @@
+//! 심볼 테이블 - 주소와 이름 매핑
+use crate::Result;
+use error::Error;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// 레이블+오프셋 표기를 허용하는 최대 거리
+const MAX_SYMBOL_OFFSET: u16 = 0xFF;
+
+/// 외부 심볼 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolFormat {
+    /// VICE 레이블 파일 (`al C:c0a3 .PRINT_STRING`)
+    Vice,
+    /// ca65/ld65 디버그 정보 (`sym id=0,name="PRINT_STRING",...,val=0xC0A3`)
+    Ca65Debug,
+    /// `PRINT_STRING = $C0A3` 형태의 목록
+    Assignments,
+}
+
+impl SymbolFormat {
+    /// 첫 유효 줄로 형식 추정
+    pub fn detect(text: &str) -> Self {
+        let first = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with(';'))
+            .unwrap_or_default();
+        if first.starts_with("al ") {
+            SymbolFormat::Vice
+        } else if first.starts_with("version") || first.starts_with("sym") {
+            SymbolFormat::Ca65Debug
+        } else {
+            SymbolFormat::Assignments
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    by_name: HashMap<String, u16>,
+    // 같은 주소에 이름이 여럿이면 먼저 등록된 이름 사용
+    by_address: BTreeMap<u16, String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 어셈블러 레이블 맵으로 생성
+    pub fn from_labels(labels: &HashMap<String, u16>) -> Self {
+        let mut labels: Vec<_> = labels.iter().collect();
+        labels.sort();
+        let mut table = Self::new();
+        for (name, &address) in labels {
+            table.insert(name.clone(), address);
+        }
+        table
+    }
+
+    /// 심볼 파일 읽기 (형식은 내용으로 판단)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text, SymbolFormat::detect(&text))
+    }
+
+    pub fn parse(text: &str, format: SymbolFormat) -> Result<Self> {
+        let mut table = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            let symbol = match format {
+                SymbolFormat::Vice => parse_vice_line(line),
+                SymbolFormat::Ca65Debug => parse_ca65_line(line),
+                SymbolFormat::Assignments => parse_assignment_line(line),
+            };
+            match symbol {
+                Ok(Some((name, address))) => table.insert(name, address),
+                Ok(None) => {}
+                Err(()) => return Err(Error::InvalidSymbolFile { line: index + 1 }),
+            }
+        }
+        Ok(table)
+    }
+
+    /// 다른 테이블의 심볼 추가 (같은 이름은 덮어씀)
+    pub fn merge(&mut self, other: &SymbolTable) {
+        for (name, &address) in &other.by_name {
+            self.insert(name.clone(), address);
+        }
+    }
+
+    /// 이미 있는 이름이면 새 주소로 옮김 - 이전 주소에는 같은 주소의 다른 이름이 남음
+    pub fn insert(&mut self, name: impl Into<String>, address: u16) {
+        let name = name.into();
+        if let Some(old) = self.by_name.insert(name.clone(), address)
+            && old != address
+            && self.by_address.get(&old) == Some(&name)
+        {
+            self.by_address.remove(&old);
+            let other = self
+                .by_name
+                .iter()
+                .filter(|&(_, &other)| other == old)
+                .map(|(other, _)| other)
+                .min();
+            if let Some(other) = other {
+                self.by_address.insert(old, other.clone());
+            }
+        }
+        self.by_address.entry(address).or_insert(name);
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    pub fn address_of(&self, name: &str) -> Option<u16> {
+        self.by_name.get(name).copied()
+    }
+
+    /// 주소에 정확히 붙은 이름
+    pub fn name_at(&self, address: u16) -> Option<&str> {
+        self.by_address.get(&address).map(String::as_str)
+    }
+
+    /// 주소 이전의 가장 가까운 심볼 (이름, 심볼 주소)
+    pub fn nearest(&self, address: u16) -> Option<(&str, u16)> {
+        self.by_address
+            .range(..=address)
+            .next_back()
+            .filter(|&(&symbol, _)| address - symbol <= MAX_SYMBOL_OFFSET)
+            .map(|(&symbol, name)| (name.as_str(), symbol))
+    }
+
+    /// `NAME`, `NAME+3` 또는 `$C0A3` 형태의 주소 표기
+    pub fn describe(&self, address: u16) -> String {
+        match self.nearest(address) {
+            Some((name, symbol)) if symbol == address => name.to_string(),
+            Some((name, symbol)) => format!("{}+{}", name, address - symbol),
+            None => format!("${:04X}", address),
+        }
+    }
+
+    /// 주소 순으로 (이름, 주소) 순회
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u16)> {
+        self.by_address
+            .iter()
+            .map(|(&address, name)| (name.as_str(), address))
+    }
+}
+
+// 심볼이 아닌 줄은 Ok(None), 형식 오류는 Err(())
+type ParsedLine = std::result::Result<Option<(String, u16)>, ()>;
+
+// al C:c0a3 .PRINT_STRING
+fn parse_vice_line(line: &str) -> ParsedLine {
+    let mut parts = line.split_whitespace();
+    if parts.next() != Some("al") {
+        return Ok(None);
+    }
+    let (Some(address), Some(name)) = (parts.next(), parts.next()) else {
+        return Err(());
+    };
+    let address = address.rsplit(':').next().unwrap_or(address);
+    let address = u16::from_str_radix(address, 16).map_err(|_| ())?;
+    Ok(Some((name.trim_start_matches('.').to_string(), address)))
+}
+
+// sym id=3,name="PRINT_STRING",addrsize=absolute,scope=0,def=5,val=0xC0A3,type=lab
+fn parse_ca65_line(line: &str) -> ParsedLine {
+    let Some(fields) = line.strip_prefix("sym") else {
+        return Ok(None);
+    };
+    let mut name = None;
+    let mut value = None;
+    for field in fields.trim().split(',') {
+        match field.split_once('=') {
+            Some(("name", text)) => name = Some(text.trim_matches('"').to_string()),
+            Some(("val", text)) => value = Some(parse_number(text).ok_or(())?),
+            _ => {}
+        }
+    }
+    // 값이 없는 심볼(임포트 등)은 건너뜀
+    match (name, value) {
+        (Some(name), Some(value)) => Ok(Some((name, value))),
+        (None, _) => Err(()),
+        _ => Ok(None),
+    }
+}
+
+// PRINT_STRING = $C0A3 ; 주석
+fn parse_assignment_line(line: &str) -> ParsedLine {
+    let line = line.split(';').next().unwrap_or_default();
+    let (name, value) = line.split_once('=').ok_or(())?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(());
+    }
+    let value = parse_number(value.trim()).ok_or(())?;
+    Ok(Some((name.to_string(), value)))
+}
+
+/// `$C0A3`, `0xC0A3`, `%1010`, `49315` 형태의 숫자
+fn parse_number(text: &str) -> Option<u16> {
+    let value = if let Some(hex) = text.strip_prefix('$') {
+        u32::from_str_radix(hex, 16)
+    } else if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16)
+    } else if let Some(binary) = text.strip_prefix('%') {
+        u32::from_str_radix(binary, 2)
+    } else {
+        text.parse::<u32>()
+    };
+    value.ok().and_then(|value| u16::try_from(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let mut symbols = SymbolTable::new();
+        symbols.insert("START", 0x0200);
+        symbols.insert("PRINT_STRING", 0xC0A3);
+
+        assert_eq!(symbols.describe(0xC0A3), "PRINT_STRING");
+        assert_eq!(symbols.describe(0x0203), "START+3");
+        assert_eq!(symbols.describe(0x0400), "$0400");
+        assert_eq!(symbols.address_of("START"), Some(0x0200));
+    }
+
+    #[test]
+    fn test_rebind_name() {
+        let mut symbols = SymbolTable::new();
+        symbols.insert("LOOP", 0x0200);
+        symbols.insert("START", 0x0200);
+        symbols.insert("LOOP", 0x0210);
+
+        assert_eq!(symbols.address_of("LOOP"), Some(0x0210));
+        assert_eq!(symbols.name_at(0x0210), Some("LOOP"));
+        assert_eq!(symbols.name_at(0x0200), Some("START"), "other name remains");
+
+        symbols.insert("LOOP", 0x0220);
+        assert_eq!(symbols.name_at(0x0210), None);
+        assert_eq!(symbols.describe(0x0212), "START+18");
+        assert_eq!(
+            symbols.iter().collect::<Vec<_>>(),
+            [("START", 0x0200), ("LOOP", 0x0220)]
+        );
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let vice = "al C:c0a3 .PRINT_STRING\nal 0200 .START\n";
+        assert_eq!(SymbolFormat::detect(vice), SymbolFormat::Vice);
+        let symbols = SymbolTable::parse(vice, SymbolFormat::Vice).unwrap();
+        assert_eq!(symbols.address_of("PRINT_STRING"), Some(0xC0A3));
+
+        let dbg = "version\tmajor=2,minor=0\n\
+                   sym\tid=0,name=\"RESET\",addrsize=absolute,scope=0,def=1,val=0xE000,seg=0,type=lab\n\
+                   sym\tid=1,name=\"chrout\",addrsize=absolute,scope=0,def=2,type=imp\n";
+        assert_eq!(SymbolFormat::detect(dbg), SymbolFormat::Ca65Debug);
+        let symbols = SymbolTable::parse(dbg, SymbolFormat::Ca65Debug).unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols.name_at(0xE000), Some("RESET"));
+
+        let list = "; ROM entry points\nCHROUT = $FFD2\nSCREEN = 1024\n";
+        assert_eq!(SymbolFormat::detect(list), SymbolFormat::Assignments);
+        let symbols = SymbolTable::parse(list, SymbolFormat::Assignments).unwrap();
+        assert_eq!(symbols.address_of("SCREEN"), Some(0x0400));
+
+        assert!(matches!(
+            SymbolTable::parse("CHROUT $FFD2", SymbolFormat::Assignments),
+            Err(Error::InvalidSymbolFile { line: 1 })
+        ));
+    }
+}
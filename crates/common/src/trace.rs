@@ -0,0 +1,28 @@
+//! 디버그 출력 제어
+//!
+//! 라이브러리 내부의 진단 메시지는 `trace!` 매크로로 출력합니다.
+//! stdout을 프로토콜 통신에 사용하는 경우(GDB 파이프, DAP 등) 출력을 끌 수 있습니다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// 디버그 출력 활성화 여부 설정
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 디버그 출력 활성화 여부
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 디버그 출력이 활성화된 경우에만 stdout에 출력
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::is_enabled() {
+            println!($($arg)*);
+        }
+    };
+}
@@ -9,8 +9,11 @@ use std::sync::mpsc::{Receiver, TryRecvError};
 use std::sync::{Arc, Mutex};
 use types::Instruction;
 
+/// IRQ/NMI 진입에 걸리는 사이클
+const INTERRUPT_CYCLES: u64 = 7;
+
 /// CPU 인터럽트 타입
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterruptType {
     /// 리셋 인터럽트
     Reset,
@@ -26,12 +29,43 @@ pub enum InterruptType {
     Breakpoint,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// 디버거 정지 시 메모리 접근 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryAccess {
+    Read,
+    Write,
+}
+
+/// 디버거에 의한 일시 정지 사유
+/// 정지(Halted)와 달리 CPU 상태를 건드리지 않으므로 그대로 실행을 재개할 수 있음
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// 실행 브레이크포인트 (브레이크포인트 ID)
+    Breakpoint(usize),
+    /// 메모리 접근 브레이크포인트
+    Watchpoint {
+        id: usize,
+        address: u16,
+        access: MemoryAccess,
+    },
+    /// 단일 스텝 실행 완료
+    StepComplete,
+    /// 잘못된 명령어 (PC는 해당 명령어를 가리킴)
+    IllegalOpcode(u8),
+    /// 사용자 요청에 의한 일시 정지
+    UserPause,
+    /// 쓰기 보호된 영역(ROM) 쓰기 시도
+    WriteProtected { address: u16 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CPUState {
     /// CPU가 정상적으로 실행 중
     Running,
     /// CPU가 정지됨 (BRK, 잘못된 명령어 등으로 인해)
     Halted(InterruptType),
+    /// CPU가 디버거에 의해 일시 정지됨 (재개 가능)
+    Paused(StopReason),
     /// CPU가 대기 중 (실행 준비는 되어있으나 아직 시작하지 않음)
     Ready,
 }
@@ -46,12 +80,22 @@ pub struct CPU {
     pub(crate) state: CPUState,
     /// 현재 명령어의 사이클 정보
     pub(crate) cycles: u8,
+    /// 실행 중인 명령어(또는 인터럽트 진입 시 중단된 위치) 주소
+    pub(crate) instruction_address: u16,
+    /// 생성 이후 누적 사이클
+    pub(crate) total_cycles: u64,
     /// 이벤트 핸들러
     pub(crate) event_handlers: Vec<EventHandler>,
     /// 디버깅 활성화 여부
     pub(crate) debug_enabled: bool,
     /// 인터럽트 채널
     pub(crate) interrupt_channel: Option<Receiver<InterruptType>>,
+    /// 일시 정지 요청 채널
+    pub(crate) stop_channel: Option<Receiver<StopReason>>,
+    /// 직전에 본 NMI 선 레벨 (엣지 검출)
+    pub(crate) nmi_line: bool,
+    /// 브레이크포인트 위치에서 재개 - 그 명령어는 실행 전 정지 검사를 건너뜀
+    pub(crate) step_over_stop: bool,
 }
 
 impl Default for CPU {
@@ -71,7 +115,12 @@ impl CPU {
             event_handlers: Vec::new(),
             debug_enabled: false,
             cycles: 0,
+            instruction_address: 0,
+            total_cycles: 0,
             interrupt_channel: None,
+            stop_channel: None,
+            nmi_line: false,
+            step_over_stop: false,
         }
     }
 
@@ -90,17 +139,56 @@ impl CPU {
         self.interrupt_channel = None;
     }
 
+    /// 일시 정지 요청 채널 설정
+    /// 디버거가 브레이크포인트 등으로 CPU를 멈출 때 사용
+    pub fn set_stop_channel(&mut self, rx: Receiver<StopReason>) {
+        self.stop_channel = Some(rx);
+    }
+
+    /// 일시 정지 요청 채널 제거
+    pub fn clear_stop_channel(&mut self) {
+        self.stop_channel = None;
+    }
+
+    /// 현재 CPU 상태 가져오기
+    pub fn get_state(&self) -> CPUState {
+        self.state
+    }
+
+    /// CPU 일시 정지 - 레지스터, 스택, 플래그는 그대로 유지
+    pub fn pause(&mut self, reason: StopReason) {
+        self.state = CPUState::Paused(reason);
+        // 같은 명령어에서 발생한 나머지 요청은 버림
+        if let Some(rx) = &self.stop_channel {
+            while rx.try_recv().is_ok() {}
+        }
+    }
+
+    /// 일시 정지된 CPU를 실행 가능 상태로 되돌림
+    /// 브레이크포인트에서 멈췄으면 그 명령어는 다시 멈추지 않고 실행
+    pub fn resume(&mut self) {
+        if let CPUState::Paused(reason) = self.state {
+            self.state = CPUState::Running;
+            self.step_over_stop = matches!(reason, StopReason::Breakpoint(_));
+        }
+    }
+
+    /// 다음 명령어는 실행 전 정지 검사 없이 실행 (디버거의 한 단계 실행)
+    pub fn skip_next_stop_check(&mut self) {
+        self.step_over_stop = true;
+    }
+
     /// CPU를 특정 이유로 정지시킴
     pub fn halt_with_reason(&mut self, reason: InterruptType) {
         self.state = CPUState::Halted(reason);
         match reason {
-            InterruptType::Reset => println!("[CPU] CPU halted: Reset requested"),
-            InterruptType::IRQ => println!("[CPU] CPU halted: IRQ received"),
-            InterruptType::NMI => println!("[CPU] CPU halted: NMI received"),
-            InterruptType::BRK => println!("[CPU] CPU halted: BRK instruction executed"),
-            InterruptType::Breakpoint => println!("[CPU] CPU halted: Breakpoint hit"),
+            InterruptType::Reset => common::trace!("[CPU] CPU halted: Reset requested"),
+            InterruptType::IRQ => common::trace!("[CPU] CPU halted: IRQ received"),
+            InterruptType::NMI => common::trace!("[CPU] CPU halted: NMI received"),
+            InterruptType::BRK => common::trace!("[CPU] CPU halted: BRK instruction executed"),
+            InterruptType::Breakpoint => common::trace!("[CPU] CPU halted: Breakpoint hit"),
             InterruptType::IllegalOpcode => {
-                println!("[CPU] CPU halted: Illegal opcode encountered")
+                common::trace!("[CPU] CPU halted: Illegal opcode encountered")
             }
         }
     }
@@ -128,7 +216,7 @@ impl CPU {
 
     /// CPU 클럭 실행
     pub fn clock(&mut self) {
-        println!("[+] CPU clock cycle");
+        common::trace!("[+] CPU clock cycle");
     }
 
     /// CPU ID 가져오기
@@ -196,29 +284,33 @@ impl CPU {
     /// opcode 가져오기
     fn fetch_opcode(&mut self) -> Result<u8> {
         let pc = self.get_pc();
-        let opcode = self.read_memory(pc)?;
+        let opcode = self.fetch_byte(pc)?;
         self.increment_pc(1);
         Ok(opcode)
     }
 
     /// 메모리에서 PC 위치의 데이터 읽기
-    fn fetch(&mut self) -> Result<Fetch> {
+    /// 디버거에 의해 일시 정지된 경우 None 반환
+    fn fetch(&mut self) -> Result<Option<Fetch>> {
         // PC 레지스터에서 주소 가져오기
+        let pc = self.get_pc();
         let opcode = self.fetch_opcode()?;
 
-        let ins = self.instruction.get_instruction_info(opcode);
-
         // 유효하지 않은 opcode 처리
-        if ins.is_none() {
-            println!("[CPU] Invalid opcode: 0x{:02X}", opcode);
+        let Some(ins) = self.instruction.get_instruction_info(opcode) else {
+            common::trace!("[CPU] Invalid opcode: 0x{:02X}", opcode);
+            if self.stop_channel.is_some() {
+                // 디버거가 연결된 경우 PC를 되돌리고 일시 정지
+                self.set_pc(pc);
+                self.pause(StopReason::IllegalOpcode(opcode));
+                return Ok(None);
+            }
             self.halt_with_reason(InterruptType::IllegalOpcode);
             return Err(Error::InvalidOpcode(opcode));
-        }
-
-        let ins = ins.unwrap();
+        };
 
         let operand = self.fetch_operand(&ins)?;
-        Ok(Fetch::new(ins, operand, opcode))
+        Ok(Some(Fetch::new(ins, operand, opcode)))
     }
 
     /// 명령어의 피연산자(operand) 가져오기
@@ -231,7 +323,7 @@ impl CPU {
             1 => self.fetch_one_byte_operand(ins),
             2 => self.fetch_two_byte_operand(),
             _ => {
-                println!("[DEBUG] Unexpected operand size: {}", need);
+                common::trace!("[DEBUG] Unexpected operand size: {}", need);
                 Ok(vec![])
             }
         }
@@ -254,13 +346,13 @@ impl CPU {
                 | Instruction::BVS(_)
         );
 
-        let operand = self.read_memory(pc)?;
+        let operand = self.fetch_byte(pc)?;
         self.increment_pc(1);
 
         if is_branch {
             // 분기 명령어인 경우 signed byte로 표시
             let signed_operand = operand as i8;
-            println!("[DEBUG] Fetched 1-byte branch offset: {:+}", signed_operand);
+            common::trace!("[DEBUG] Fetched 1-byte branch offset: {:+}", signed_operand);
         }
         // TODO: 출력을 Debug 모드에서만 하도록 변경
         // } else {
@@ -273,41 +365,93 @@ impl CPU {
     /// 2바이트 피연산자 가져오기
     fn fetch_two_byte_operand(&mut self) -> Result<Vec<u8>> {
         let pc = self.get_pc();
-        let low_byte = self.read_memory(pc)?;
+        let low_byte = self.fetch_byte(pc)?;
         self.increment_pc(1);
 
-        let high_byte = self.read_memory(self.get_pc())?;
+        let high_byte = self.fetch_byte(self.get_pc())?;
         self.increment_pc(1);
 
-        println!(
+        common::trace!(
             "[DEBUG] Fetched 2-byte operand: 0x{:02X}{:02X}",
-            high_byte, low_byte
+            high_byte,
+            low_byte
         );
 
         Ok(vec![low_byte, high_byte])
     }
 
-    /// 메모리 읽기 (MemoryBus 사용)
-    pub fn read_memory(&self, address: u16) -> Result<u8> {
+    /// 명령어 바이트 읽기 - 데이터 접근이 아니므로 MemoryRead 이벤트를 보내지 않음
+    /// 버스에는 실제 읽기이므로 장치의 부수 효과가 일어남
+    fn fetch_byte(&self, address: u16) -> Result<u8> {
         if let Some(bus) = &self.memory_bus {
-            let value = bus
+            Ok(bus
                 .lock()
                 .map_err(|_| Error::FailedToLockMemoryBus)?
-                .read(address);
-            self.emit_event(CPUEvent::MemoryRead { address, value });
-            Ok(value)
+                .read(address))
         } else {
             Err(Error::MemoryBusConnectionFailed)
         }
     }
 
-    /// 메모리 쓰기 (MemoryBus 사용)
-    pub fn write_memory(&self, address: u16, value: u8) -> Result<()> {
+    /// 이벤트와 부수 효과 없이 메모리 읽기 (디버거 화면 등 CPU 실행과 무관한 접근용)
+    pub fn peek_memory(&self, address: u16) -> Result<u8> {
+        if let Some(bus) = &self.memory_bus {
+            Ok(bus
+                .lock()
+                .map_err(|_| Error::FailedToLockMemoryBus)?
+                .peek(address))
+        } else {
+            Err(Error::MemoryBusConnectionFailed)
+        }
+    }
+
+    /// 이벤트 없이 메모리 쓰기 (디버거에서 메모리 수정 시 사용)
+    pub fn poke_memory(&self, address: u16, value: u8) -> Result<()> {
         if let Some(bus) = &self.memory_bus {
             bus.lock()
                 .map_err(|_| Error::FailedToLockMemoryBus)?
                 .write(address, value);
-            self.emit_event(CPUEvent::MemoryWrite { address, value });
+            Ok(())
+        } else {
+            Err(Error::MemoryBusConnectionFailed)
+        }
+    }
+
+    /// 메모리 읽기 (MemoryBus 사용)
+    pub fn read_memory(&self, address: u16) -> Result<u8> {
+        let value = self.fetch_byte(address)?;
+        self.emit_event(CPUEvent::MemoryRead {
+            address,
+            value,
+            instruction: self.instruction_address,
+            cycle: self.total_cycles,
+        });
+        Ok(value)
+    }
+
+    /// 메모리 쓰기 (MemoryBus 사용)
+    pub fn write_memory(&self, address: u16, value: u8) -> Result<()> {
+        if let Some(bus) = &self.memory_bus {
+            let result = bus
+                .lock()
+                .map_err(|_| Error::FailedToLockMemoryBus)?
+                .try_write(address, value);
+            if let Err(Error::WriteProtected(address)) = result {
+                // 쓰기는 무시되고 디버거에 알림
+                self.emit_event(CPUEvent::WriteProtected {
+                    address,
+                    value,
+                    instruction: self.instruction_address,
+                });
+                return Ok(());
+            }
+            result?;
+            self.emit_event(CPUEvent::MemoryWrite {
+                address,
+                value,
+                instruction: self.instruction_address,
+                cycle: self.total_cycles,
+            });
             Ok(())
         } else {
             Err(Error::MemoryBusConnectionFailed)
@@ -315,6 +459,7 @@ impl CPU {
     }
 
     pub fn run(&mut self) -> Result<()> {
+        self.resume();
         self.state = CPUState::Running;
         while self.state == CPUState::Running {
             self.step()?;
@@ -326,13 +471,17 @@ impl CPU {
     pub fn step(&mut self) -> Result<()> {
         match self.state {
             CPUState::Halted(reason) => {
-                println!("[CPU] CPU is halted: {:?}", reason);
+                common::trace!("[CPU] CPU is halted: {:?}", reason);
                 Ok(())
             }
             CPUState::Ready => {
                 self.state = CPUState::Running;
                 self.execute_cycle()
             }
+            CPUState::Paused(_) => {
+                self.resume();
+                self.execute_cycle()
+            }
             CPUState::Running => self.execute_cycle(),
         }
     }
@@ -343,14 +492,29 @@ impl CPU {
             return Ok(());
         }
 
+        let step_over = std::mem::take(&mut self.step_over_stop);
+
         // Check for pending interrupts
         if let Some(interrupt) = self.check_interrupts() {
             self.handle_interrupt(interrupt)?;
             return Ok(());
         }
 
+        // 실행 전 정지 검사 - 브레이크포인트에서는 그 명령어를 실행하지 않고 멈춤
+        let address = self.get_pc();
+        if !step_over {
+            self.emit_event(CPUEvent::InstructionFetch { address });
+            if let Some(reason) = self.check_stop_request() {
+                self.pause(reason);
+                return Ok(());
+            }
+        }
+
         // 1. 명령어 가져오기
-        let fetch = self.fetch()?;
+        self.instruction_address = address;
+        let Some(fetch) = self.fetch()? else {
+            return Ok(());
+        };
         let decode = self.instruction.decode(fetch.clone())?;
 
         // 2. 사이클 설정
@@ -358,20 +522,44 @@ impl CPU {
 
         // 3. 명령어 실행
         self.execute(decode.clone())?;
+        self.total_cycles += self.cycles as u64;
 
         self.emit_event(CPUEvent::StateChanged {
             state: self.get_context().unwrap(),
         });
 
         self.emit_event(CPUEvent::InstructionExecuted {
+            address,
             pc: self.get_pc(),
             opcode: fetch.opcode,
             operand: fetch.to_operand_u16(),
-            cycles: decode.cycles,
+            // 분기/페이지 경계 추가 사이클 포함
+            cycles: self.cycles,
+            status: self.get_value(RegisterType::P).as_u8(),
         });
+
+        // 이벤트 처리 중 요청된 일시 정지 반영 (BRK 등으로 정지된 경우는 유지)
+        if let Some(reason) = self.check_stop_request()
+            && self.state == CPUState::Running
+        {
+            self.pause(reason);
+        }
         Ok(())
     }
 
+    /// 일시 정지 요청 체크
+    fn check_stop_request(&mut self) -> Option<StopReason> {
+        let rx = self.stop_channel.as_ref()?;
+        match rx.try_recv() {
+            Ok(reason) => Some(reason),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.stop_channel = None;
+                None
+            }
+        }
+    }
+
     /// 인터럽트 체크
     fn check_interrupts(&mut self) -> Option<InterruptType> {
         // 실제 하드웨어에서는 여기서 외부 인터럽트 핀의 상태를 체크합니다
@@ -381,7 +569,7 @@ impl CPU {
             match rx.try_recv() {
                 Ok(interrupt_type) => {
                     // 인터럽트 발생
-                    println!("[CPU] Received interrupt: {:?}", interrupt_type);
+                    common::trace!("[CPU] Received interrupt: {:?}", interrupt_type);
                     // self.halt_with_reason(interrupt_type);
                     return Some(interrupt_type);
                 }
@@ -408,16 +596,22 @@ impl CPU {
         None
     }
 
-    /// NMI 핀 상태 체크 (하드웨어 구현 필요)
-    fn check_nmi_pin(&self) -> bool {
-        // TODO: 실제 하드웨어 NMI 핀 상태 체크 구현
-        false
+    /// NMI 핀 상태 체크 - 버스의 NMI 선이 올라가는 순간에만 true
+    fn check_nmi_pin(&mut self) -> bool {
+        let line = self
+            .memory_bus
+            .as_ref()
+            .is_some_and(|bus| bus.lock().is_ok_and(|bus| bus.nmi()));
+        let edge = line && !self.nmi_line;
+        self.nmi_line = line;
+        edge
     }
 
-    /// IRQ 핀 상태 체크 (하드웨어 구현 필요)
+    /// IRQ 핀 상태 체크 - 버스에 연결된 장치의 IRQ 출력
     fn check_irq_pin(&self) -> bool {
-        // TODO: 실제 하드웨어 IRQ 핀 상태 체크 구현
-        false
+        self.memory_bus
+            .as_ref()
+            .is_some_and(|bus| bus.lock().is_ok_and(|bus| bus.irq()))
     }
 
     /// 스택 포인터의 실제 메모리 주소 계산
@@ -457,7 +651,26 @@ impl CPU {
     }
 
     /// 인터럽트 처리
+    // 인터럽트 핸들러로 이동하고 진입 이벤트 발생
+    fn enter_interrupt(&mut self, interrupt: InterruptType, handler: u16) {
+        let return_address = self.get_pc();
+        // 핸들러 안에서 같은 IRQ가 다시 들어오지 않도록 (RTI가 복원)
+        self.set_flag(StatusRegister::INTERRUPT_DISABLE, true);
+        self.set_pc(handler);
+        self.total_cycles += INTERRUPT_CYCLES;
+        self.emit_event(CPUEvent::StateChanged {
+            state: self.get_context().unwrap(),
+        });
+        self.emit_event(CPUEvent::Interrupt {
+            interrupt,
+            return_address,
+            handler,
+        });
+    }
+
     pub fn handle_interrupt(&mut self, interrupt: InterruptType) -> Result<()> {
+        // 스택/벡터 접근은 중단된 위치에서 일어난 것으로 기록
+        self.instruction_address = self.get_pc();
         match interrupt {
             InterruptType::Reset => {
                 self.reset();
@@ -474,7 +687,7 @@ impl CPU {
                 let low = self.read_memory(0xFFFA)?;
                 let high = self.read_memory(0xFFFB)?;
                 let nmi_vector = ((high as u16) << 8) | (low as u16);
-                self.set_pc(nmi_vector);
+                self.enter_interrupt(interrupt, nmi_vector);
             }
             InterruptType::IRQ => {
                 // IRQ vector at 0xFFFE-0xFFFF (same as BRK)
@@ -484,7 +697,7 @@ impl CPU {
                     let low = self.read_memory(0xFFFE)?;
                     let high = self.read_memory(0xFFFF)?;
                     let irq_vector = ((high as u16) << 8) | (low as u16);
-                    self.set_pc(irq_vector);
+                    self.enter_interrupt(interrupt, irq_vector);
                 }
             }
             _ => self.halt_with_reason(interrupt),
@@ -501,6 +714,31 @@ impl CPU {
     pub fn get_cycles(&self) -> u8 {
         self.cycles
     }
+
+    /// 누적 사이클 수 (IRQ/NMI 진입 포함)
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    /// 누적 사이클 수 설정 (스냅샷 복원용)
+    pub fn set_total_cycles(&mut self, cycles: u64) {
+        self.total_cycles = cycles;
+    }
+
+    /// CPU 상태 설정 (스냅샷 복원용)
+    pub fn set_state(&mut self, state: CPUState) {
+        self.state = state;
+    }
+
+    /// 직전에 본 NMI 선 레벨
+    pub fn nmi_line(&self) -> bool {
+        self.nmi_line
+    }
+
+    /// NMI 선 레벨 설정 (스냅샷 복원용) - 복원 직후 같은 레벨을 새 엣지로 보지 않도록
+    pub fn set_nmi_line(&mut self, line: bool) {
+        self.nmi_line = line;
+    }
 }
 
 #[cfg(test)]
@@ -1,28 +1,49 @@
 use crate::CPU;
+use crate::cpu::InterruptType;
 use crate::register::{RegisterValue, Registers};
 
-pub type EventHandler = Box<dyn Fn(&CPUEvent)>;
+/// 이벤트 핸들러 - CPU를 작업 스레드로 옮길 수 있도록 Send 필요
+pub type EventHandler = Box<dyn Fn(&CPUEvent) + Send>;
 
 #[derive(Debug, Clone)]
 pub enum CPUEvent {
+    /// 명령어 실행 직전 (address: 명령어 시작 주소) - 실행 위치 브레이크포인트 검사용
+    InstructionFetch {
+        address: u16,
+    },
+    /// 명령어 실행 완료 (address: 명령어 시작 주소, pc: 다음에 실행할 주소, status: 실행 후 P)
     InstructionExecuted {
+        address: u16,
         pc: u16,
         opcode: u8,
         cycles: u8,
         operand: u16,
+        status: u8,
     },
     RegisterChanged {
         register: String,
         value: u16,
         old_value: u16,
     },
+    /// 데이터 읽기 (instruction: 접근한 명령어 주소, cycle: 그 명령어 시작 시 누적 사이클)
     MemoryRead {
         address: u16,
         value: u8,
+        instruction: u16,
+        cycle: u64,
     },
+    /// 데이터 쓰기 (instruction/cycle은 MemoryRead와 같음)
     MemoryWrite {
         address: u16,
         value: u8,
+        instruction: u16,
+        cycle: u64,
+    },
+    /// 쓰기 보호된 영역 쓰기 시도 (쓰기는 무시됨)
+    WriteProtected {
+        address: u16,
+        value: u8,
+        instruction: u16,
     },
     FlagChanged {
         flag: String,
@@ -32,6 +53,12 @@ pub enum CPUEvent {
     StateChanged {
         state: CPUContext,
     },
+    /// IRQ/NMI 진입 (복귀 주소와 상태 레지스터를 스택에 넣은 뒤)
+    Interrupt {
+        interrupt: InterruptType,
+        return_address: u16,
+        handler: u16,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +114,7 @@ impl std::fmt::Debug for CPU {
             .field("instruction", &self.instruction)
             .field("state", &self.state)
             .field("cycles", &self.cycles)
+            .field("total_cycles", &self.total_cycles)
             // event_handlers는 제외
             .field("debug_enabled", &self.debug_enabled)
             .finish()
@@ -101,9 +129,14 @@ impl std::clone::Clone for CPU {
             instruction: self.instruction.clone(),
             state: self.state,
             cycles: self.cycles,
+            instruction_address: self.instruction_address,
+            total_cycles: self.total_cycles,
             event_handlers: vec![],
             debug_enabled: self.debug_enabled,
             interrupt_channel: None,
+            stop_channel: None,
+            nmi_line: self.nmi_line,
+            step_over_stop: self.step_over_stop,
         }
     }
 }
@@ -141,10 +174,12 @@ mod tests {
         }));
 
         let event = CPUEvent::InstructionExecuted {
+            address: 0x0FFF,
             pc: 0x8000,
             opcode: 0x69,
             cycles: 2,
             operand: 0x42,
+            status: 0x24,
         };
         println!("emit event: {:?}", event);
         cpu.emit_event(event);
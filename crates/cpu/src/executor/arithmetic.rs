@@ -8,7 +8,7 @@ use types::{AddressModeValue, Instruction};
 
 impl CPU {
     pub(super) fn execute_arithmetic(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing arithmetic instruction: {:?}",
             decoded.instruction
         );
@@ -302,7 +302,7 @@ impl CPU {
     }
 
     fn dey(&mut self) -> Result<()> {
-        println!("[CPU] Executing DEY");
+        common::trace!("[CPU] Executing DEY");
         let value = self.get_value(RegisterType::Y).as_u8().wrapping_sub(1);
         self.set_value(RegisterType::Y, RegisterData::Bit8(value));
 
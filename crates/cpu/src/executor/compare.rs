@@ -118,7 +118,7 @@ impl CompareOperation for CPU {
 
 impl CPU {
     pub(super) fn execute_compare(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing compare instruction: {:?}",
             decoded.instruction
         );
@@ -5,7 +5,7 @@ use types::Instruction;
 
 impl CPU {
     pub(super) fn execute_flag(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing flag instruction: {:?}",
             decoded.instruction
         );
@@ -5,7 +5,7 @@ use types::{AddressModeValue, Instruction};
 
 impl CPU {
     pub(super) fn execute_jump(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing jump instruction: {:?}",
             decoded.instruction
         );
@@ -27,9 +27,10 @@ impl CPU {
                     _ => return Err(Error::InvalidInstruction { inst_type: "jump" }),
                 };
 
-                println!(
+                common::trace!(
                     "[CPU] Executing branch instruction: {:?} with offset: 0x{:02X}",
-                    instruction, decoded.operand_value
+                    instruction,
+                    decoded.operand_value
                 );
 
                 self.branch_if_flag(flag, condition, decoded)
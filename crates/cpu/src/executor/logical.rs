@@ -222,7 +222,7 @@ impl LogicalOperation for CPU {
 
 impl CPU {
     pub(super) fn execute_logical(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing logical instruction: {:?}",
             decoded.instruction
         );
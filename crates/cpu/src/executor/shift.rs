@@ -285,7 +285,7 @@ impl ShiftOperation for CPU {
 
 impl CPU {
     pub(super) fn execute_shift(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing shift instruction: {:?}",
             decoded.instruction
         );
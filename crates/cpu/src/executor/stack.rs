@@ -5,7 +5,7 @@ use types::Instruction;
 
 impl CPU {
     pub(super) fn execute_stack(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing stack instruction: {:?}",
             decoded.instruction
         );
@@ -4,7 +4,7 @@ use error::Error;
 use types::Instruction;
 impl CPU {
     pub(super) fn execute_system(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing system instruction: {:?}",
             decoded.instruction
         );
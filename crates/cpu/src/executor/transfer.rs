@@ -274,7 +274,7 @@ impl TransferOperation for CPU {
 
 impl CPU {
     pub(super) fn execute_transfer(&mut self, decoded: DecodedInstruction) -> Result<()> {
-        println!(
+        common::trace!(
             "[CPU] Executing transfer instruction: {:?}",
             decoded.instruction
         );
@@ -11,6 +11,6 @@ pub mod register;
 
 pub use common::Result;
 // 주요 타입 재내보내기
-pub use cpu::{CPU, InterruptType};
+pub use cpu::{CPU, CPUState, InterruptType, MemoryAccess, StopReason};
 pub use cpu_event::{CPUContext, CPUEvent, EventHandler};
 pub use register::{RegisterData, RegisterType};
@@ -35,7 +35,7 @@ impl MockMemoryBus {
 }
 
 impl MemoryBus for MockMemoryBus {
-    fn read(&self, address: u16) -> u8 {
+    fn peek(&self, address: u16) -> u8 {
         self.memory[address as usize]
     }
 
@@ -0,0 +1,221 @@
+//! 메모리 접근 기록 - 주소별 읽기/쓰기 횟수, 마지막 쓰기 명령어, 최근 접근 목록과 히트맵
+use cpu::MemoryAccess;
+use std::collections::VecDeque;
+
+/// 기본 최근 접근 보관 수
+pub const DEFAULT_HISTORY: usize = 256;
+
+/// 접근 한 건
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessRecord {
+    pub address: u16,
+    pub value: u8,
+    pub access: MemoryAccess,
+    /// 접근한 명령어 주소
+    pub pc: u16,
+    /// 접근한 명령어 시작 시점의 누적 사이클
+    pub cycle: u64,
+}
+
+/// 주소별 누적 통계
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AddressAccess {
+    pub reads: u64,
+    pub writes: u64,
+    /// 마지막으로 이 주소에 쓴 명령어 주소
+    pub last_writer: Option<u16>,
+    pub last_access_cycle: Option<u64>,
+}
+
+/// 히트맵에 표시할 값
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapKind {
+    Reads,
+    Writes,
+    Total,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLog {
+    enabled: bool,
+    counters: Vec<AddressAccess>,
+    recent: VecDeque<AccessRecord>,
+    capacity: usize,
+}
+
+impl Default for AccessLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY)
+    }
+}
+
+impl AccessLog {
+    /// capacity: 최근 접근 보관 수
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            enabled: false,
+            counters: vec![AddressAccess::default(); 0x10000],
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.fill(AddressAccess::default());
+        self.recent.clear();
+    }
+
+    /// 최근 접근 보관 수 변경 - 줄이면 오래된 것부터 버림
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.recent.len() > capacity {
+            self.recent.pop_front();
+        }
+    }
+
+    pub fn record(&mut self, record: AccessRecord) {
+        let counter = &mut self.counters[record.address as usize];
+        match record.access {
+            MemoryAccess::Read => counter.reads += 1,
+            MemoryAccess::Write => {
+                counter.writes += 1;
+                counter.last_writer = Some(record.pc);
+            }
+        }
+        counter.last_access_cycle = Some(record.cycle);
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(record);
+    }
+
+    pub fn stats(&self, address: u16) -> AddressAccess {
+        self.counters[address as usize]
+    }
+
+    /// 최근 접근 (오래된 것부터)
+    pub fn recent(&self) -> Vec<AccessRecord> {
+        self.recent.iter().copied().collect()
+    }
+
+    /// 최근 접근 중 address에 대한 것만
+    pub fn history(&self, address: u16) -> Vec<AccessRecord> {
+        self.recent
+            .iter()
+            .filter(|record| record.address == address)
+            .copied()
+            .collect()
+    }
+
+    fn count(&self, address: usize, kind: HeatmapKind) -> u64 {
+        let counter = &self.counters[address];
+        match kind {
+            HeatmapKind::Reads => counter.reads,
+            HeatmapKind::Writes => counter.writes,
+            HeatmapKind::Total => counter.reads + counter.writes,
+        }
+    }
+
+    /// 페이지(상위 바이트)별 한 줄, 하위 바이트별 한 칸의 256x256 CSV
+    pub fn heatmap_csv(&self, kind: HeatmapKind) -> String {
+        let mut csv = String::from("page");
+        for low in 0..0x100 {
+            csv.push_str(&format!(",{:02X}", low));
+        }
+        csv.push('\n');
+        for page in 0..0x100 {
+            csv.push_str(&format!("{:02X}", page));
+            for low in 0..0x100 {
+                csv.push_str(&format!(",{}", self.count(page << 8 | low, kind)));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// 256x256 PPM(P6) 이미지 - 빨강은 쓰기, 초록은 읽기 (로그 스케일)
+    pub fn heatmap_ppm(&self) -> Vec<u8> {
+        let max_reads = self.counters.iter().map(|c| c.reads).max().unwrap_or(0);
+        let max_writes = self.counters.iter().map(|c| c.writes).max().unwrap_or(0);
+        let scale = |count: u64, max: u64| {
+            if count == 0 {
+                0
+            } else {
+                // 한 번이라도 접근하면 보이도록 최소 밝기 64
+                let ratio = ((count as f64).ln_1p() / (max as f64).ln_1p()).min(1.0);
+                (64.0 + ratio * 191.0) as u8
+            }
+        };
+
+        let mut image = b"P6\n256 256\n255\n".to_vec();
+        for counter in &self.counters {
+            image.push(scale(counter.writes, max_writes));
+            image.push(scale(counter.reads, max_reads));
+            image.push(0);
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(address: u16, access: MemoryAccess, pc: u16, cycle: u64) -> AccessRecord {
+        AccessRecord {
+            address,
+            value: 0,
+            access,
+            pc,
+            cycle,
+        }
+    }
+
+    #[test]
+    fn test_counters_and_history() {
+        let mut log = AccessLog::new(2);
+        log.record(access(0x0010, MemoryAccess::Write, 0x0200, 2));
+        log.record(access(0x0010, MemoryAccess::Read, 0x0204, 5));
+        log.record(access(0x0011, MemoryAccess::Write, 0x0206, 8));
+
+        assert_eq!(
+            log.stats(0x0010),
+            AddressAccess {
+                reads: 1,
+                writes: 1,
+                last_writer: Some(0x0200),
+                last_access_cycle: Some(5),
+            }
+        );
+        // 가장 오래된 접근은 밀려남
+        assert_eq!(log.recent().len(), 2);
+        assert_eq!(
+            log.history(0x0010),
+            vec![access(0x0010, MemoryAccess::Read, 0x0204, 5)]
+        );
+
+        let csv = log.heatmap_csv(HeatmapKind::Total);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(
+            row.starts_with("00,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,2,1,0"),
+            "{}",
+            row
+        );
+
+        let image = log.heatmap_ppm();
+        let pixel = 15 + 0x0011 * 3;
+        assert_eq!(&image[pixel..pixel + 3], &[255, 0, 0]);
+    }
+}
@@ -0,0 +1,290 @@
+//! 섀도 콜 스택 - JSR/RTS와 인터럽트 진입/RTI를 따라가며 호출 관계 추적
+use cpu::InterruptType;
+use std::collections::VecDeque;
+use std::fmt;
+
+const JSR_OPCODE: u8 = 0x20;
+const RTS_OPCODE: u8 = 0x60;
+const RTI_OPCODE: u8 = 0x40;
+/// 보관할 최대 이상 징후 수
+const MAX_ANOMALIES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    Subroutine,
+    Interrupt(InterruptType),
+}
+
+impl FrameKind {
+    // 복귀 정보가 차지하는 스택 바이트 수
+    fn stack_size(&self) -> u16 {
+        match self {
+            FrameKind::Subroutine => 2,
+            FrameKind::Interrupt(_) => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallFrame {
+    pub kind: FrameKind,
+    /// JSR 명령어 주소 (인터럽트는 중단된 위치)
+    pub call_site: u16,
+    /// 서브루틴 또는 인터럽트 핸들러 주소
+    pub target: u16,
+    /// 정상 복귀 시 돌아갈 주소
+    pub return_address: u16,
+    /// 복귀 정보를 넣은 뒤의 스택 포인터
+    pub stack_pointer: u8,
+}
+
+/// 섀도 스택과 실제 스택이 어긋난 경우
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackAnomaly {
+    /// RTS/RTI 없이 복귀 정보가 스택에서 제거됨 (PLA/PLA, TXS 등)
+    FramesPopped { address: u16, count: usize },
+    /// 더 바깥 프레임으로 복귀하면서 안쪽 프레임을 건너뜀
+    FramesSkipped { address: u16, count: usize },
+    /// 복귀 주소가 호출 시와 다름 (스택의 복귀 주소 수정)
+    ReturnAddressChanged {
+        address: u16,
+        expected: u16,
+        target: u16,
+    },
+    /// 대응하는 호출이 없는 RTS/RTI (RTS 트릭 점프 등)
+    UnmatchedReturn { address: u16, target: u16 },
+}
+
+impl fmt::Display for StackAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StackAnomaly::FramesPopped { address, count } => write!(
+                f,
+                "${:04X}: {} return address(es) removed from stack without RTS/RTI",
+                address, count
+            ),
+            StackAnomaly::FramesSkipped { address, count } => {
+                write!(f, "${:04X}: return skipped {} frame(s)", address, count)
+            }
+            StackAnomaly::ReturnAddressChanged {
+                address,
+                expected,
+                target,
+            } => write!(
+                f,
+                "${:04X}: returned to ${:04X} instead of ${:04X}",
+                address, target, expected
+            ),
+            StackAnomaly::UnmatchedReturn { address, target } => write!(
+                f,
+                "${:04X}: return to ${:04X} without matching call (RTS trick?)",
+                address, target
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallStack {
+    frames: Vec<CallFrame>,
+    anomalies: VecDeque<StackAnomaly>,
+    // 마지막으로 관찰한 스택 포인터
+    stack_pointer: u8,
+}
+
+impl CallStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 바깥쪽 호출부터 순서대로
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    /// 최근 이상 징후 (오래된 것부터)
+    pub fn anomalies(&self) -> Vec<StackAnomaly> {
+        self.anomalies.iter().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.anomalies.clear();
+    }
+
+    /// CPU 상태 변경 시 스택 포인터 갱신
+    pub fn set_stack_pointer(&mut self, stack_pointer: u8) {
+        self.stack_pointer = stack_pointer;
+    }
+
+    /// 명령어 실행 후 호출 (address: 명령어 주소, pc: 다음 주소)
+    pub fn on_instruction(&mut self, address: u16, opcode: u8, pc: u16) {
+        match opcode {
+            JSR_OPCODE => self.push(CallFrame {
+                kind: FrameKind::Subroutine,
+                call_site: address,
+                target: pc,
+                return_address: address.wrapping_add(3),
+                stack_pointer: self.stack_pointer,
+            }),
+            RTS_OPCODE | RTI_OPCODE => self.on_return(address, pc),
+            _ => self.prune_popped(address),
+        }
+    }
+
+    /// IRQ/NMI 진입 시 호출
+    pub fn on_interrupt(&mut self, interrupt: InterruptType, return_address: u16, handler: u16) {
+        self.push(CallFrame {
+            kind: FrameKind::Interrupt(interrupt),
+            call_site: return_address,
+            target: handler,
+            return_address,
+            stack_pointer: self.stack_pointer,
+        });
+    }
+
+    fn push(&mut self, frame: CallFrame) {
+        // 새 복귀 정보가 기존 프레임 자리를 덮으면 기존 프레임은 이미 사라진 것
+        let stale = self
+            .frames
+            .iter()
+            .rev()
+            .take_while(|existing| existing.stack_pointer <= frame.stack_pointer)
+            .count();
+        self.drop_frames(stale, |count| StackAnomaly::FramesPopped {
+            address: frame.call_site,
+            count,
+        });
+        self.frames.push(frame);
+    }
+
+    fn on_return(&mut self, address: u16, target: u16) {
+        // 복귀 정보를 꺼낸 뒤의 스택 포인터로 어느 프레임에서 돌아왔는지 판단
+        let stack_pointer = self.stack_pointer as u16;
+        let matched = self.frames.iter().rposition(|frame| {
+            frame.stack_pointer as u16 + frame.kind.stack_size() == stack_pointer
+        });
+
+        let Some(index) = matched else {
+            self.record(StackAnomaly::UnmatchedReturn { address, target });
+            self.prune_popped(address);
+            return;
+        };
+
+        let skipped = self.frames.len() - 1 - index;
+        self.drop_frames(skipped, |count| StackAnomaly::FramesSkipped {
+            address,
+            count,
+        });
+        if let Some(frame) = self.frames.pop()
+            && frame.return_address != target
+        {
+            self.record(StackAnomaly::ReturnAddressChanged {
+                address,
+                expected: frame.return_address,
+                target,
+            });
+        }
+    }
+
+    // 복귀 정보가 완전히 꺼내진 프레임 제거
+    fn prune_popped(&mut self, address: u16) {
+        let stack_pointer = self.stack_pointer as u16;
+        let popped = self
+            .frames
+            .iter()
+            .rev()
+            .take_while(|frame| {
+                frame.stack_pointer as u16 + frame.kind.stack_size() <= stack_pointer
+            })
+            .count();
+        self.drop_frames(popped, |count| StackAnomaly::FramesPopped {
+            address,
+            count,
+        });
+    }
+
+    fn drop_frames(&mut self, count: usize, anomaly: impl FnOnce(usize) -> StackAnomaly) {
+        if count > 0 {
+            self.frames.truncate(self.frames.len() - count);
+            self.record(anomaly(count));
+        }
+    }
+
+    fn record(&mut self, anomaly: StackAnomaly) {
+        if self.anomalies.len() == MAX_ANOMALIES {
+            self.anomalies.pop_front();
+        }
+        self.anomalies.push_back(anomaly);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 명령어 실행 결과 스택 포인터와 함께 전달
+    fn execute(stack: &mut CallStack, address: u16, opcode: u8, pc: u16, sp: u8) {
+        stack.set_stack_pointer(sp);
+        stack.on_instruction(address, opcode, pc);
+    }
+
+    #[test]
+    fn test_nested_calls() {
+        let mut stack = CallStack::new();
+        stack.set_stack_pointer(0xFD);
+        execute(&mut stack, 0x0200, JSR_OPCODE, 0x0300, 0xFB);
+        execute(&mut stack, 0x0300, JSR_OPCODE, 0x0400, 0xF9);
+        assert_eq!(stack.frames().len(), 2);
+        assert_eq!(stack.frames()[1].call_site, 0x0300);
+
+        execute(&mut stack, 0x0400, RTS_OPCODE, 0x0303, 0xFB);
+        execute(&mut stack, 0x0303, RTS_OPCODE, 0x0203, 0xFD);
+        assert!(stack.frames().is_empty());
+        assert!(stack.anomalies().is_empty());
+    }
+
+    #[test]
+    fn test_interrupt_frame() {
+        let mut stack = CallStack::new();
+        stack.set_stack_pointer(0xFA);
+        stack.on_interrupt(InterruptType::IRQ, 0x0205, 0x8000);
+        assert_eq!(
+            stack.frames()[0].kind,
+            FrameKind::Interrupt(InterruptType::IRQ)
+        );
+
+        execute(&mut stack, 0x8010, RTI_OPCODE, 0x0205, 0xFD);
+        assert!(stack.frames().is_empty());
+        assert!(stack.anomalies().is_empty());
+    }
+
+    #[test]
+    fn test_stack_manipulation() {
+        let mut stack = CallStack::new();
+        stack.set_stack_pointer(0xFD);
+        execute(&mut stack, 0x0200, JSR_OPCODE, 0x0300, 0xFB);
+
+        // PLA/PLA로 복귀 주소 제거
+        execute(&mut stack, 0x0300, 0x68, 0x0301, 0xFC);
+        assert_eq!(stack.frames().len(), 1);
+        execute(&mut stack, 0x0301, 0x68, 0x0302, 0xFD);
+        assert!(stack.frames().is_empty());
+
+        // 주소를 스택에 넣고 RTS로 점프
+        execute(&mut stack, 0x0310, RTS_OPCODE, 0x0500, 0xFD);
+        assert_eq!(
+            stack.anomalies(),
+            vec![
+                StackAnomaly::FramesPopped {
+                    address: 0x0301,
+                    count: 1
+                },
+                StackAnomaly::UnmatchedReturn {
+                    address: 0x0310,
+                    target: 0x0500
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! 코드/데이터 커버리지 - 바이트마다 옵코드 실행, 오퍼랜드, 데이터 읽기/쓰기 기록
+use bitflags::bitflags;
+use common::{Result, SymbolTable};
+use disassembler::Disassembler;
+use types::OPCODE_MAP;
+
+bitflags! {
+    /// 주소별 커버리지 플래그 - 비트맵 내보내기 시 바이트 값 그대로 사용
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct CoverageFlags: u8 {
+        /// 옵코드로 실행됨
+        const OPCODE = 0b0000_0001;
+        /// 실행된 명령어의 오퍼랜드
+        const OPERAND = 0b0000_0010;
+        /// 데이터로 읽힘
+        const READ = 0b0000_0100;
+        /// 데이터로 쓰임
+        const WRITE = 0b0000_1000;
+        /// 분기 명령어가 분기함
+        const BRANCH_TAKEN = 0b0001_0000;
+        /// 분기 명령어가 분기하지 않음
+        const BRANCH_NOT_TAKEN = 0b0010_0000;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CoverageSummary {
+    pub opcode_bytes: usize,
+    pub operand_bytes: usize,
+    pub read_bytes: usize,
+    pub written_bytes: usize,
+    /// 실행된 분기 명령어 수
+    pub branches: usize,
+    /// 양쪽 방향 모두 실행된 분기 명령어 수
+    pub full_branches: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    enabled: bool,
+    map: Vec<CoverageFlags>,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            map: vec![CoverageFlags::empty(); 0x10000],
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn clear(&mut self) {
+        self.map.fill(CoverageFlags::empty());
+    }
+
+    /// 명령어 실행 기록 (address: 명령어 주소, status: 실행 후 P)
+    pub fn record_instruction(&mut self, address: u16, opcode: u8, status: u8) {
+        self.map[address as usize] |= CoverageFlags::OPCODE;
+        let operand_size = OPCODE_MAP
+            .get(&opcode)
+            .map_or(0, |info| info.get_operand_size());
+        for offset in 1..=operand_size as u16 {
+            self.map[address.wrapping_add(offset) as usize] |= CoverageFlags::OPERAND;
+        }
+
+        if is_branch(opcode) {
+            // 오프셋 0이면 분기해도 다음 주소와 같으므로 PC 대신 조건 플래그로 판단
+            self.map[address as usize] |= if branch_taken(opcode, status) {
+                CoverageFlags::BRANCH_TAKEN
+            } else {
+                CoverageFlags::BRANCH_NOT_TAKEN
+            };
+        }
+    }
+
+    pub fn record_read(&mut self, address: u16) {
+        self.map[address as usize] |= CoverageFlags::READ;
+    }
+
+    pub fn record_write(&mut self, address: u16) {
+        self.map[address as usize] |= CoverageFlags::WRITE;
+    }
+
+    pub fn flags(&self, address: u16) -> CoverageFlags {
+        self.map[address as usize]
+    }
+
+    /// 64KB 비트맵 - 주소마다 CoverageFlags 한 바이트
+    pub fn bitmap(&self) -> Vec<u8> {
+        self.map.iter().map(|flags| flags.bits()).collect()
+    }
+
+    pub fn summary(&self, start: u16, end: u16) -> CoverageSummary {
+        let mut summary = CoverageSummary::default();
+        for flags in &self.map[start as usize..=end as usize] {
+            let count = |flag: CoverageFlags| flags.contains(flag) as usize;
+            summary.opcode_bytes += count(CoverageFlags::OPCODE);
+            summary.operand_bytes += count(CoverageFlags::OPERAND);
+            summary.read_bytes += count(CoverageFlags::READ);
+            summary.written_bytes += count(CoverageFlags::WRITE);
+            summary.branches += flags
+                .intersects(CoverageFlags::BRANCH_TAKEN | CoverageFlags::BRANCH_NOT_TAKEN)
+                as usize;
+            summary.full_branches +=
+                count(CoverageFlags::BRANCH_TAKEN | CoverageFlags::BRANCH_NOT_TAKEN);
+        }
+        summary
+    }
+
+    /// 실행된 옵코드 위치만 코드로, 나머지는 `.BYTE`로 표시한 주석 달린 리스팅
+    /// 표시: X 실행, R 읽기, W 쓰기, 분기는 T(분기만)/N(통과만)/B(양쪽)
+    pub fn listing(
+        &self,
+        start: u16,
+        end: u16,
+        symbols: &SymbolTable,
+        read: impl Fn(u16) -> Result<u8>,
+    ) -> Result<String> {
+        let bytes = (start..=end).map(read).collect::<Result<Vec<u8>>>()?;
+        let mut disassembler = Disassembler::with_origin(bytes, start);
+        disassembler.set_symbols(symbols);
+        disassembler.set_code_map(|address| self.flags(address).contains(CoverageFlags::OPCODE));
+
+        let lines: Vec<String> = disassembler
+            .disassemble()?
+            .iter()
+            .map(|instruction| {
+                let bytes = std::iter::once(instruction.opcode)
+                    .chain(instruction.operand.iter().copied())
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!(
+                    "{} .{:04X}  {:<9} {}",
+                    self.marker(instruction.address),
+                    instruction.address,
+                    bytes,
+                    instruction.mnemonic
+                )
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    // 4칸 표시: 실행, 읽기, 쓰기, 분기 방향
+    fn marker(&self, address: u16) -> String {
+        let flags = self.flags(address);
+        let mark = |flag: CoverageFlags, c: char| if flags.contains(flag) { c } else { ' ' };
+        let branch = match (
+            flags.contains(CoverageFlags::BRANCH_TAKEN),
+            flags.contains(CoverageFlags::BRANCH_NOT_TAKEN),
+        ) {
+            (true, true) => 'B',
+            (true, false) => 'T',
+            (false, true) => 'N',
+            (false, false) => ' ',
+        };
+        [
+            mark(CoverageFlags::OPCODE, 'X'),
+            mark(CoverageFlags::READ, 'R'),
+            mark(CoverageFlags::WRITE, 'W'),
+            branch,
+        ]
+        .iter()
+        .collect()
+    }
+}
+
+// BPL, BMI, BVC, BVS, BCC, BCS, BNE, BEQ
+fn is_branch(opcode: u8) -> bool {
+    opcode & 0x1F == 0x10
+}
+
+/// 분기 명령어의 상위 2비트는 검사할 플래그(N V C Z), 5번 비트는 분기 조건 값
+fn branch_taken(opcode: u8, status: u8) -> bool {
+    let flag = [7, 6, 0, 1][(opcode >> 6) as usize];
+    (status >> flag) & 1 == (opcode >> 5) & 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_listing() {
+        // $0200: LDX #$02 / DEX / BNE $0202 / BRK / .BYTE $FF
+        let memory = [0xA2, 0x02, 0xCA, 0xD0, 0xFD, 0x00, 0xFF];
+        let mut coverage = Coverage::new();
+        coverage.record_instruction(0x0200, 0xA2, 0x20);
+        coverage.record_instruction(0x0202, 0xCA, 0x20);
+        coverage.record_instruction(0x0203, 0xD0, 0x20);
+        coverage.record_instruction(0x0202, 0xCA, 0x22);
+        coverage.record_instruction(0x0203, 0xD0, 0x22);
+        coverage.record_read(0x0206);
+
+        assert_eq!(
+            coverage.flags(0x0201),
+            CoverageFlags::OPERAND,
+            "operand byte"
+        );
+        let summary = coverage.summary(0x0200, 0x0206);
+        assert_eq!(summary.opcode_bytes, 3);
+        assert_eq!(summary.operand_bytes, 2);
+        assert_eq!((summary.branches, summary.full_branches), (1, 1));
+        assert_eq!(coverage.bitmap()[0x0203], 0b0011_0001);
+
+        let listing = coverage
+            .listing(0x0200, 0x0206, &SymbolTable::new(), |address| {
+                Ok(memory[(address - 0x0200) as usize])
+            })
+            .unwrap();
+        assert_eq!(
+            listing,
+            "X    .0200  A2 02     LDX #$02\n\
+             X    .0202  CA        DEX\n\
+             X  B .0203  D0 FD     BNE $0202\n\
+             \u{20}    .0205  00        .BYTE $00\n\
+             \u{20}R   .0206  FF        .BYTE $FF"
+        );
+    }
+    #[test]
+    fn test_branch_direction_from_flags() {
+        let mut coverage = Coverage::new();
+        // BEQ +0: Z=1이면 분기해도 다음 명령어 주소로 감
+        coverage.record_instruction(0x0300, 0xF0, 0x22);
+        assert_eq!(
+            coverage.flags(0x0300),
+            CoverageFlags::OPCODE | CoverageFlags::BRANCH_TAKEN
+        );
+        // BCS (C=0), BMI (N=1), BVC (V=1)
+        coverage.record_instruction(0x0310, 0xB0, 0x20);
+        coverage.record_instruction(0x0320, 0x30, 0xA0);
+        coverage.record_instruction(0x0330, 0x50, 0x60);
+        assert!(
+            coverage
+                .flags(0x0310)
+                .contains(CoverageFlags::BRANCH_NOT_TAKEN)
+        );
+        assert!(coverage.flags(0x0320).contains(CoverageFlags::BRANCH_TAKEN));
+        assert!(
+            coverage
+                .flags(0x0330)
+                .contains(CoverageFlags::BRANCH_NOT_TAKEN)
+        );
+    }
+}
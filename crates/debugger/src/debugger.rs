@@ -1,9 +1,19 @@
-use common::Result;
-use cpu::{CPU, CPUEvent, InterruptType};
+use crate::access_log::{AccessLog, AccessRecord, AddressAccess, HeatmapKind};
+use crate::call_stack::{CallFrame, CallStack, FrameKind, StackAnomaly};
+use crate::coverage::{Coverage, CoverageFlags, CoverageSummary};
+use crate::profiler::{AddressProfile, FunctionProfile, Profiler};
+use common::{Result, SymbolTable};
+use cpu::{CPU, CPUEvent, CPUState, InterruptType, MemoryAccess, RegisterType, StopReason};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+const JSR_OPCODE: u8 = 0x20;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AccessType {
     Access, // read + write
     Read,
@@ -11,28 +21,74 @@ pub enum AccessType {
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
-struct BreakPoint {
-    address: u16,
-    enabled: bool,
-    access_type: AccessType,
+pub struct BreakPoint {
+    pub id: usize,
+    pub address: u16,
+    pub enabled: bool,
+    pub access_type: AccessType,
 }
 
 impl BreakPoint {
-    fn new(address: u16, enabled: bool, access_type: AccessType) -> Self {
+    fn new(id: usize, address: u16, enabled: bool, access_type: AccessType) -> Self {
         Self {
+            id,
             address,
             enabled,
             access_type,
         }
     }
+
+    /// 메모리 접근이 이 브레이크포인트에 해당하는지 확인
+    fn matches_access(&self, access: MemoryAccess) -> bool {
+        self.enabled
+            && match access {
+                MemoryAccess::Read => {
+                    matches!(self.access_type, AccessType::Read | AccessType::Access)
+                }
+                MemoryAccess::Write => {
+                    matches!(self.access_type, AccessType::Write | AccessType::Access)
+                }
+            }
+    }
+}
+
+/// 다른 스레드(시그널 핸들러 등)에서 일시 정지를 요청하는 핸들
+#[derive(Debug, Clone)]
+pub struct PauseHandle {
+    sender: Sender<StopReason>,
+    running: Arc<AtomicBool>,
+}
+
+impl PauseHandle {
+    /// run_batch 실행 중일 때만 요청 - 프롬프트에서 보낸 요청이 다음 실행을 멈추지 않도록
+    pub fn pause(&self) {
+        if self.running.load(Ordering::SeqCst) {
+            let _ = self.sender.send(StopReason::UserPause);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Debugger {
     cpu: Option<CPU>,
-    breakpoints: HashMap<u16, BreakPoint>,
+    // 이벤트 핸들러와 공유 - 디버깅 중 추가/삭제한 브레이크포인트도 바로 반영
+    breakpoints: Arc<Mutex<HashMap<u16, BreakPoint>>>,
+    next_breakpoint_id: usize,
     // 인터럽트 전송용 채널
     interrupt_sender: Option<Sender<InterruptType>>,
+    // 일시 정지 요청 전송용 채널
+    stop_sender: Option<Sender<StopReason>>,
+    // run_batch 실행 중 여부 - PauseHandle과 공유
+    running: Arc<AtomicBool>,
+    // 이벤트 핸들러가 갱신하는 섀도 콜 스택
+    call_stack: Arc<Mutex<CallStack>>,
+    // 이벤트 핸들러가 갱신하는 실행 프로파일
+    profiler: Arc<Mutex<Profiler>>,
+    // 이벤트 핸들러가 갱신하는 코드/데이터 커버리지
+    coverage: Arc<Mutex<Coverage>>,
+    // 이벤트 핸들러가 갱신하는 메모리 접근 기록
+    access_log: Arc<Mutex<AccessLog>>,
+    symbols: SymbolTable,
 }
 
 impl Default for Debugger {
@@ -45,8 +101,16 @@ impl Debugger {
     pub fn new() -> Self {
         Self {
             cpu: None,
-            breakpoints: HashMap::new(),
+            breakpoints: Arc::new(Mutex::new(HashMap::new())),
+            next_breakpoint_id: 1,
             interrupt_sender: None,
+            stop_sender: None,
+            running: Arc::new(AtomicBool::new(false)),
+            call_stack: Arc::new(Mutex::new(CallStack::new())),
+            profiler: Arc::new(Mutex::new(Profiler::new())),
+            coverage: Arc::new(Mutex::new(Coverage::new())),
+            access_log: Arc::new(Mutex::new(AccessLog::default())),
+            symbols: SymbolTable::new(),
         }
     }
 
@@ -66,6 +130,82 @@ impl Debugger {
         }
     }
 
+    /// 정지된 위치에서 실행 재개 - 스택과 플래그는 건드리지 않음
+    pub fn continue_execution(&mut self) -> Result<Option<StopReason>> {
+        self.run_cpu()?;
+        self.stop_reason()
+    }
+
+    /// 명령어 하나 실행 후 일시 정지 - 도착한 주소에 브레이크포인트가 있으면 브레이크포인트로 보고
+    pub fn step(&mut self) -> Result<Option<StopReason>> {
+        let breakpoint = {
+            let cpu = self.get_cpu_mut()?;
+            cpu.skip_next_stop_check();
+            cpu.step()?;
+            if cpu.get_state() != CPUState::Running {
+                return self.stop_reason();
+            }
+            let pc = cpu.get_value(RegisterType::PC).as_u16();
+            self.breakpoints
+                .lock()
+                .unwrap()
+                .get(&pc)
+                .filter(|bp| bp.enabled && bp.access_type == AccessType::Access)
+                .map(|bp| bp.id)
+        };
+        self.get_cpu_mut()?.pause(match breakpoint {
+            Some(id) => StopReason::Breakpoint(id),
+            None => StopReason::StepComplete,
+        });
+        self.stop_reason()
+    }
+
+    /// 최대 `limit`개의 명령어 실행
+    /// CPU가 아직 실행 중이면 true, 일시 정지/정지 상태가 되면 false 반환
+    pub fn run_batch(&mut self, limit: usize) -> Result<bool> {
+        self.running.store(true, Ordering::SeqCst);
+        let result = self.run_instructions(limit);
+        self.running.store(false, Ordering::SeqCst);
+        result
+    }
+
+    fn run_instructions(&mut self, limit: usize) -> Result<bool> {
+        let cpu = self.get_cpu_mut()?;
+        cpu.resume();
+        for _ in 0..limit {
+            cpu.step()?;
+            if cpu.get_state() != CPUState::Running {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 실행 중인 CPU에 일시 정지 요청 - 다음 명령어 경계에서 멈춤
+    pub fn pause(&self) -> Result<()> {
+        self.send_stop(StopReason::UserPause)
+    }
+
+    /// 다른 스레드에서 run_batch를 멈추는 핸들 (enabled 이후 사용 가능)
+    pub fn pause_handle(&self) -> Result<PauseHandle> {
+        let sender = self
+            .stop_sender
+            .clone()
+            .ok_or(error::Error::DebuggerNotAttached)?;
+        Ok(PauseHandle {
+            sender,
+            running: self.running.clone(),
+        })
+    }
+
+    /// 현재 일시 정지 사유 (일시 정지 상태가 아니면 None)
+    pub fn stop_reason(&self) -> Result<Option<StopReason>> {
+        match self.get_cpu()?.get_state() {
+            CPUState::Paused(reason) => Ok(Some(reason)),
+            _ => Ok(None),
+        }
+    }
+
     pub fn attach(&mut self, cpu: CPU) {
         self.cpu = Some(cpu);
     }
@@ -80,82 +220,171 @@ impl Debugger {
 
         // 인터럽트 채널 생성
         let (tx, rx) = mpsc::channel();
-        self.interrupt_sender = Some(tx.clone());
+        self.interrupt_sender = Some(tx);
+
+        // 일시 정지 채널 생성
+        let (stop_tx, stop_rx) = mpsc::channel();
+        self.stop_sender = Some(stop_tx.clone());
 
         // 이벤트 핸들러 등록
-        // 클로저에서 사용할 브레이크포인트 복사
         let breakpoints = self.breakpoints.clone();
-        let tx_clone = tx.clone();
+        let call_stack = self.call_stack.clone();
+        call_stack.lock().unwrap().clear();
+        let profiler = self.profiler.clone();
+        let coverage = self.coverage.clone();
+        let access_log = self.access_log.clone();
 
         self.get_cpu_mut()?.register_event_handler(Box::new(move |event| {
             match event {
-                CPUEvent::InstructionExecuted { pc, opcode, cycles, operand } => {
-                    println!(
-                        "[Debugger] InstructionExecuted: pc: {:#X}, opcode: {:#X}, cycles: {:#X}, operand: {:#X}",
-                        pc, opcode, cycles, operand
-                    );
-
-                    // pc에 브레이크포인트가 활성화되어 있는지 확인
-                    let has_active_breakpoint = breakpoints
-                        .get(pc)
+                CPUEvent::InstructionFetch { address } => {
+                    // 실행할 명령어 주소에 브레이크포인트가 활성화되어 있는지 확인
+                    let active_breakpoint = breakpoints
+                        .lock()
+                        .unwrap()
+                        .get(address)
                         .filter(|bp| bp.enabled && bp.access_type == AccessType::Access)
-                        .is_some();
+                        .map(|bp| bp.id);
 
-                    println!(
-                        "[Debugger] Breakpoint check: {:#X}, {:#?}",
-                        pc, has_active_breakpoint
+                    // 활성화된 브레이크포인트가 있으면 실행 전에 CPU 일시 정지
+                    if let Some(id) = active_breakpoint {
+                        common::trace!("[Debugger] Breakpoint hit at {:#X}", address);
+                        let _ = stop_tx.send(StopReason::Breakpoint(id));
+                    }
+                }
+                CPUEvent::InstructionExecuted { address, pc, opcode, cycles, operand, status } => {
+                    common::trace!(
+                        "[Debugger] InstructionExecuted: pc: {:#X}, opcode: {:#X}, cycles: {:#X}, operand: {:#X}",
+                        pc, opcode, cycles, operand
                     );
+                    let mut call_stack = call_stack.lock().unwrap();
+                    // 호출 전 스택 기준으로 집계해야 JSR 사이클이 호출한 쪽에 더해짐
+                    let mut profiler = profiler.lock().unwrap();
+                    if profiler.is_enabled() {
+                        profiler.record(*address, *cycles, call_stack.frames());
+                        if *opcode == JSR_OPCODE {
+                            profiler.record_call(*pc);
+                        }
+                    }
+                    drop(profiler);
+                    call_stack.on_instruction(*address, *opcode, *pc);
+                    drop(call_stack);
 
-                    // 활성화된 브레이크포인트가 있으면 CPU 중단
-                    if has_active_breakpoint {
-                        println!("[Debugger] Breakpoint hit at {:#X}", pc);
-                        let _ = tx_clone.send(InterruptType::BRK);
+                    let mut coverage = coverage.lock().unwrap();
+                    if coverage.is_enabled() {
+                        coverage.record_instruction(*address, *opcode, *status);
                     }
+                    drop(coverage);
                 }
-                CPUEvent::MemoryWrite { address, value } => {
-                    println!(
+                CPUEvent::MemoryWrite { address, value, instruction, cycle } => {
+                    common::trace!(
                         "[Debugger] MemoryWrite: address: {:#X}, value: {:#X}",
                         address, value
                     );
+                    let mut coverage = coverage.lock().unwrap();
+                    if coverage.is_enabled() {
+                        coverage.record_write(*address);
+                    }
+                    drop(coverage);
+                    let mut access_log = access_log.lock().unwrap();
+                    if access_log.is_enabled() {
+                        access_log.record(AccessRecord {
+                            address: *address,
+                            value: *value,
+                            access: MemoryAccess::Write,
+                            pc: *instruction,
+                            cycle: *cycle,
+                        });
+                    }
+                    drop(access_log);
+
                     // 메모리 쓰기 브레이크포인트 확인
-                    let has_write_breakpoint = breakpoints
+                    let write_breakpoint = breakpoints
+                        .lock()
+                        .unwrap()
                         .get(address)
-                        .filter(|bp| bp.enabled && (bp.access_type == AccessType::Write || bp.access_type == AccessType::Access))
-                        .is_some();
+                        .filter(|bp| bp.matches_access(MemoryAccess::Write))
+                        .map(|bp| bp.id);
 
-                    if has_write_breakpoint {
-                        println!("[Debugger] Write breakpoint hit at {:#X}", address);
-                        let _ = tx_clone.send(InterruptType::BRK);
+                    if let Some(id) = write_breakpoint {
+                        common::trace!("[Debugger] Write breakpoint hit at {:#X}", address);
+                        let _ = stop_tx.send(StopReason::Watchpoint {
+                            id,
+                            address: *address,
+                            access: MemoryAccess::Write,
+                        });
                     }
                 }
-                CPUEvent::MemoryRead { address, value } => {
-                    println!(
+                CPUEvent::MemoryRead { address, value, instruction, cycle } => {
+                    common::trace!(
                         "[Debugger] MemoryRead: address: {:#X}, value: {:#X}",
                         address, value
                     );
+                    let mut coverage = coverage.lock().unwrap();
+                    if coverage.is_enabled() {
+                        coverage.record_read(*address);
+                    }
+                    drop(coverage);
+                    let mut access_log = access_log.lock().unwrap();
+                    if access_log.is_enabled() {
+                        access_log.record(AccessRecord {
+                            address: *address,
+                            value: *value,
+                            access: MemoryAccess::Read,
+                            pc: *instruction,
+                            cycle: *cycle,
+                        });
+                    }
+                    drop(access_log);
+
                     // 메모리 읽기 브레이크포인트 확인
-                    let has_read_breakpoint = breakpoints
+                    let read_breakpoint = breakpoints
+                        .lock()
+                        .unwrap()
                         .get(address)
-                        .filter(|bp| bp.enabled &&
-                               (bp.access_type == AccessType::Read ||
-                                bp.access_type == AccessType::Access))
-                        .is_some();
-                    if has_read_breakpoint {
-                        println!("[Debugger] Read breakpoint hit at {:#X}", address);
-                        let _ = tx_clone.send(InterruptType::BRK);
+                        .filter(|bp| bp.matches_access(MemoryAccess::Read))
+                        .map(|bp| bp.id);
+                    if let Some(id) = read_breakpoint {
+                        common::trace!("[Debugger] Read breakpoint hit at {:#X}", address);
+                        let _ = stop_tx.send(StopReason::Watchpoint {
+                            id,
+                            address: *address,
+                            access: MemoryAccess::Read,
+                        });
                     }
                 }
+                CPUEvent::WriteProtected { address, value, instruction } => {
+                    common::trace!(
+                        "[Debugger] WriteProtected: address: {:#X}, value: {:#X}, pc: {:#X}",
+                        address, value, instruction
+                    );
+                    let _ = stop_tx.send(StopReason::WriteProtected { address: *address });
+                }
                 CPUEvent::FlagChanged { flag, value, old_value } => {
-                    println!(
+                    common::trace!(
                         "[Debugger] FlagChanged: {} changed from {} to {}",
                         flag, old_value, value
                     );
                 }
                 CPUEvent::StateChanged { state } => {
-                    println!("[Debugger] StateChanged: {:?}", state);
+                    common::trace!("[Debugger] StateChanged: {:?}", state);
+                    call_stack.lock().unwrap().set_stack_pointer(state.s);
+                }
+                CPUEvent::Interrupt { interrupt, return_address, handler } => {
+                    common::trace!(
+                        "[Debugger] Interrupt: {:?} from {:#X} to {:#X}",
+                        interrupt, return_address, handler
+                    );
+                    call_stack
+                        .lock()
+                        .unwrap()
+                        .on_interrupt(*interrupt, *return_address, *handler);
+                    let mut profiler = profiler.lock().unwrap();
+                    if profiler.is_enabled() {
+                        profiler.record_call(*handler);
+                    }
                 }
                 CPUEvent::RegisterChanged { register, value, old_value } => {
-                    println!(
+                    common::trace!(
                         "[Debugger] RegisterChanged: {} changed from {:#X} to {:#X}",
                         register, old_value, value
                     );
@@ -165,38 +394,273 @@ impl Debugger {
 
         // CPU에 수신자 등록
         self.get_cpu_mut()?.set_interrupt_channel(rx);
+        self.get_cpu_mut()?.set_stop_channel(stop_rx);
 
         Ok(())
     }
 
+    /// 디버깅 모드 활성화 여부
+    pub fn is_enabled(&self) -> bool {
+        self.stop_sender.is_some()
+    }
+
     pub fn disabled(&mut self) -> Result<()> {
         self.get_cpu_mut()?.enabled_debug(false);
         self.get_cpu_mut()?.clear_event_handler();
+        self.get_cpu_mut()?.clear_stop_channel();
         self.interrupt_sender = None;
+        self.stop_sender = None;
         Ok(())
     }
 
+    /// 메모리 읽기 - 디버거 접근은 브레이크포인트를 건드리지 않음
     pub fn read(&self, address: u16) -> Result<u8> {
-        self.get_cpu()?.read_memory(address)
+        self.get_cpu()?.peek_memory(address)
     }
 
+    /// 메모리 쓰기 - 디버거 접근은 브레이크포인트를 건드리지 않음
     pub fn write(&mut self, address: u16, value: u8) -> Result<()> {
-        self.get_cpu_mut()?.write_memory(address, value)
+        self.get_cpu_mut()?.poke_memory(address, value)
+    }
+
+    /// 심볼 테이블 설정 - 백트레이스 등 주소 표시에 사용
+    pub fn load_symbols(&mut self, symbols: SymbolTable) {
+        self.symbols = symbols;
+    }
+
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    /// 섀도 콜 스택 (바깥쪽 호출부터)
+    pub fn call_stack(&self) -> Vec<CallFrame> {
+        self.call_stack.lock().unwrap().frames().to_vec()
+    }
+
+    /// 섀도 콜 스택을 깨뜨린 스택 조작 기록
+    pub fn stack_anomalies(&self) -> Vec<StackAnomaly> {
+        self.call_stack.lock().unwrap().anomalies()
+    }
+
+    /// 프로파일링 시작/중지 - 중지해도 집계는 남음
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiler.lock().unwrap().set_enabled(enabled);
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.lock().unwrap().is_enabled()
+    }
+
+    pub fn clear_profile(&mut self) {
+        self.profiler.lock().unwrap().clear();
+    }
+
+    /// 사이클이 많은 주소부터
+    pub fn hot_spots(&self) -> Vec<AddressProfile> {
+        self.profiler.lock().unwrap().hot_spots()
+    }
+
+    /// 하위 호출 포함 사이클이 많은 서브루틴부터
+    pub fn function_profile(&self) -> Vec<FunctionProfile> {
+        self.profiler.lock().unwrap().functions()
+    }
+
+    /// 상위 limit개 주소/서브루틴을 디스어셈블과 심볼로 표시한 보고서
+    pub fn profile_report(&self, limit: usize) -> Result<String> {
+        let profiler = self.profiler.lock().unwrap();
+        Ok(profiler.report(&self.symbols, limit, |address| {
+            self.disassemble_one(address).unwrap_or_default()
+        }))
+    }
+
+    /// flamegraph 도구용 collapsed stack 텍스트
+    pub fn profile_collapsed(&self) -> String {
+        self.profiler.lock().unwrap().collapsed(&self.symbols)
+    }
+
+    /// 커버리지 기록 시작/중지 - 중지해도 기록은 남음
+    pub fn set_coverage(&mut self, enabled: bool) {
+        self.coverage.lock().unwrap().set_enabled(enabled);
+    }
+
+    pub fn is_coverage_enabled(&self) -> bool {
+        self.coverage.lock().unwrap().is_enabled()
+    }
+
+    pub fn clear_coverage(&mut self) {
+        self.coverage.lock().unwrap().clear();
+    }
+
+    pub fn coverage_flags(&self, address: u16) -> CoverageFlags {
+        self.coverage.lock().unwrap().flags(address)
+    }
+
+    pub fn coverage_summary(&self, start: u16, end: u16) -> CoverageSummary {
+        self.coverage.lock().unwrap().summary(start, end)
+    }
+
+    /// 64KB 커버리지 비트맵 (주소마다 CoverageFlags 한 바이트)
+    pub fn coverage_bitmap(&self) -> Vec<u8> {
+        self.coverage.lock().unwrap().bitmap()
+    }
+
+    /// 실행된 코드와 데이터를 구분해 커버리지를 표시한 리스팅
+    pub fn coverage_listing(&self, start: u16, end: u16) -> Result<String> {
+        self.coverage
+            .lock()
+            .unwrap()
+            .listing(start, end, &self.symbols, |address| self.read(address))
+    }
+
+    /// 메모리 접근 기록 시작/중지 - 중지해도 기록은 남음
+    pub fn set_access_log(&mut self, enabled: bool) {
+        self.access_log.lock().unwrap().set_enabled(enabled);
+    }
+
+    pub fn is_access_log_enabled(&self) -> bool {
+        self.access_log.lock().unwrap().is_enabled()
+    }
+
+    pub fn clear_access_log(&mut self) {
+        self.access_log.lock().unwrap().clear();
+    }
+
+    /// 최근 접근 보관 수 설정
+    pub fn set_access_history_size(&mut self, capacity: usize) {
+        self.access_log.lock().unwrap().set_capacity(capacity);
+    }
+
+    /// 주소별 읽기/쓰기 횟수, 마지막 쓰기 명령어, 마지막 접근 사이클
+    pub fn access_stats(&self, address: u16) -> AddressAccess {
+        self.access_log.lock().unwrap().stats(address)
+    }
+
+    /// 최근 접근 (오래된 것부터)
+    pub fn recent_accesses(&self) -> Vec<AccessRecord> {
+        self.access_log.lock().unwrap().recent()
+    }
+
+    /// 최근 접근 중 address에 대한 것만
+    pub fn access_history(&self, address: u16) -> Vec<AccessRecord> {
+        self.access_log.lock().unwrap().history(address)
+    }
+
+    pub fn heatmap_csv(&self, kind: HeatmapKind) -> String {
+        self.access_log.lock().unwrap().heatmap_csv(kind)
+    }
+
+    pub fn heatmap_ppm(&self) -> Vec<u8> {
+        self.access_log.lock().unwrap().heatmap_ppm()
+    }
+
+    // 주소의 명령어 하나를 심볼과 함께 디스어셈블
+    fn disassemble_one(&self, address: u16) -> Result<String> {
+        let bytes = (0..3)
+            .map(|offset| self.read(address.wrapping_add(offset)))
+            .collect::<Result<Vec<u8>>>()?;
+        let mut disassembler = disassembler::Disassembler::with_origin(bytes, address);
+        disassembler.set_symbols(&self.symbols);
+        Ok(disassembler
+            .disassemble()?
+            .first()
+            .map(|instruction| instruction.mnemonic.clone())
+            .unwrap_or_default())
+    }
+
+    /// 현재 위치부터 바깥쪽 호출까지 심볼 이름으로 표시한 백트레이스
+    pub fn backtrace(&self) -> Result<Vec<String>> {
+        let pc = self.get_cpu()?.get_value(RegisterType::PC).as_u16();
+        let mut lines = vec![format!("#0  ${:04X}  {}", pc, self.symbols.describe(pc))];
+        for (depth, frame) in self.call_stack().iter().rev().enumerate() {
+            let call = match frame.kind {
+                FrameKind::Subroutine => format!("JSR {}", self.symbols.describe(frame.target)),
+                FrameKind::Interrupt(interrupt) => {
+                    format!("{:?} -> {}", interrupt, self.symbols.describe(frame.target))
+                }
+            };
+            lines.push(format!(
+                "#{}  ${:04X}  {}  {}",
+                depth + 1,
+                frame.call_site,
+                self.symbols.describe(frame.call_site),
+                call
+            ));
+        }
+        Ok(lines)
     }
 
-    pub fn add_breakpoint(&mut self, address: u16, access_type: AccessType) {
+    /// 스택 페이지에서 JSR 복귀 주소를 찾아 호출 위치 목록 반환 (가장 최근 호출부터)
+    /// 복귀 주소 - 2 위치에 JSR 옵코드가 있는 값만 호출 프레임으로 간주
+    pub fn walk_stack(&self) -> Result<Vec<u16>> {
+        let sp = self.get_cpu()?.get_value(RegisterType::S).as_u8() as u16;
+        let mut call_sites = Vec::new();
+        let mut offset = sp + 1;
+        while offset < 0xFF {
+            let low = self.read(0x0100 + offset)? as u16;
+            let high = self.read(0x0100 + offset + 1)? as u16;
+            let call_site = ((high << 8) | low).wrapping_sub(2);
+            if self.read(call_site)? == JSR_OPCODE {
+                call_sites.push(call_site);
+                offset += 2;
+            } else {
+                offset += 1;
+            }
+        }
+        Ok(call_sites)
+    }
+
+    /// 브레이크포인트 추가 - 정지 사유에 쓰이는 ID 반환
+    pub fn add_breakpoint(&mut self, address: u16, access_type: AccessType) -> usize {
+        let id = self.next_breakpoint_id;
+        self.next_breakpoint_id += 1;
         self.breakpoints
-            .insert(address, BreakPoint::new(address, true, access_type));
+            .lock()
+            .unwrap()
+            .insert(address, BreakPoint::new(id, address, true, access_type));
+        id
+    }
+
+    /// 심볼 이름 위치에 브레이크포인트 추가
+    pub fn add_symbol_breakpoint(&mut self, name: &str, access_type: AccessType) -> Result<usize> {
+        let address = self
+            .symbols
+            .address_of(name)
+            .ok_or_else(|| error::Error::UnknownSymbol(name.to_string()))?;
+        Ok(self.add_breakpoint(address, access_type))
+    }
+
+    /// 등록된 브레이크포인트 목록 (ID 순)
+    pub fn breakpoints(&self) -> Vec<BreakPoint> {
+        let mut breakpoints: Vec<BreakPoint> =
+            self.breakpoints.lock().unwrap().values().cloned().collect();
+        breakpoints.sort_by_key(|bp| bp.id);
+        breakpoints
+    }
+
+    /// address에 걸린 브레이크포인트 (주소마다 하나)
+    pub fn breakpoint_at(&self, address: u16) -> Option<BreakPoint> {
+        self.breakpoints.lock().unwrap().get(&address).cloned()
     }
 
     pub fn toggle_breakpoint(&mut self, address: u16) {
-        if let Some(bp) = self.breakpoints.get_mut(&address) {
+        if let Some(bp) = self.breakpoints.lock().unwrap().get_mut(&address) {
             bp.enabled = !bp.enabled;
         }
     }
 
     pub fn remove_breakpoint(&mut self, address: u16) {
-        self.breakpoints.remove(&address);
+        self.breakpoints.lock().unwrap().remove(&address);
+    }
+
+    // CPU에 일시 정지 요청 보내기
+    fn send_stop(&self, reason: StopReason) -> Result<()> {
+        if let Some(sender) = &self.stop_sender {
+            sender
+                .send(reason)
+                .map_err(|_| error::Error::Internal("Failed to send stop request".to_string()))
+        } else {
+            Err(error::Error::DebuggerNotAttached)
+        }
     }
 
     // CPU에 인터럽트 신호 보내기
@@ -235,10 +699,25 @@ mod tests {
             memory[0x1003] = 0xe8; // INX
             Self { memory }
         }
+
+        fn with_program(address: u16, program: &[u8]) -> Self {
+            let mut memory = [0; 65536];
+            memory[address as usize..address as usize + program.len()].copy_from_slice(program);
+            Self { memory }
+        }
+    }
+
+    fn debugger_with(mem: MockMemoryBus) -> Debugger {
+        let mut cpu = CPU::new();
+        cpu.set_pc(0x1000);
+        cpu.set_memory_bus(Arc::new(Mutex::new(mem)));
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger
     }
 
     impl MemoryBus for MockMemoryBus {
-        fn read(&self, address: u16) -> u8 {
+        fn peek(&self, address: u16) -> u8 {
             self.memory[address as usize]
         }
 
@@ -287,4 +766,297 @@ mod tests {
         let context = debugger.get_cpu().unwrap().get_context().unwrap();
         assert_eq!(context.pc, 0x1002);
     }
+
+    #[test]
+    fn test_continue_after_breakpoint() {
+        let mut debugger = debugger_with(MockMemoryBus::new());
+        let id = debugger.add_breakpoint(0x1002, AccessType::Access);
+        debugger.enabled().unwrap();
+
+        let reason = debugger.continue_execution().unwrap();
+        assert_eq!(reason, Some(StopReason::Breakpoint(id)));
+        let before = debugger.get_cpu().unwrap().get_context().unwrap();
+        assert_eq!(before.pc, 0x1002);
+
+        // 스택과 플래그를 건드리지 않고 정지 위치에서 이어서 실행
+        let reason = debugger.continue_execution().unwrap();
+        assert_eq!(reason, None);
+        let cpu = debugger.get_cpu().unwrap();
+        assert_eq!(cpu.get_value(cpu::RegisterType::X).as_u8(), 2);
+        assert_eq!(
+            cpu.get_state(),
+            CPUState::Halted(InterruptType::BRK),
+            "BRK at $1004 should still halt the CPU"
+        );
+    }
+
+    #[test]
+    fn test_breakpoint_on_entry_and_branch_target() {
+        // $1000: LDX #$00; $1002: BEQ $1006; $1004: INX; BRK; $1006: INX; BRK
+        let program = [0xA2, 0x00, 0xF0, 0x02, 0xE8, 0x00, 0xE8, 0x00];
+        let mut debugger = debugger_with(MockMemoryBus::with_program(0x1000, &program));
+        let entry = debugger.add_breakpoint(0x1000, AccessType::Access);
+        let target = debugger.add_breakpoint(0x1006, AccessType::Access);
+        debugger.enabled().unwrap();
+
+        // 첫 명령어도 실행 전에 멈춤
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::Breakpoint(entry))
+        );
+        let context = debugger.get_cpu().unwrap().get_context().unwrap();
+        assert_eq!((context.pc, context.x), (0x1000, 0));
+
+        // 정지 위치의 브레이크포인트는 건너뛰고 분기 목적지에서 멈춤
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::Breakpoint(target))
+        );
+        let context = debugger.get_cpu().unwrap().get_context().unwrap();
+        assert_eq!((context.pc, context.x), (0x1006, 0));
+    }
+
+    #[test]
+    fn test_step_and_user_pause() {
+        let mut debugger = debugger_with(MockMemoryBus::new());
+        debugger.enabled().unwrap();
+
+        assert_eq!(debugger.step().unwrap(), Some(StopReason::StepComplete));
+        assert_eq!(
+            debugger.get_cpu().unwrap().get_context().unwrap().pc,
+            0x1002
+        );
+
+        debugger.pause().unwrap();
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::UserPause)
+        );
+        // 요청은 명령어 실행 전에 확인하므로 다음 명령어를 실행하지 않고 멈춤
+        let context = debugger.get_cpu().unwrap().get_context().unwrap();
+        assert_eq!(context.pc, 0x1002);
+        assert_eq!(context.x, 0);
+
+        assert_eq!(debugger.step().unwrap(), Some(StopReason::StepComplete));
+        let context = debugger.get_cpu().unwrap().get_context().unwrap();
+        assert_eq!(context.pc, 0x1003);
+        assert_eq!(context.x, 1);
+    }
+
+    #[test]
+    fn test_pause_handle_from_another_thread() {
+        // $1000: JMP $1000
+        let program = [0x4C, 0x00, 0x10];
+        let mut debugger = debugger_with(MockMemoryBus::with_program(0x1000, &program));
+        debugger.enabled().unwrap();
+        let handle = debugger.pause_handle().unwrap();
+
+        // 실행 중이 아닐 때 보낸 요청은 버림
+        handle.pause();
+        assert!(debugger.run_batch(10).unwrap());
+
+        // 실행이 시작되기 전에 보낸 요청은 버려지므로 멈출 때까지 반복
+        let stopped = Arc::new(AtomicBool::new(false));
+        let pauser = {
+            let stopped = stopped.clone();
+            std::thread::spawn(move || {
+                while !stopped.load(Ordering::SeqCst) {
+                    handle.pause();
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            })
+        };
+        assert!(!debugger.run_batch(usize::MAX).unwrap());
+        stopped.store(true, Ordering::SeqCst);
+        pauser.join().unwrap();
+        assert_eq!(debugger.stop_reason().unwrap(), Some(StopReason::UserPause));
+    }
+
+    #[test]
+    fn test_profile_counts_branch_and_page_cross_cycles() {
+        // $1000: JMP $10FB / $10FB: LDX #2 / $10FD: DEX / $10FE: BNE $10FD / $1100: BRK
+        let mut memory = MockMemoryBus::with_program(0x1000, &[0x4C, 0xFB, 0x10]);
+        memory.memory[0x10FB..0x1100].copy_from_slice(&[0xA2, 0x02, 0xCA, 0xD0, 0xFD]);
+        let mut debugger = debugger_with(memory);
+        debugger.enabled().unwrap();
+        debugger.set_profiling(true);
+
+        // 명령어별 실제 사이클 (total_cycles 증가분)
+        let mut expected: HashMap<u16, (u64, u64)> = HashMap::new();
+        let mut branch_cycles = Vec::new();
+        while debugger
+            .get_cpu()
+            .unwrap()
+            .get_value(RegisterType::PC)
+            .as_u16()
+            != 0x1100
+        {
+            let cpu = debugger.get_cpu().unwrap();
+            let address = cpu.get_value(RegisterType::PC).as_u16();
+            let before = cpu.total_cycles();
+            debugger.step().unwrap();
+            let cycles = debugger.get_cpu().unwrap().total_cycles() - before;
+            let entry = expected.entry(address).or_default();
+            entry.0 += 1;
+            entry.1 += cycles;
+            if address == 0x10FE {
+                branch_cycles.push(cycles);
+            }
+        }
+
+        // 페이지를 넘는 분기(taken)는 분기하지 않을 때보다 2사이클 많음
+        assert_eq!(branch_cycles.len(), 2);
+        assert_eq!(branch_cycles[0], branch_cycles[1] + 2);
+        let profiled: HashMap<u16, (u64, u64)> = debugger
+            .hot_spots()
+            .into_iter()
+            .map(|profile| (profile.address, (profile.count, profile.cycles)))
+            .collect();
+        assert_eq!(profiled, expected);
+        assert_eq!(
+            profiled.values().map(|&(_, cycles)| cycles).sum::<u64>(),
+            debugger.get_cpu().unwrap().total_cycles()
+        );
+    }
+
+    #[test]
+    fn test_watchpoint_stop() {
+        // LDA #$42; STA $2000; LDA $2000; BRK
+        let program = [0xA9, 0x42, 0x8D, 0x00, 0x20, 0xAD, 0x00, 0x20, 0x00];
+        let mut debugger = debugger_with(MockMemoryBus::with_program(0x1000, &program));
+        let write_id = debugger.add_breakpoint(0x2000, AccessType::Write);
+        debugger.enabled().unwrap();
+
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::Watchpoint {
+                id: write_id,
+                address: 0x2000,
+                access: MemoryAccess::Write,
+            })
+        );
+        assert_eq!(
+            debugger.get_cpu().unwrap().get_context().unwrap().pc,
+            0x1005
+        );
+
+        // 디버깅 중에 추가한 읽기 브레이크포인트도 바로 적용
+        debugger.remove_breakpoint(0x2000);
+        let read_id = debugger.add_breakpoint(0x2000, AccessType::Read);
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::Watchpoint {
+                id: read_id,
+                address: 0x2000,
+                access: MemoryAccess::Read,
+            })
+        );
+        assert_eq!(
+            debugger.get_cpu().unwrap().get_context().unwrap().pc,
+            0x1008
+        );
+    }
+
+    #[test]
+    fn test_rom_write_trap() {
+        // LDA #$42; STA $E000; BRK
+        let mut memory = memory::Memory::new();
+        memory
+            .load(0x1000, &[0xA9, 0x42, 0x8D, 0x00, 0xE0, 0x00])
+            .unwrap();
+        memory.load_rom(0xE000, &[0xEA]).unwrap();
+        memory.set_write_policy(memory::WritePolicy::Trap);
+        let mut cpu = CPU::new();
+        cpu.set_pc(0x1000);
+        cpu.set_memory_bus(Arc::new(Mutex::new(memory)));
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger.enabled().unwrap();
+
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::WriteProtected { address: 0xE000 })
+        );
+        assert_eq!(
+            debugger.get_cpu().unwrap().get_context().unwrap().pc,
+            0x1005
+        );
+        assert_eq!(debugger.read(0xE000).unwrap(), 0xEA);
+    }
+
+    #[test]
+    fn test_illegal_opcode_pause() {
+        // LDA #$01; .byte $02; INX
+        let program = [0xA9, 0x01, 0x02, 0xE8];
+        let mut debugger = debugger_with(MockMemoryBus::with_program(0x1000, &program));
+        debugger.enabled().unwrap();
+
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::IllegalOpcode(0x02))
+        );
+        let cpu = debugger.get_cpu_mut().unwrap();
+        assert_eq!(cpu.get_context().unwrap().pc, 0x1002);
+
+        // 잘못된 바이트를 건너뛰고 재개
+        cpu.set_pc(0x1003);
+        assert_eq!(debugger.step().unwrap(), Some(StopReason::StepComplete));
+        assert_eq!(debugger.get_cpu().unwrap().get_context().unwrap().x, 1);
+    }
+
+    #[test]
+    fn test_walk_stack() {
+        // $1000: JSR $1010 / $1010: JSR $1020 / $1020: NOP
+        let mut mem = MockMemoryBus::with_program(0x1000, &[0x20, 0x10, 0x10]);
+        mem.memory[0x1010..0x1013].copy_from_slice(&[0x20, 0x20, 0x10]);
+        mem.memory[0x1020] = 0xEA;
+        let mut debugger = debugger_with(mem);
+
+        debugger.step().unwrap();
+        debugger.step().unwrap();
+
+        assert_eq!(debugger.walk_stack().unwrap(), vec![0x1010, 0x1000]);
+    }
+
+    #[test]
+    fn test_backtrace_with_symbols() {
+        // $1000: JSR $1010 / $1010: JSR $1020 / $1020: NOP
+        let mut mem = MockMemoryBus::with_program(0x1000, &[0x20, 0x10, 0x10]);
+        mem.memory[0x1010..0x1013].copy_from_slice(&[0x20, 0x20, 0x10]);
+        mem.memory[0x1020] = 0xEA;
+        let mut debugger = debugger_with(mem);
+        debugger.enabled().unwrap();
+
+        let mut symbols = SymbolTable::new();
+        symbols.insert("MAIN", 0x1000);
+        symbols.insert("OUTER", 0x1010);
+        symbols.insert("INNER", 0x1020);
+        debugger.load_symbols(symbols);
+
+        let id = debugger
+            .add_symbol_breakpoint("INNER", AccessType::Access)
+            .unwrap();
+        assert_eq!(debugger.breakpoints()[0].address, 0x1020);
+        assert!(
+            debugger
+                .add_symbol_breakpoint("NOWHERE", AccessType::Access)
+                .is_err()
+        );
+        debugger.remove_breakpoint(0x1020);
+        assert!(debugger.breakpoints().iter().all(|bp| bp.id != id));
+
+        debugger.step().unwrap();
+        debugger.step().unwrap();
+        debugger.step().unwrap();
+
+        assert_eq!(
+            debugger.backtrace().unwrap(),
+            vec![
+                "#0  $1021  INNER+1",
+                "#1  $1010  OUTER  JSR INNER",
+                "#2  $1000  MAIN  JSR OUTER",
+            ]
+        );
+        assert!(debugger.stack_anomalies().is_empty());
+    }
 }
@@ -0,0 +1,756 @@
+//! GDB 원격 시리얼 프로토콜(RSP) 서버
+//!
+//! TCP 소켓이나 파이프(stdin/stdout)로 GDB RSP 패킷을 주고받으며
+//! 레지스터, 메모리, 브레이크포인트, 워치포인트, 스텝/계속 실행을 `Debugger`에 연결합니다.
+//!
+//! 레지스터 순서: A, X, Y, P, SP (각 8비트), PC (16비트, 리틀 엔디언)
+
+use crate::debugger::{AccessType, Debugger};
+use common::Result;
+use cpu::{CPUState, InterruptType, MemoryAccess, RegisterData, RegisterType, StopReason};
+use error::Error;
+use std::io::{self, Read, Stdin, Stdout, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+/// 계속 실행 중 Ctrl-C 확인 간격 (명령어 수)
+const POLL_INTERVAL: usize = 1000;
+
+/// qSupported로 알리는 최대 패킷 크기
+const PACKET_SIZE: usize = 0x1000;
+/// m 패킷 한 번에 읽을 수 있는 바이트 수 (16진 2글자씩)
+const MAX_MEMORY_READ: usize = PACKET_SIZE / 2;
+
+/// GDB가 읽어가는 6502 레지스터 구성
+const TARGET_XML: &str = concat!(
+    r#"<?xml version="1.0"?><!DOCTYPE target SYSTEM "gdb-target.dtd">"#,
+    r#"<target version="1.0"><feature name="org.mos6502.cpu">"#,
+    r#"<reg name="a" bitsize="8" regnum="0"/><reg name="x" bitsize="8"/>"#,
+    r#"<reg name="y" bitsize="8"/><reg name="p" bitsize="8"/>"#,
+    r#"<reg name="sp" bitsize="8"/><reg name="pc" bitsize="16" type="code_ptr"/>"#,
+    r#"</feature></target>"#
+);
+
+/// RSP 연결 - 읽기/쓰기 외에 실행 중 인터럽트(Ctrl-C) 확인 기능 제공
+pub trait GdbConnection: Read + Write {
+    /// 실행 중 클라이언트가 보낸 인터럽트(0x03)가 있는지 비차단 방식으로 확인
+    fn poll_interrupt(&mut self) -> bool {
+        false
+    }
+}
+
+impl GdbConnection for TcpStream {
+    fn poll_interrupt(&mut self) -> bool {
+        if self.set_nonblocking(true).is_err() {
+            return false;
+        }
+        let mut byte = [0u8; 1];
+        let interrupted = matches!(self.peek(&mut byte), Ok(1) if byte[0] == 0x03);
+        if interrupted {
+            let _ = self.read(&mut byte);
+        }
+        let _ = self.set_nonblocking(false);
+        interrupted
+    }
+}
+
+/// 파이프 연결 - 읽기용과 쓰기용 스트림을 묶어 사용
+pub struct PipeConnection<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: Read, W: Write> PipeConnection<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+}
+
+impl PipeConnection<Stdin, Stdout> {
+    /// 표준 입출력을 사용하는 연결 (`target remote | 명령` 형식)
+    pub fn stdio() -> Self {
+        Self::new(io::stdin(), io::stdout())
+    }
+}
+
+impl<R: Read, W: Write> Read for PipeConnection<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<R: Read, W: Write> Write for PipeConnection<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<R: Read, W: Write> GdbConnection for PipeConnection<R, W> {}
+
+/// 연결에서 읽은 입력
+enum Incoming {
+    Packet(String),
+    Interrupt,
+    Closed,
+}
+
+/// 패킷 처리 결과
+enum Reply {
+    Send(String),
+    /// 응답 후 연결 종료
+    Detach(String),
+    /// 응답 없이 연결 종료
+    Kill,
+}
+
+/// GDB RSP 서버
+pub struct GdbServer<'a> {
+    debugger: &'a mut Debugger,
+    no_ack: bool,
+}
+
+impl<'a> GdbServer<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self {
+            debugger,
+            no_ack: false,
+        }
+    }
+
+    /// TCP 주소에서 연결 하나를 받아 세션이 끝날 때까지 처리
+    pub fn listen<A: ToSocketAddrs>(&mut self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let (mut stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        self.serve(&mut stream)
+    }
+
+    /// 표준 입출력으로 세션 처리 - stdout을 프로토콜에 쓰므로 디버그 출력은 끔
+    pub fn serve_stdio(&mut self) -> Result<()> {
+        common::trace::set_enabled(false);
+        self.serve(&mut PipeConnection::stdio())
+    }
+
+    /// 연결이 끊기거나 detach/kill 요청이 올 때까지 패킷 처리
+    pub fn serve<C: GdbConnection>(&mut self, conn: &mut C) -> Result<()> {
+        if !self.debugger.is_enabled() {
+            self.debugger.enabled()?;
+        }
+        self.no_ack = false;
+
+        loop {
+            let packet = match self.read_packet(conn)? {
+                Incoming::Packet(packet) => packet,
+                // 정지 상태에서 받은 인터럽트는 현재 정지 사유로 응답
+                Incoming::Interrupt => {
+                    let reply = self.stop_reply()?;
+                    self.send_packet(conn, &reply)?;
+                    continue;
+                }
+                Incoming::Closed => return Ok(()),
+            };
+
+            match self.handle_packet(&packet, conn)? {
+                Reply::Send(reply) => self.send_packet(conn, &reply)?,
+                Reply::Detach(reply) => {
+                    self.send_packet(conn, &reply)?;
+                    return Ok(());
+                }
+                Reply::Kill => return Ok(()),
+            }
+        }
+    }
+
+    fn read_packet<C: GdbConnection>(&mut self, conn: &mut C) -> Result<Incoming> {
+        loop {
+            let Some(byte) = read_byte(conn)? else {
+                return Ok(Incoming::Closed);
+            };
+            match byte {
+                b'$' => {}
+                0x03 => return Ok(Incoming::Interrupt),
+                // ack(+/-) 및 기타 잡음 무시
+                _ => continue,
+            }
+
+            let mut data = Vec::new();
+            loop {
+                match read_byte(conn)? {
+                    Some(b'#') => break,
+                    Some(b) => data.push(b),
+                    None => return Ok(Incoming::Closed),
+                }
+            }
+            let mut checksum = [0u8; 2];
+            for digit in checksum.iter_mut() {
+                *digit = read_byte(conn)?.unwrap_or(b'0');
+            }
+
+            let expected = u8::from_str_radix(&String::from_utf8_lossy(&checksum), 16).ok();
+            if expected != Some(checksum_of(&data)) && !self.no_ack {
+                conn.write_all(b"-")?;
+                conn.flush()?;
+                continue;
+            }
+            if !self.no_ack {
+                conn.write_all(b"+")?;
+            }
+            return Ok(Incoming::Packet(
+                String::from_utf8_lossy(&data).into_owned(),
+            ));
+        }
+    }
+
+    fn send_packet<C: GdbConnection>(&mut self, conn: &mut C, data: &str) -> Result<()> {
+        let framed = format!("${}#{:02x}", data, checksum_of(data.as_bytes()));
+        conn.write_all(framed.as_bytes())?;
+        conn.flush()?;
+        Ok(())
+    }
+
+    fn handle_packet<C: GdbConnection>(&mut self, packet: &str, conn: &mut C) -> Result<Reply> {
+        let reply = match packet.as_bytes().first() {
+            Some(b'?') => self.stop_reply()?,
+            Some(b'g') => self.read_registers()?,
+            Some(b'G') => ok_or_error(self.write_registers(&packet[1..])),
+            Some(b'p') => or_error(self.read_register(&packet[1..])),
+            Some(b'P') => ok_or_error(self.write_register(&packet[1..])),
+            Some(b'm') => or_error(self.read_memory(&packet[1..])),
+            Some(b'M') => ok_or_error(self.write_memory(&packet[1..])),
+            Some(b'Z') => ok_or_error(self.set_breakpoint(&packet[1..], true)),
+            Some(b'z') => ok_or_error(self.set_breakpoint(&packet[1..], false)),
+            Some(b'c') => self.resume(conn, false)?,
+            Some(b's') => self.resume(conn, true)?,
+            Some(b'H') | Some(b'T') => "OK".to_string(),
+            Some(b'D') => return Ok(Reply::Detach("OK".to_string())),
+            Some(b'k') => return Ok(Reply::Kill),
+            Some(b'q') | Some(b'Q') | Some(b'v') => self.handle_query(packet, conn)?,
+            _ => String::new(),
+        };
+        Ok(Reply::Send(reply))
+    }
+
+    fn handle_query<C: GdbConnection>(&mut self, packet: &str, conn: &mut C) -> Result<String> {
+        let reply = if packet.starts_with("qSupported") {
+            format!(
+                "PacketSize={:x};qXfer:features:read+;swbreak+;hwbreak+;QStartNoAckMode+",
+                PACKET_SIZE
+            )
+        } else if packet == "QStartNoAckMode" {
+            self.no_ack = true;
+            "OK".to_string()
+        } else if let Some(range) = packet.strip_prefix("qXfer:features:read:target.xml:") {
+            or_error(read_xfer(TARGET_XML, range))
+        } else if packet == "qAttached" {
+            "1".to_string()
+        } else if packet == "qC" {
+            "QC1".to_string()
+        } else if packet == "qfThreadInfo" {
+            "m1".to_string()
+        } else if packet == "qsThreadInfo" {
+            "l".to_string()
+        } else if packet == "vCont?" {
+            "vCont;c;C;s;S".to_string()
+        } else if let Some(action) = packet.strip_prefix("vCont;") {
+            let single_step = action.starts_with('s') || action.starts_with('S');
+            self.resume(conn, single_step)?
+        } else {
+            String::new()
+        };
+        Ok(reply)
+    }
+
+    /// 정지 상태를 RSP 정지 응답으로 변환
+    fn stop_reply(&self) -> Result<String> {
+        let reply = match self.debugger.get_cpu()?.get_state() {
+            CPUState::Paused(StopReason::Breakpoint(_)) => "T05swbreak:;".to_string(),
+            CPUState::Paused(StopReason::Watchpoint {
+                address, access, ..
+            }) => {
+                let kind = match access {
+                    MemoryAccess::Read => "rwatch",
+                    MemoryAccess::Write => "watch",
+                };
+                format!("T05{}:{:04x};", kind, address)
+            }
+            CPUState::Paused(StopReason::IllegalOpcode(_)) => "S04".to_string(),
+            CPUState::Paused(StopReason::UserPause) => "S02".to_string(),
+            // SIGSEGV
+            CPUState::Paused(StopReason::WriteProtected { .. }) => "S0b".to_string(),
+            CPUState::Halted(InterruptType::IllegalOpcode) => "S04".to_string(),
+            _ => "S05".to_string(),
+        };
+        Ok(reply)
+    }
+
+    /// 계속 실행 또는 단일 스텝 후 정지 응답 반환
+    fn resume<C: GdbConnection>(&mut self, conn: &mut C, single_step: bool) -> Result<String> {
+        if single_step {
+            self.debugger.step()?;
+        } else {
+            while self.debugger.run_batch(POLL_INTERVAL)? {
+                if conn.poll_interrupt() {
+                    self.debugger.get_cpu_mut()?.pause(StopReason::UserPause);
+                    break;
+                }
+            }
+        }
+        self.stop_reply()
+    }
+
+    fn read_registers(&self) -> Result<String> {
+        let context = self.debugger.get_cpu()?.get_context()?;
+        Ok(format!(
+            "{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+            context.a,
+            context.x,
+            context.y,
+            context.p,
+            context.s,
+            context.pc & 0xFF,
+            context.pc >> 8
+        ))
+    }
+
+    fn write_registers(&mut self, data: &str) -> Result<()> {
+        let bytes = decode_hex(data)?;
+        if bytes.len() < 7 {
+            return Err(invalid_packet());
+        }
+        let pc = u16::from_le_bytes([bytes[5], bytes[6]]);
+        let cpu = self.debugger.get_cpu_mut()?;
+        for (i, reg) in [
+            RegisterType::A,
+            RegisterType::X,
+            RegisterType::Y,
+            RegisterType::P,
+            RegisterType::S,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            cpu.set_value(reg, RegisterData::Bit8(bytes[i]));
+        }
+        cpu.set_value(RegisterType::PC, RegisterData::Bit16(pc));
+        Ok(())
+    }
+
+    fn read_register(&self, data: &str) -> Result<String> {
+        let index = usize::from_str_radix(data, 16).map_err(|_| invalid_packet())?;
+        let cpu = self.debugger.get_cpu()?;
+        match register_at(index)? {
+            RegisterType::PC => Ok(hex_bytes(
+                &cpu.get_value(RegisterType::PC).as_u16().to_le_bytes(),
+            )),
+            reg => Ok(format!("{:02x}", cpu.get_value(reg).as_u8())),
+        }
+    }
+
+    fn write_register(&mut self, data: &str) -> Result<()> {
+        let (index, value) = data.split_once('=').ok_or_else(invalid_packet)?;
+        let index = usize::from_str_radix(index, 16).map_err(|_| invalid_packet())?;
+        let bytes = decode_hex(value)?;
+        let reg = register_at(index)?;
+        let value = match (reg, bytes.as_slice()) {
+            (RegisterType::PC, [low, high, ..]) => {
+                RegisterData::Bit16(u16::from_le_bytes([*low, *high]))
+            }
+            (RegisterType::PC, _) => return Err(invalid_packet()),
+            (_, [value, ..]) => RegisterData::Bit8(*value),
+            _ => return Err(invalid_packet()),
+        };
+        self.debugger.get_cpu_mut()?.set_value(reg, value);
+        Ok(())
+    }
+
+    fn read_memory(&self, data: &str) -> Result<String> {
+        let (address, length) = parse_address_length(data)?;
+        // 응답은 바이트당 2글자 - 알린 PacketSize 안에 들어가야 함
+        if length > MAX_MEMORY_READ {
+            return Err(invalid_packet());
+        }
+        let bytes = (0..length)
+            .map(|i| self.debugger.read(address.wrapping_add(i as u16)))
+            .collect::<Result<Vec<u8>>>()?;
+        Ok(hex_bytes(&bytes))
+    }
+
+    fn write_memory(&mut self, data: &str) -> Result<()> {
+        let (range, values) = data.split_once(':').ok_or_else(invalid_packet)?;
+        let (address, length) = parse_address_length(range)?;
+        let bytes = decode_hex(values)?;
+        if bytes.len() != length {
+            return Err(invalid_packet());
+        }
+        for (i, value) in bytes.into_iter().enumerate() {
+            self.debugger.write(address.wrapping_add(i as u16), value)?;
+        }
+        Ok(())
+    }
+
+    /// Z/z 패킷 처리 - 0/1: 실행, 2: 쓰기, 3: 읽기, 4: 읽기+쓰기
+    /// 디버거는 주소마다 브레이크포인트 하나만 두므로 다른 종류가 있는 주소에 넣거나
+    /// 종류가 다른 것을 지우려 하면 아무것도 바꾸지 않고 오류 응답
+    fn set_breakpoint(&mut self, data: &str, insert: bool) -> Result<()> {
+        let mut fields = data.split(',');
+        let kind = fields.next().ok_or_else(invalid_packet)?;
+        let address = fields.next().ok_or_else(invalid_packet)?;
+        let address = u16::from_str_radix(address, 16).map_err(|_| invalid_packet())?;
+        let length = fields
+            .next()
+            .and_then(|len| usize::from_str_radix(len, 16).ok())
+            .unwrap_or(1);
+
+        let (access_type, length) = match kind {
+            "0" | "1" => (AccessType::Access, 1),
+            "2" => (AccessType::Write, length.max(1)),
+            "3" => (AccessType::Read, length.max(1)),
+            "4" => (AccessType::Access, length.max(1)),
+            _ => return Err(invalid_packet()),
+        };
+
+        let points: Vec<(u16, Option<AccessType>)> = (0..length)
+            .map(|i| {
+                let address = address.wrapping_add(i as u16);
+                let existing = self
+                    .debugger
+                    .breakpoint_at(address)
+                    .map(|bp| bp.access_type);
+                (address, existing)
+            })
+            .collect();
+        let conflict = points.iter().any(|(_, existing)| match existing {
+            Some(existing) => *existing != access_type,
+            None => !insert,
+        });
+        if conflict {
+            return Err(invalid_packet());
+        }
+
+        for (address, existing) in points {
+            if !insert {
+                self.debugger.remove_breakpoint(address);
+            } else if existing.is_none() {
+                self.debugger.add_breakpoint(address, access_type.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_byte<R: Read>(reader: &mut R) -> Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn checksum_of(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, &b| sum.wrapping_add(b))
+}
+
+/// 데이터 응답 - 실패 시 오류 응답
+fn or_error(result: Result<String>) -> String {
+    result.unwrap_or_else(|_| "E01".to_string())
+}
+
+/// 데이터 없는 명령 응답 - 성공 시 "OK", 실패 시 오류 응답
+fn ok_or_error(result: Result<()>) -> String {
+    or_error(result.map(|_| "OK".to_string()))
+}
+
+fn invalid_packet() -> Error {
+    Error::Internal("Invalid GDB packet".to_string())
+}
+
+fn register_at(index: usize) -> Result<RegisterType> {
+    match index {
+        0 => Ok(RegisterType::A),
+        1 => Ok(RegisterType::X),
+        2 => Ok(RegisterType::Y),
+        3 => Ok(RegisterType::P),
+        4 => Ok(RegisterType::S),
+        5 => Ok(RegisterType::PC),
+        _ => Err(invalid_packet()),
+    }
+}
+
+fn parse_address_length(data: &str) -> Result<(u16, usize)> {
+    let (address, length) = data.split_once(',').ok_or_else(invalid_packet)?;
+    let address = u16::from_str_radix(address, 16).map_err(|_| invalid_packet())?;
+    let length = usize::from_str_radix(length, 16).map_err(|_| invalid_packet())?;
+    Ok((address, length))
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 16진수 쌍을 바이트로 - 패킷에 ASCII가 아닌 문자가 있어도 문자 경계와 무관하게 바이트 단위로 처리
+fn decode_hex(data: &str) -> Result<Vec<u8>> {
+    let pairs = data.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(invalid_packet());
+    }
+    pairs
+        .map(|pair| {
+            let digit = |byte: u8| char::from(byte).to_digit(16).ok_or_else(invalid_packet);
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+/// qXfer 응답 - "offset,length" 구간을 잘라 m(계속)/l(마지막) 접두어와 함께 반환
+fn read_xfer(document: &str, range: &str) -> Result<String> {
+    let (offset, length) = range.split_once(',').ok_or_else(invalid_packet)?;
+    let offset = usize::from_str_radix(offset, 16).map_err(|_| invalid_packet())?;
+    let length = usize::from_str_radix(length, 16).map_err(|_| invalid_packet())?;
+    let start = offset.min(document.len());
+    let end = (start + length).min(document.len());
+    let prefix = if end < document.len() { 'm' } else { 'l' };
+    Ok(format!("{}{}", prefix, &document[start..end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryBus;
+    use cpu::CPU;
+    use std::io::BufReader;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[derive(Debug)]
+    struct TestMemory {
+        memory: Vec<u8>,
+    }
+
+    impl MemoryBus for TestMemory {
+        fn peek(&self, address: u16) -> u8 {
+            self.memory[address as usize]
+        }
+
+        fn write(&mut self, address: u16, value: u8) {
+            self.memory[address as usize] = value;
+        }
+    }
+
+    /// 스크립트로 동작하는 RSP 클라이언트
+    struct ScriptedClient {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl ScriptedClient {
+        fn request(&mut self, packet: &str) -> String {
+            let framed = format!("${}#{:02x}", packet, checksum_of(packet.as_bytes()));
+            self.writer.write_all(framed.as_bytes()).unwrap();
+
+            let mut reply = Vec::new();
+            let mut byte = [0u8; 1];
+            // ack와 '$' 건너뛰기
+            loop {
+                self.reader.read_exact(&mut byte).unwrap();
+                if byte[0] == b'$' {
+                    break;
+                }
+            }
+            loop {
+                self.reader.read_exact(&mut byte).unwrap();
+                if byte[0] == b'#' {
+                    break;
+                }
+                reply.push(byte[0]);
+            }
+            let mut checksum = [0u8; 2];
+            self.reader.read_exact(&mut checksum).unwrap();
+            self.writer.write_all(b"+").unwrap();
+            String::from_utf8(reply).unwrap()
+        }
+    }
+
+    // $1000: LDA #$42; STA $2000; INX; INX; BRK
+    fn test_debugger() -> Debugger {
+        let mut memory = vec![0u8; 0x10000];
+        memory[0x1000..0x1008].copy_from_slice(&[0xA9, 0x42, 0x8D, 0x00, 0x20, 0xE8, 0xE8, 0x00]);
+        let mut cpu = CPU::new();
+        cpu.set_pc(0x1000);
+        cpu.set_memory_bus(Arc::new(Mutex::new(TestMemory { memory })));
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger
+    }
+
+    fn frame(packet: &str) -> String {
+        format!("${}#{:02x}", packet, checksum_of(packet.as_bytes()))
+    }
+
+    /// 입력 바이트를 파이프로 흘려보내고 서버가 쓴 바이트를 그대로 반환
+    fn serve_bytes(debugger: &mut Debugger, input: &[u8]) -> String {
+        let mut conn = PipeConnection::new(io::Cursor::new(input.to_vec()), Vec::new());
+        GdbServer::new(debugger).serve(&mut conn).unwrap();
+        String::from_utf8(conn.writer).unwrap()
+    }
+
+    /// 패킷을 차례로 보내고 응답 패킷 내용만 모음
+    fn serve_packets(debugger: &mut Debugger, packets: &[&str]) -> Vec<String> {
+        let input: String = packets.iter().map(|packet| frame(packet)).collect();
+        serve_bytes(debugger, input.as_bytes())
+            .split('$')
+            .skip(1)
+            .map(|reply| reply.split('#').next().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_checksum_nak_and_retransmit() {
+        let mut debugger = test_debugger();
+        // 체크섬이 틀린 패킷은 NAK, 같은 패킷을 다시 보내면 처리
+        let input = format!("$?#00{}{}", frame("?"), frame("D"));
+        let output = serve_bytes(&mut debugger, input.as_bytes());
+        assert_eq!(output, format!("-+{}+{}", frame("S05"), frame("OK")));
+    }
+
+    #[test]
+    fn test_malformed_packets() {
+        let mut debugger = test_debugger();
+        let replies = serve_packets(
+            &mut debugger,
+            &[
+                "m2000",
+                "mzz,1",
+                "m0,ffffffff",
+                "m0,801",
+                "M1000,2:abc",
+                "M1000,2:abcdef",
+                "M1000,2:a\u{e9}b",
+                "M1000,1:\u{fffd}",
+                "M1000,1:+f",
+                "P9=00",
+                "Z9,1000,1",
+                "Z0",
+                "Xbogus",
+            ],
+        );
+        let (errors, unsupported) = replies.split_at(replies.len() - 1);
+        assert!(errors.iter().all(|reply| reply == "E01"), "{:?}", errors);
+        assert_eq!(unsupported, [""]);
+        assert_eq!(serve_packets(&mut debugger, &["m0,800"])[0].len(), 0x1000);
+    }
+
+    #[test]
+    fn test_non_ascii_payload() {
+        let mut debugger = test_debugger();
+        // UTF-8이 아닌 바이트는 U+FFFD(3바이트)로 바뀌어 문자 경계가 어긋남
+        let payload = b"M1000,2:\xff\xfe00";
+        let mut input = vec![b'$'];
+        input.extend_from_slice(payload);
+        input.extend_from_slice(format!("#{:02x}", checksum_of(payload)).as_bytes());
+        input.extend_from_slice(frame("m1000,2").as_bytes());
+        let output = serve_bytes(&mut debugger, &input);
+        assert_eq!(output, format!("+{}+{}", frame("E01"), frame("a942")));
+    }
+
+    #[test]
+    fn test_breakpoint_types_do_not_clobber_each_other() {
+        let mut debugger = test_debugger();
+        let replies = serve_packets(
+            &mut debugger,
+            &[
+                "Z0,1005,1",
+                "Z2,1005,1",
+                "z2,1005,1",
+                "Z2,2000,2",
+                "z3,2000,2",
+                "Z2,2000,1",
+            ],
+        );
+        assert_eq!(replies, ["OK", "E01", "E01", "OK", "E01", "OK"]);
+        assert_eq!(
+            debugger.breakpoint_at(0x1005).unwrap().access_type,
+            AccessType::Access
+        );
+        assert_eq!(
+            debugger.breakpoint_at(0x2001).unwrap().access_type,
+            AccessType::Write
+        );
+
+        let replies = serve_packets(&mut debugger, &["z0,1005,1", "z0,1005,1", "z2,2000,2"]);
+        assert_eq!(replies, ["OK", "E01", "OK"]);
+        assert!(debugger.breakpoints().is_empty());
+    }
+
+    #[test]
+    fn test_stop_reply_after_breakpoint() {
+        let mut debugger = test_debugger();
+        let replies = serve_packets(&mut debugger, &["?", "Z0,1005,1", "c", "?", "p5"]);
+        assert_eq!(
+            replies,
+            ["S05", "OK", "T05swbreak:;", "T05swbreak:;", "0510"]
+        );
+    }
+
+    #[test]
+    fn test_scripted_rsp_session() {
+        let mut debugger = test_debugger();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let stream = TcpStream::connect(addr).unwrap();
+            let mut client = ScriptedClient {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+            };
+            let replies = vec![
+                client.request("qSupported:swbreak+"),
+                client.request("g"),
+                client.request("Z0,1006,1"),
+                client.request("Z2,2000,1"),
+                client.request("c"),
+                client.request("m2000,1"),
+                client.request("z2,2000,1"),
+                client.request("c"),
+                client.request("p1"),
+                client.request("s"),
+                client.request("P0=7f"),
+                client.request("M3000,2:beef"),
+                client.request("m3000,2"),
+                client.request("g"),
+                client.request("D"),
+            ];
+            replies
+        });
+
+        let (mut stream, _) = listener.accept().unwrap();
+        GdbServer::new(&mut debugger).serve(&mut stream).unwrap();
+        let replies = client.join().unwrap();
+
+        assert!(replies[0].contains("swbreak+"));
+        assert_eq!(replies[1], "00000020fd0010");
+        assert_eq!(replies[2], "OK");
+        assert_eq!(replies[3], "OK");
+        assert_eq!(replies[4], "T05watch:2000;");
+        assert_eq!(replies[5], "42");
+        assert_eq!(replies[6], "OK");
+        assert_eq!(replies[7], "T05swbreak:;");
+        assert_eq!(replies[8], "01");
+        assert_eq!(replies[9], "S05");
+        assert_eq!(replies[10], "OK");
+        assert_eq!(replies[11], "OK");
+        assert_eq!(replies[12], "beef");
+        // A=$7F, X=$02, PC=$1007
+        assert!(replies[13].starts_with("7f02"));
+        assert!(replies[13].ends_with("0710"));
+        assert_eq!(replies[14], "OK");
+    }
+}
@@ -1,6 +1,20 @@
+mod access_log;
+mod call_stack;
+mod coverage;
 mod debugger;
+mod gdb;
+mod profiler;
+mod remote;
+mod session;
 
-pub use debugger::Debugger;
+pub use access_log::{AccessLog, AccessRecord, AddressAccess, DEFAULT_HISTORY, HeatmapKind};
+pub use call_stack::{CallFrame, CallStack, FrameKind, StackAnomaly};
+pub use coverage::{Coverage, CoverageFlags, CoverageSummary};
+pub use debugger::{AccessType, BreakPoint, Debugger, PauseHandle};
+pub use gdb::{GdbConnection, GdbServer, PipeConnection};
+pub use profiler::{AddressProfile, FunctionProfile, Profiler};
+pub use remote::{Command, RemoteDebugger, Status};
+pub use session::{ProgramImage, SaveState, Session, SessionBreakpoint, SessionMachine};
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -0,0 +1,278 @@
+//! 실행 프로파일러 - 주소별 실행 횟수/사이클과 서브루틴별 누적 사이클 집계
+use crate::call_stack::CallFrame;
+use common::SymbolTable;
+use std::collections::HashMap;
+
+/// 콜 스택이 비어 있을 때의 최상위 프레임 이름
+const ROOT_FRAME: &str = "root";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressProfile {
+    pub address: u16,
+    /// 실행 횟수
+    pub count: u64,
+    /// 소비한 사이클
+    pub cycles: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// 서브루틴 또는 인터럽트 핸들러 주소
+    pub address: u16,
+    pub calls: u64,
+    /// 하위 호출을 포함한 사이클
+    pub cycles: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    enabled: bool,
+    // 주소별 (실행 횟수, 사이클)
+    addresses: HashMap<u16, (u64, u64)>,
+    // 서브루틴 진입 횟수
+    calls: HashMap<u16, u64>,
+    // 호출 경로(바깥쪽부터 서브루틴 주소)별 사이클
+    stacks: HashMap<Vec<u16>, u64>,
+    instructions: u64,
+    cycles: u64,
+    // 경로 조회용 버퍼 - 명령어마다 할당하지 않기 위함
+    path: Vec<u16>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// 집계 초기화 (활성화 여부는 유지)
+    pub fn clear(&mut self) {
+        self.addresses.clear();
+        self.calls.clear();
+        self.stacks.clear();
+        self.instructions = 0;
+        self.cycles = 0;
+    }
+
+    /// 명령어 실행 기록 - frames는 명령어 실행 전의 콜 스택
+    pub fn record(&mut self, address: u16, cycles: u8, frames: &[CallFrame]) {
+        let cycles = cycles as u64;
+        let entry = self.addresses.entry(address).or_default();
+        entry.0 += 1;
+        entry.1 += cycles;
+        self.instructions += 1;
+        self.cycles += cycles;
+
+        self.path.clear();
+        self.path.extend(frames.iter().map(|frame| frame.target));
+        match self.stacks.get_mut(self.path.as_slice()) {
+            Some(total) => *total += cycles,
+            None => {
+                self.stacks.insert(self.path.clone(), cycles);
+            }
+        }
+    }
+
+    /// JSR 또는 인터럽트로 target에 진입
+    pub fn record_call(&mut self, target: u16) {
+        *self.calls.entry(target).or_default() += 1;
+    }
+
+    pub fn total_instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    pub fn total_cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// 사이클이 많은 주소부터
+    pub fn hot_spots(&self) -> Vec<AddressProfile> {
+        let mut hot_spots: Vec<AddressProfile> = self
+            .addresses
+            .iter()
+            .map(|(&address, &(count, cycles))| AddressProfile {
+                address,
+                count,
+                cycles,
+            })
+            .collect();
+        hot_spots.sort_by_key(|profile| (std::cmp::Reverse(profile.cycles), profile.address));
+        hot_spots
+    }
+
+    /// 누적 사이클이 많은 서브루틴부터
+    pub fn functions(&self) -> Vec<FunctionProfile> {
+        let mut inclusive: HashMap<u16, u64> = HashMap::new();
+        for (path, &cycles) in &self.stacks {
+            // 재귀 호출은 한 번만 더함
+            let mut seen = Vec::with_capacity(path.len());
+            for &target in path {
+                if !seen.contains(&target) {
+                    seen.push(target);
+                    *inclusive.entry(target).or_default() += cycles;
+                }
+            }
+        }
+        let mut functions: Vec<FunctionProfile> = inclusive
+            .into_iter()
+            .map(|(address, cycles)| FunctionProfile {
+                address,
+                calls: self.calls.get(&address).copied().unwrap_or_default(),
+                cycles,
+            })
+            .collect();
+        functions.sort_by_key(|profile| (std::cmp::Reverse(profile.cycles), profile.address));
+        functions
+    }
+
+    /// flamegraph.pl / inferno 입력용 `root;MAIN;PRINT 123` 형식
+    pub fn collapsed(&self, symbols: &SymbolTable) -> String {
+        let mut lines: Vec<String> = self
+            .stacks
+            .iter()
+            .map(|(path, cycles)| {
+                let frames: Vec<String> = std::iter::once(ROOT_FRAME.to_string())
+                    .chain(path.iter().map(|&target| symbols.describe(target)))
+                    .collect();
+                format!("{} {}", frames.join(";"), cycles)
+            })
+            .collect();
+        lines.sort();
+        lines.join("\n")
+    }
+
+    /// 텍스트 보고서 - disassemble은 주소의 명령어 문자열을 돌려줌
+    pub fn report(
+        &self,
+        symbols: &SymbolTable,
+        limit: usize,
+        disassemble: impl Fn(u16) -> String,
+    ) -> String {
+        let percent = |cycles: u64| {
+            if self.cycles == 0 {
+                0.0
+            } else {
+                cycles as f64 * 100.0 / self.cycles as f64
+            }
+        };
+
+        let mut lines = vec![
+            format!(
+                "Instructions: {}  Cycles: {}",
+                self.instructions, self.cycles
+            ),
+            String::new(),
+            "Hot spots:".to_string(),
+            format!(
+                "{:<7} {:<16} {:>10} {:>12} {:>6}  INSTRUCTION",
+                "ADDR", "LABEL", "COUNT", "CYCLES", "%"
+            ),
+        ];
+        for profile in self.hot_spots().iter().take(limit) {
+            lines.push(format!(
+                "${:04X}   {:<16} {:>10} {:>12} {:>5.1}%  {}",
+                profile.address,
+                symbols.describe(profile.address),
+                profile.count,
+                profile.cycles,
+                percent(profile.cycles),
+                disassemble(profile.address)
+            ));
+        }
+
+        let functions = self.functions();
+        if !functions.is_empty() {
+            lines.push(String::new());
+            lines.push("Subroutines (inclusive):".to_string());
+            lines.push(format!(
+                "{:<7} {:<16} {:>10} {:>12} {:>6}",
+                "ADDR", "LABEL", "CALLS", "CYCLES", "%"
+            ));
+            for profile in functions.iter().take(limit) {
+                lines.push(format!(
+                    "${:04X}   {:<16} {:>10} {:>12} {:>5.1}%",
+                    profile.address,
+                    symbols.describe(profile.address),
+                    profile.calls,
+                    profile.cycles,
+                    percent(profile.cycles)
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_stack::FrameKind;
+
+    fn frame(target: u16) -> CallFrame {
+        CallFrame {
+            kind: FrameKind::Subroutine,
+            call_site: 0,
+            target,
+            return_address: 0,
+            stack_pointer: 0,
+        }
+    }
+
+    #[test]
+    fn test_cycle_attribution() {
+        let mut profiler = Profiler::new();
+        let outer = [frame(0x1010)];
+        let inner = [frame(0x1010), frame(0x1020)];
+
+        profiler.record(0x1000, 6, &[]);
+        profiler.record_call(0x1010);
+        profiler.record(0x1010, 6, &outer);
+        profiler.record_call(0x1020);
+        for _ in 0..5 {
+            profiler.record(0x1020, 2, &inner);
+        }
+        profiler.record(0x1022, 6, &inner);
+        profiler.record(0x1013, 6, &outer);
+
+        assert_eq!(profiler.total_cycles(), 34);
+        assert_eq!(
+            profiler.hot_spots()[0],
+            AddressProfile {
+                address: 0x1020,
+                count: 5,
+                cycles: 10
+            }
+        );
+        assert_eq!(
+            profiler.functions(),
+            vec![
+                FunctionProfile {
+                    address: 0x1010,
+                    calls: 1,
+                    cycles: 28
+                },
+                FunctionProfile {
+                    address: 0x1020,
+                    calls: 1,
+                    cycles: 16
+                },
+            ]
+        );
+
+        let mut symbols = SymbolTable::new();
+        symbols.insert("OUTER", 0x1010);
+        symbols.insert("INNER", 0x1020);
+        assert_eq!(
+            profiler.collapsed(&symbols),
+            "root 6\nroot;OUTER 12\nroot;OUTER;INNER 16"
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! 작업 스레드에서 디버거 실행 - 명령 채널로 제어하고 상태 채널로 결과를 받음
+use crate::debugger::{AccessType, Debugger};
+use common::Result;
+use cpu::{CPUState, InterruptType, RegisterType};
+use error::Error;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// 명령 확인 사이에 실행할 명령어 수
+const BATCH_SIZE: usize = 1000;
+/// 실행 중 Running 상태를 보내는 간격
+const STATUS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 작업 스레드로 보내는 명령
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Pause,
+    Resume,
+    Step,
+    /// 응답: Status::Memory
+    Read {
+        address: u16,
+        length: usize,
+    },
+    Write {
+        address: u16,
+        data: Vec<u8>,
+    },
+    /// 응답: Status::BreakpointAdded
+    AddBreakpoint {
+        address: u16,
+        access: AccessType,
+    },
+    RemoveBreakpoint {
+        address: u16,
+    },
+    Interrupt(InterruptType),
+    Shutdown,
+}
+
+/// 작업 스레드가 보내는 상태
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// 실행 중 (주기적으로 전송)
+    Running {
+        cycles: u64,
+    },
+    /// 일시 정지/정지 - state로 사유 확인
+    Stopped {
+        state: CPUState,
+        pc: u16,
+        cycles: u64,
+    },
+    Memory {
+        address: u16,
+        data: Vec<u8>,
+    },
+    BreakpointAdded {
+        id: usize,
+        address: u16,
+    },
+    Error(String),
+}
+
+/// 작업 스레드에서 실행 중인 디버거 핸들
+pub struct RemoteDebugger {
+    commands: Sender<Command>,
+    status: Receiver<Status>,
+    worker: Option<JoinHandle<Debugger>>,
+}
+
+impl RemoteDebugger {
+    /// CPU가 연결된 디버거를 작업 스레드로 옮김 - 정지 상태로 시작
+    pub fn spawn(mut debugger: Debugger) -> Result<Self> {
+        if !debugger.is_enabled() {
+            debugger.enabled()?;
+        }
+        let (command_tx, command_rx) = mpsc::channel();
+        let (status_tx, status_rx) = mpsc::channel();
+        let worker = thread::spawn(move || Worker::new(debugger, status_tx).run(command_rx));
+        Ok(Self {
+            commands: command_tx,
+            status: status_rx,
+            worker: Some(worker),
+        })
+    }
+
+    pub fn send(&self, command: Command) -> Result<()> {
+        self.commands
+            .send(command)
+            .map_err(|_| Error::Internal("Remote debugger worker stopped".to_string()))
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        self.send(Command::Pause)
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        self.send(Command::Resume)
+    }
+
+    pub fn step(&self) -> Result<()> {
+        self.send(Command::Step)
+    }
+
+    pub fn read(&self, address: u16, length: usize) -> Result<()> {
+        self.send(Command::Read { address, length })
+    }
+
+    pub fn write(&self, address: u16, data: Vec<u8>) -> Result<()> {
+        self.send(Command::Write { address, data })
+    }
+
+    pub fn add_breakpoint(&self, address: u16, access: AccessType) -> Result<()> {
+        self.send(Command::AddBreakpoint { address, access })
+    }
+
+    pub fn remove_breakpoint(&self, address: u16) -> Result<()> {
+        self.send(Command::RemoveBreakpoint { address })
+    }
+
+    pub fn send_interrupt(&self, interrupt: InterruptType) -> Result<()> {
+        self.send(Command::Interrupt(interrupt))
+    }
+
+    /// 대기 없이 상태 하나 받기
+    pub fn try_status(&self) -> Option<Status> {
+        self.status.try_recv().ok()
+    }
+
+    /// 최대 timeout 동안 상태 하나 기다리기
+    pub fn wait_status(&self, timeout: Duration) -> Option<Status> {
+        self.status.recv_timeout(timeout).ok()
+    }
+
+    /// Running 상태는 건너뛰고 Running이 아닌 다음 상태를 기다림
+    pub fn wait_until_stopped(&self, timeout: Duration) -> Option<Status> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            match self.status.recv_timeout(remaining) {
+                Ok(Status::Running { .. }) => continue,
+                Ok(status) => return Some(status),
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    /// 작업 스레드를 종료하고 디버거를 돌려받음
+    pub fn shutdown(mut self) -> Result<Debugger> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<Debugger> {
+        let worker = self.worker.take().ok_or(Error::Internal(
+            "Remote debugger already stopped".to_string(),
+        ))?;
+        // 이미 종료된 경우에는 전송 실패를 무시
+        let _ = self.commands.send(Command::Shutdown);
+        worker
+            .join()
+            .map_err(|_| Error::Internal("Remote debugger worker panicked".to_string()))
+    }
+}
+
+impl Drop for RemoteDebugger {
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+struct Worker {
+    debugger: Debugger,
+    status: Sender<Status>,
+    running: bool,
+}
+
+impl Worker {
+    fn new(debugger: Debugger, status: Sender<Status>) -> Self {
+        Self {
+            debugger,
+            status,
+            running: false,
+        }
+    }
+
+    fn run(mut self, commands: Receiver<Command>) -> Debugger {
+        let mut last_status = Instant::now();
+        loop {
+            // 정지 상태에서는 명령이 올 때까지 대기, 실행 중에는 배치 사이마다 확인
+            let command = if self.running {
+                match commands.try_recv() {
+                    Ok(command) => Some(command),
+                    Err(TryRecvError::Empty) => None,
+                    Err(TryRecvError::Disconnected) => break,
+                }
+            } else {
+                match commands.recv() {
+                    Ok(command) => Some(command),
+                    Err(_) => break,
+                }
+            };
+
+            match command {
+                Some(Command::Shutdown) => break,
+                Some(command) => self.handle(command),
+                None => {}
+            }
+
+            if self.running {
+                match self.debugger.run_batch(BATCH_SIZE) {
+                    Ok(true) => {
+                        if last_status.elapsed() >= STATUS_INTERVAL {
+                            last_status = Instant::now();
+                            self.send_running();
+                        }
+                    }
+                    Ok(false) => self.stop(),
+                    Err(e) => {
+                        self.send(Status::Error(e.to_string()));
+                        self.stop();
+                    }
+                }
+            }
+        }
+        self.debugger
+    }
+
+    fn handle(&mut self, command: Command) {
+        let result = match command {
+            Command::Pause => {
+                self.pause();
+                self.send_stopped();
+                Ok(())
+            }
+            Command::Resume => {
+                self.running = true;
+                self.send_running();
+                Ok(())
+            }
+            Command::Step => {
+                self.pause();
+                self.debugger.step().map(|_| self.send_stopped())
+            }
+            Command::Read { address, length } => (0..length)
+                .map(|offset| self.debugger.read(address.wrapping_add(offset as u16)))
+                .collect::<Result<Vec<u8>>>()
+                .map(|data| self.send(Status::Memory { address, data })),
+            Command::Write { address, data } => {
+                data.iter().enumerate().try_for_each(|(offset, &value)| {
+                    self.debugger
+                        .write(address.wrapping_add(offset as u16), value)
+                })
+            }
+            Command::AddBreakpoint { address, access } => {
+                let id = self.debugger.add_breakpoint(address, access);
+                self.send(Status::BreakpointAdded { id, address });
+                Ok(())
+            }
+            Command::RemoveBreakpoint { address } => {
+                self.debugger.remove_breakpoint(address);
+                Ok(())
+            }
+            Command::Interrupt(interrupt) => self.debugger.send_interrupt(interrupt),
+            Command::Shutdown => Ok(()),
+        };
+        if let Err(e) = result {
+            self.send(Status::Error(e.to_string()));
+        }
+    }
+
+    /// 실행 중이면 사용자 일시 정지로 멈춤
+    fn pause(&mut self) {
+        if self.running
+            && let Ok(cpu) = self.debugger.get_cpu_mut()
+            && cpu.get_state() == CPUState::Running
+        {
+            cpu.pause(cpu::StopReason::UserPause);
+        }
+        self.running = false;
+    }
+
+    fn stop(&mut self) {
+        self.running = false;
+        self.send_stopped();
+    }
+
+    fn send_running(&self) {
+        if let Ok(cpu) = self.debugger.get_cpu() {
+            self.send(Status::Running {
+                cycles: cpu.total_cycles(),
+            });
+        }
+    }
+
+    fn send_stopped(&self) {
+        if let Ok(cpu) = self.debugger.get_cpu() {
+            self.send(Status::Stopped {
+                state: cpu.get_state(),
+                pc: cpu.get_value(RegisterType::PC).as_u16(),
+                cycles: cpu.total_cycles(),
+            });
+        }
+    }
+
+    fn send(&self, status: Status) {
+        // 핸들이 사라졌으면 다음 명령 수신에서 종료됨
+        let _ = self.status.send(status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryBus;
+    use cpu::{CPU, StopReason};
+    use memory::Memory;
+    use std::sync::{Arc, Mutex};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn remote_with(program: &[u8]) -> RemoteDebugger {
+        let mut memory = Memory::new();
+        for (offset, &byte) in program.iter().enumerate() {
+            memory.write(0x0200 + offset as u16, byte);
+        }
+        let mut cpu = CPU::new();
+        cpu.set_memory_bus(Arc::new(Mutex::new(memory)));
+        cpu.set_pc(0x0200);
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        RemoteDebugger::spawn(debugger).unwrap()
+    }
+
+    #[test]
+    fn test_breakpoint_and_memory() {
+        // LDA #$42 / STA $10 / BRK
+        let remote = remote_with(&[0xA9, 0x42, 0x85, 0x10, 0x00]);
+        remote.add_breakpoint(0x0204, AccessType::Access).unwrap();
+        let Some(Status::BreakpointAdded { id, .. }) = remote.wait_status(TIMEOUT) else {
+            panic!("breakpoint not added");
+        };
+
+        remote.resume().unwrap();
+        assert_eq!(
+            remote.wait_until_stopped(TIMEOUT),
+            Some(Status::Stopped {
+                state: CPUState::Paused(StopReason::Breakpoint(id)),
+                pc: 0x0204,
+                cycles: 5,
+            })
+        );
+
+        remote.write(0x11, vec![0x43]).unwrap();
+        remote.read(0x10, 2).unwrap();
+        assert_eq!(
+            remote.wait_status(TIMEOUT),
+            Some(Status::Memory {
+                address: 0x10,
+                data: vec![0x42, 0x43],
+            })
+        );
+
+        let debugger = remote.shutdown().unwrap();
+        assert_eq!(
+            debugger.get_cpu().unwrap().get_context().unwrap().pc,
+            0x0204
+        );
+    }
+
+    #[test]
+    fn test_pause_running_cpu() {
+        // JMP $0200
+        let remote = remote_with(&[0x4C, 0x00, 0x02]);
+        remote.resume().unwrap();
+        assert!(matches!(
+            remote.wait_status(TIMEOUT),
+            Some(Status::Running { .. })
+        ));
+
+        remote.pause().unwrap();
+        let Some(Status::Stopped { state, pc, cycles }) = remote.wait_until_stopped(TIMEOUT) else {
+            panic!("CPU did not stop");
+        };
+        assert_eq!(state, CPUState::Paused(StopReason::UserPause));
+        assert_eq!(pc, 0x0200);
+        assert!(cycles > 0);
+
+        remote.step().unwrap();
+        assert!(matches!(
+            remote.wait_until_stopped(TIMEOUT),
+            Some(Status::Stopped {
+                state: CPUState::Paused(StopReason::StepComplete),
+                pc: 0x0200,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+//! 디버깅 세션 저장/복원 - 브레이크포인트, 적재한 바이너리, 심볼 파일, 머신 설정, 세이브 스테이트
+use crate::debugger::{AccessType, Debugger};
+use common::{Result, SymbolTable};
+use cpu::{RegisterData, RegisterType};
+use error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 세이브 스테이트 파일 식별자
+const SAVE_STATE_MAGIC: &[u8; 8] = b"M6502ST1";
+/// 식별자 + 레지스터(A X Y S P PCL PCH) + 64KB 메모리
+const SAVE_STATE_SIZE: usize = 8 + 7 + 0x10000;
+
+/// 지정 주소에 적재한 바이너리
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramImage {
+    pub path: PathBuf,
+    #[serde(with = "hex_address")]
+    pub address: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBreakpoint {
+    #[serde(with = "hex_address")]
+    pub address: u16,
+    pub access: AccessType,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// 세션의 머신 - 프리셋 이름과 ROM 이미지, 또는 머신 기술 파일 (TOML)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionMachine {
+    Preset { name: String, rom: PathBuf },
+    Description(PathBuf),
+}
+
+impl SessionMachine {
+    fn path_mut(&mut self) -> &mut PathBuf {
+        match self {
+            SessionMachine::Preset { rom, .. } => rom,
+            SessionMachine::Description(path) => path,
+        }
+    }
+}
+
+/// 세션 파일 (TOML) - 파일 안의 경로는 세션 파일 위치 기준
+/// 메모리에 들고 있는 경로는 현재 디렉터리 기준 (load/save에서 변환)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// 없으면 64KB RAM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<SessionMachine>,
+    #[serde(default)]
+    pub programs: Vec<ProgramImage>,
+    #[serde(default)]
+    pub symbol_files: Vec<PathBuf>,
+    /// 직접 추가한 레이블
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, u16>,
+    #[serde(default)]
+    pub breakpoints: Vec<SessionBreakpoint>,
+    /// 세이브 스테이트 파일 - 있으면 프로그램 적재 후 메모리와 레지스터를 덮어씀
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<PathBuf>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 세션 파일 읽기 - 상대 경로는 세션 파일 위치 기준으로 바꿈
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut session: Session =
+            toml::from_str(&text).map_err(|e| Error::InvalidSession(e.to_string()))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        if let Some(machine) = &mut session.machine {
+            let path = machine.path_mut();
+            *path = base.join(&*path);
+        }
+        for program in &mut session.programs {
+            program.path = base.join(&program.path);
+        }
+        for symbol_file in &mut session.symbol_files {
+            *symbol_file = base.join(&*symbol_file);
+        }
+        if let Some(state) = &mut session.state {
+            *state = base.join(&*state);
+        }
+        Ok(session)
+    }
+
+    /// 세션 파일 쓰기 - 경로는 세션 파일 위치 기준 상대 경로로 (밖에 있으면 절대 경로)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let base = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => absolute(parent)?,
+            _ => absolute(Path::new("."))?,
+        };
+        let mut session = self.clone();
+        if let Some(machine) = &mut session.machine {
+            let path = machine.path_mut();
+            *path = relative_to(path, &base)?;
+        }
+        for program in &mut session.programs {
+            program.path = relative_to(&program.path, &base)?;
+        }
+        for symbol_file in &mut session.symbol_files {
+            *symbol_file = relative_to(symbol_file, &base)?;
+        }
+        if let Some(state) = &mut session.state {
+            *state = relative_to(state, &base)?;
+        }
+
+        let text = toml::to_string(&session).map_err(|e| Error::InvalidSession(e.to_string()))?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// 디버거의 현재 브레이크포인트로 교체
+    pub fn capture_breakpoints(&mut self, debugger: &Debugger) {
+        self.breakpoints = debugger
+            .breakpoints()
+            .into_iter()
+            .map(|bp| SessionBreakpoint {
+                address: bp.address,
+                access: bp.access_type,
+                enabled: bp.enabled,
+            })
+            .collect();
+    }
+
+    /// 프로그램 적재, 심볼/레이블 설정, 브레이크포인트 추가, 세이브 스테이트 복원 순으로 적용
+    pub fn apply(&self, debugger: &mut Debugger) -> Result<()> {
+        for program in &self.programs {
+            let data = std::fs::read(&program.path)?;
+            for (offset, &byte) in data.iter().enumerate() {
+                debugger.write(program.address.wrapping_add(offset as u16), byte)?;
+            }
+        }
+
+        let mut symbols = debugger.symbols().clone();
+        for symbol_file in &self.symbol_files {
+            symbols.merge(&SymbolTable::load(symbol_file)?);
+        }
+        for (name, &address) in &self.labels {
+            symbols.insert(name.clone(), address);
+        }
+        debugger.load_symbols(symbols);
+
+        for breakpoint in &self.breakpoints {
+            debugger.add_breakpoint(breakpoint.address, breakpoint.access.clone());
+            if !breakpoint.enabled {
+                debugger.toggle_breakpoint(breakpoint.address);
+            }
+        }
+
+        if let Some(state) = &self.state {
+            SaveState::load(state)?.restore(debugger)?;
+        }
+        Ok(())
+    }
+}
+
+/// 파일이 없어도 절대 경로는 만들 수 있도록 canonicalize 실패 시 현재 디렉터리 기준으로
+fn absolute(path: &Path) -> Result<PathBuf> {
+    Ok(path.canonicalize().or_else(|_| std::path::absolute(path))?)
+}
+
+fn relative_to(path: &Path, base: &Path) -> Result<PathBuf> {
+    let path = absolute(path)?;
+    Ok(path
+        .strip_prefix(base)
+        .map_or_else(|_| path.clone(), Path::to_path_buf))
+}
+
+/// CPU 레지스터와 64KB 메모리 전체
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveState {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    pub s: u8,
+    pub p: u8,
+    pub pc: u16,
+    pub memory: Vec<u8>,
+}
+
+impl SaveState {
+    pub fn capture(debugger: &Debugger) -> Result<Self> {
+        let cpu = debugger.get_cpu()?;
+        let memory = (0..=0xFFFF)
+            .map(|address| debugger.read(address))
+            .collect::<Result<Vec<u8>>>()?;
+        Ok(Self {
+            a: cpu.get_value(RegisterType::A).as_u8(),
+            x: cpu.get_value(RegisterType::X).as_u8(),
+            y: cpu.get_value(RegisterType::Y).as_u8(),
+            s: cpu.get_value(RegisterType::S).as_u8(),
+            p: cpu.get_value(RegisterType::P).as_u8(),
+            pc: cpu.get_value(RegisterType::PC).as_u16(),
+            memory,
+        })
+    }
+
+    pub fn restore(&self, debugger: &mut Debugger) -> Result<()> {
+        for (address, &byte) in self.memory.iter().enumerate() {
+            debugger.write(address as u16, byte)?;
+        }
+        let cpu = debugger.get_cpu_mut()?;
+        cpu.set_value(RegisterType::A, RegisterData::Bit8(self.a));
+        cpu.set_value(RegisterType::X, RegisterData::Bit8(self.x));
+        cpu.set_value(RegisterType::Y, RegisterData::Bit8(self.y));
+        cpu.set_value(RegisterType::S, RegisterData::Bit8(self.s));
+        cpu.set_value(RegisterType::P, RegisterData::Bit8(self.p));
+        cpu.set_pc(self.pc);
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SAVE_STATE_SIZE);
+        bytes.extend_from_slice(SAVE_STATE_MAGIC);
+        bytes.extend_from_slice(&[self.a, self.x, self.y, self.s, self.p]);
+        bytes.extend_from_slice(&self.pc.to_le_bytes());
+        bytes.extend_from_slice(&self.memory);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != SAVE_STATE_SIZE || !bytes.starts_with(SAVE_STATE_MAGIC) {
+            return Err(Error::InvalidSaveState);
+        }
+        let registers = &bytes[8..15];
+        Ok(Self {
+            a: registers[0],
+            x: registers[1],
+            y: registers[2],
+            s: registers[3],
+            p: registers[4],
+            pc: u16::from_le_bytes([registers[5], registers[6]]),
+            memory: bytes[15..].to_vec(),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+/// 주소를 "$C000" 문자열로 저장 - 읽을 때는 정수도 허용
+mod hex_address {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(address: &u16, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("${:04X}", address))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Address {
+            Number(u16),
+            Text(String),
+        }
+        match Address::deserialize(deserializer)? {
+            Address::Number(address) => Ok(address),
+            Address::Text(text) => {
+                let hex = text
+                    .strip_prefix('$')
+                    .or_else(|| text.strip_prefix("0x"))
+                    .unwrap_or(&text);
+                u16::from_str_radix(hex, 16)
+                    .map_err(|_| de::Error::custom(format!("invalid address: {}", text)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let text = r#"
+            machine = { preset = { name = "apple1", rom = "woz.rom" } }
+            programs = [{ path = "game.bin", address = "$0800" }]
+            symbol_files = ["game.lbl"]
+            state = "game.state"
+
+            [labels]
+            MAIN = 2048
+
+            [[breakpoints]]
+            address = "$0810"
+            access = "access"
+
+            [[breakpoints]]
+            address = 0xD020
+            access = "write"
+            enabled = false
+        "#;
+        let session: Session = toml::from_str(text).unwrap();
+        assert_eq!(
+            session.machine,
+            Some(SessionMachine::Preset {
+                name: "apple1".to_string(),
+                rom: PathBuf::from("woz.rom"),
+            })
+        );
+        assert_eq!(session.programs[0].address, 0x0800);
+        assert_eq!(session.breakpoints[1].address, 0xD020);
+        assert!(!session.breakpoints[1].enabled);
+
+        let saved = toml::to_string(&session).unwrap();
+        assert!(saved.contains("address = \"$0810\""), "{}", saved);
+        assert_eq!(toml::from_str::<Session>(&saved).unwrap(), session);
+    }
+
+    #[test]
+    fn test_save_and_load_relative_paths() {
+        // 현재 디렉터리 기준 상대 경로로 적재한 파일
+        let dir = PathBuf::from(format!("session-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("data")).unwrap();
+        for file in ["game.bin", "data/game.lbl", "game.state", "data/board.toml"] {
+            std::fs::write(dir.join(file), [0]).unwrap();
+        }
+        let session = Session {
+            machine: Some(SessionMachine::Description(dir.join("data/board.toml"))),
+            programs: vec![ProgramImage {
+                path: dir.join("game.bin"),
+                address: 0x0800,
+            }],
+            symbol_files: vec![dir.join("data/game.lbl")],
+            state: Some(dir.join("game.state")),
+            ..Session::new()
+        };
+        let path = dir.join("game.toml");
+        session.save(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+
+        // 다시 읽고 저장해도 경로가 겹쳐 붙지 않음
+        let loaded = Session::load(&path).unwrap();
+        loaded.save(&path).unwrap();
+        let resaved = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(saved.contains("path = \"game.bin\""), "{}", saved);
+        assert!(saved.contains("\"data/game.lbl\""), "{}", saved);
+        assert!(saved.contains("state = \"game.state\""), "{}", saved);
+        assert!(
+            saved.contains("description = \"data/board.toml\""),
+            "{}",
+            saved
+        );
+        assert_eq!(loaded, session);
+        assert_eq!(resaved, saved);
+    }
+
+    #[test]
+    fn test_save_state_bytes() {
+        let mut memory = vec![0; 0x10000];
+        memory[0x1234] = 0x56;
+        let state = SaveState {
+            a: 1,
+            x: 2,
+            y: 3,
+            s: 0xFD,
+            p: 0x24,
+            pc: 0xC000,
+            memory,
+        };
+        let bytes = state.to_bytes();
+        assert_eq!(SaveState::from_bytes(&bytes).unwrap(), state);
+        assert!(SaveState::from_bytes(&bytes[1..]).is_err());
+    }
+}
@@ -1,29 +1,57 @@
-use common::Result;
+use common::{Result, SymbolTable};
 use types::{Instruction, OPCODE_MAP};
 
 use crate::formatter::InstructionFormatter;
 use crate::instruction::DInstruction;
 
-pub struct Disassembler {
+pub struct Disassembler<'a> {
     memory: Vec<u8>,
-    pc: u16,
+    // 메모리 첫 바이트의 실제 주소
+    origin: u16,
+    // 메모리 내 현재 위치
+    pc: usize,
+    // 오퍼랜드 주소를 이름으로 표시할 심볼
+    symbols: Option<&'a SymbolTable>,
+    // 주소가 명령어 시작인지 판단 (커버리지 등) - 아니면 데이터 바이트로 표시
+    code_map: Option<Box<dyn Fn(u16) -> bool + 'a>>,
 }
 
-impl Default for Disassembler {
+impl Default for Disassembler<'_> {
     fn default() -> Self {
         Self::new(vec![])
     }
 }
 
-impl Disassembler {
+impl<'a> Disassembler<'a> {
     pub fn new(memory: Vec<u8>) -> Self {
-        Self { memory, pc: 0 }
+        Self::with_origin(memory, 0)
+    }
+
+    /// `origin` 주소에 위치한 메모리 조각 디스어셈블러
+    pub fn with_origin(memory: Vec<u8>, origin: u16) -> Self {
+        Self {
+            memory,
+            origin,
+            pc: 0,
+            symbols: None,
+            code_map: None,
+        }
+    }
+
+    /// 오퍼랜드 주소가 심볼과 일치하면 `JSR PRINT_STRING`처럼 이름으로 표시
+    pub fn set_symbols(&mut self, symbols: &'a SymbolTable) {
+        self.symbols = Some(symbols);
+    }
+
+    /// 코드로 알려진 주소만 명령어로 해석하고 나머지는 `.BYTE`로 표시
+    pub fn set_code_map(&mut self, is_code: impl Fn(u16) -> bool + 'a) {
+        self.code_map = Some(Box::new(is_code));
     }
 
     pub fn disassemble(&mut self) -> Result<Vec<DInstruction>> {
         let mut instructions = Vec::<DInstruction>::new();
-        while self.pc < self.memory.len() as u16 {
-            let opcode = self.memory[self.pc as usize];
+        while self.pc < self.memory.len() {
+            let opcode = self.memory[self.pc];
             let instruction = self.decode_instruction(opcode)?;
             instructions.push(instruction);
         }
@@ -31,8 +59,22 @@ impl Disassembler {
     }
 
     fn decode_instruction(&mut self, opcode: u8) -> Result<DInstruction> {
-        let address = self.pc;
-        let inst_info = OPCODE_MAP.get(&opcode).unwrap();
+        let address = self.origin.wrapping_add(self.pc as u16);
+        let is_code = self
+            .code_map
+            .as_ref()
+            .is_none_or(|is_code| is_code(address));
+        let Some(inst_info) = OPCODE_MAP.get(&opcode).filter(|_| is_code) else {
+            // 정의되지 않은 옵코드나 코드가 아닌 주소는 데이터 바이트로 표시
+            self.pc += 1;
+            return Ok(DInstruction::new(
+                opcode,
+                Vec::new(),
+                format!(".BYTE ${:02X}", opcode),
+                format!("{:02X}", opcode),
+                address,
+            ));
+        };
         // 명령어 기본 이름 추출 (LDA, LDX, INX 등)
         let base_mnemonic = match inst_info.instruction {
             Instruction::LDA(_) => "LDA",
@@ -100,22 +142,26 @@ impl Disassembler {
         let operand_size = inst_info.get_operand_size();
 
         // 오퍼랜드 가져오기
-        let operand =
-            if operand_size > 0 && (self.pc + operand_size as u16) <= self.memory.len() as u16 {
-                self.memory[self.pc as usize..(self.pc as usize + operand_size as usize)].to_vec()
-            } else {
-                Vec::new()
-            };
+        let operand_end = self.pc + operand_size as usize;
+        let operand = if operand_size > 0 && operand_end <= self.memory.len() {
+            self.memory[self.pc..operand_end].to_vec()
+        } else {
+            Vec::new()
+        };
 
         let formatted_mnemonic = InstructionFormatter::format_mnemonic(
             base_mnemonic,
             &inst_info.instruction,
             &operand,
-            self.pc,
+            address.wrapping_add(1),
         );
+        let formatted_mnemonic = match self.symbols {
+            Some(symbols) => symbolize(formatted_mnemonic, symbols),
+            None => formatted_mnemonic,
+        };
 
         // PC 증가 (오퍼랜드 다음으로)
-        self.pc += operand_size as u16;
+        self.pc += operand_size as usize;
 
         let mut base_instruction = Vec::<u8>::new();
         base_instruction.push(opcode);
@@ -138,6 +184,31 @@ impl Disassembler {
     }
 }
 
+// 즉시값이 아닌 `$XXXX`/`$XX` 오퍼랜드를 심볼 이름으로 치환
+fn symbolize(mnemonic: String, symbols: &SymbolTable) -> String {
+    if mnemonic.contains('#') {
+        return mnemonic;
+    }
+    let Some(start) = mnemonic.find('$') else {
+        return mnemonic;
+    };
+    let digits = mnemonic[start + 1..]
+        .chars()
+        .take_while(char::is_ascii_hexdigit)
+        .count();
+    let text = &mnemonic[start..start + 1 + digits];
+    let name = match digits {
+        2 | 4 => u16::from_str_radix(&text[1..], 16)
+            .ok()
+            .and_then(|address| symbols.name_at(address)),
+        _ => None,
+    };
+    match name {
+        Some(name) => mnemonic.replacen(text, name, 1),
+        None => mnemonic,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +239,37 @@ mod tests {
         let bne_inst = instructions.iter().find(|i| i.opcode == 0xd0).unwrap();
         assert!(bne_inst.mnemonic.starts_with("BNE $"));
     }
+
+    #[test]
+    fn test_disassemble_with_origin() {
+        // BNE 이후 정의되지 않은 옵코드 $02
+        let memory = vec![0xd0, 0xfe, 0x02, 0x4c, 0x00, 0x02];
+        let mut disassembler = Disassembler::with_origin(memory, 0x0200);
+        let instructions = disassembler.disassemble().unwrap();
+
+        assert_eq!(instructions[0].address, 0x0200);
+        assert_eq!(instructions[0].mnemonic, "BNE $0200");
+        assert_eq!(instructions[1].mnemonic, ".BYTE $02");
+        assert_eq!(instructions[2].address, 0x0203);
+        assert_eq!(instructions[2].mnemonic, "JMP $0200");
+    }
+
+    #[test]
+    fn test_disassemble_with_symbols() {
+        let mut symbols = SymbolTable::new();
+        symbols.insert("PRINT_STRING", 0xC0A3);
+        symbols.insert("PTR", 0x00FB);
+        symbols.insert("START", 0x0200);
+
+        // JSR $C0A3, LDA ($FB),Y, LDX #$FB, BNE $0200
+        let memory = vec![0x20, 0xa3, 0xc0, 0xb1, 0xfb, 0xa2, 0xfb, 0xd0, 0xf7];
+        let mut disassembler = Disassembler::with_origin(memory, 0x0200);
+        disassembler.set_symbols(&symbols);
+        let instructions = disassembler.disassemble().unwrap();
+
+        assert_eq!(instructions[0].mnemonic, "JSR PRINT_STRING");
+        assert_eq!(instructions[1].mnemonic, "LDA (PTR),Y");
+        assert_eq!(instructions[2].mnemonic, "LDX #$FB");
+        assert_eq!(instructions[3].mnemonic, "BNE START");
+    }
 }
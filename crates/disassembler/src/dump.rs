@@ -0,0 +1,79 @@
+//! 디스어셈블 덤프 - 명령어마다 바이트와 니모닉을 한 줄로
+use common::SymbolTable;
+use common::dump::{DumpRenderer, DumpRow};
+
+use crate::disasm::Disassembler;
+
+/// DumpRenderer로 쓰는 디스어셈블러 - 줄마다 명령어 하나
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DisassemblyDump<'a> {
+    symbols: Option<&'a SymbolTable>,
+}
+
+impl<'a> DisassemblyDump<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 오퍼랜드 주소를 심볼 이름으로 표시
+    pub fn with_symbols(mut self, symbols: &'a SymbolTable) -> Self {
+        self.symbols = Some(symbols);
+        self
+    }
+}
+
+impl DumpRenderer for DisassemblyDump<'_> {
+    fn rows(&self, address: u16, data: &[u8]) -> Vec<DumpRow> {
+        let mut disassembler = Disassembler::with_origin(data.to_vec(), address);
+        if let Some(symbols) = self.symbols {
+            disassembler.set_symbols(symbols);
+        }
+        // 정의되지 않은 옵코드도 .BYTE로 나오므로 실패하지 않음
+        disassembler
+            .disassemble()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|instruction| {
+                let bytes: Vec<u8> = std::iter::once(instruction.opcode)
+                    .chain(instruction.operand)
+                    .collect();
+                DumpRow {
+                    address: instruction.address,
+                    cells: bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+                    bytes,
+                    text: instruction.mnemonic,
+                }
+            })
+            .collect()
+    }
+
+    fn format_row(&self, row: &DumpRow) -> String {
+        format!(
+            "{:04x}: {:<9} {}",
+            row.address,
+            row.cells.join(" "),
+            row.text
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassembly_rows() {
+        let mut symbols = SymbolTable::new();
+        symbols.insert("CHROUT", 0xFFD2);
+        let dump = DisassemblyDump::new().with_symbols(&symbols);
+        let program = [0xA9, 0x41, 0x20, 0xD2, 0xFF, 0x60];
+
+        let rows = dump.rows(0xC000, &program);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].bytes, vec![0x20, 0xD2, 0xFF]);
+        assert_eq!(
+            dump.render(0xC000, &program),
+            "c000: a9 41     LDA #$41\nc002: 20 d2 ff  JSR CHROUT\nc005: 60        RTS\n"
+        );
+    }
+}
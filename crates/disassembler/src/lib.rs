@@ -1,6 +1,8 @@
 pub mod disasm;
+pub mod dump;
 pub mod formatter;
 pub mod instruction;
 
 pub use disasm::Disassembler;
+pub use dump::DisassemblyDump;
 pub use instruction::DInstruction;
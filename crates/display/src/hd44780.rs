@@ -0,0 +1,392 @@
+//! HD44780 문자 LCD 컨트롤러
+//! 8비트/4비트 인터페이스, DDRAM/CGRAM, 엔트리 모드, 표시 이동 - 명령은 즉시 끝나 busy 플래그는 항상 0
+use common::Result;
+use common::device::Device;
+use error::Error;
+
+/// RS 선택 - 오프셋 비트 0 (버스에 직접 연결할 때 A0를 RS로)
+pub const INSTRUCTION: u16 = 0;
+pub const DATA: u16 = 1;
+
+/// 한 줄의 DDRAM 길이 (2줄 모드)
+const LINE_LENGTH: u8 = 40;
+const SECOND_LINE: u8 = 0x40;
+
+/// HD44780 - 열/행 수는 표시 배치에만 사용 (16x2, 20x4 등)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hd44780 {
+    columns: u8,
+    rows: u8,
+    ddram: [u8; 128],
+    cgram: [u8; 64],
+    /// 주소 카운터
+    address: u8,
+    /// 주소 카운터가 CGRAM을 가리킴
+    cgram_selected: bool,
+    increment: bool,
+    shift_on_write: bool,
+    display_on: bool,
+    cursor_on: bool,
+    blink_on: bool,
+    two_lines: bool,
+    eight_bit: bool,
+    /// 표시 시작 위치 (한 줄 길이 안에서 순환)
+    shift: u8,
+    /// 4비트 모드에서 먼저 받은 상위 니블
+    write_high: Option<u8>,
+    /// 4비트 모드에서 아직 읽지 않은 하위 니블
+    read_low: Option<u8>,
+}
+
+impl Default for Hd44780 {
+    fn default() -> Self {
+        Self::new(16, 2)
+    }
+}
+
+impl Hd44780 {
+    /// 전원 투입 상태 - 8비트, 1줄, 표시 꺼짐
+    pub fn new(columns: u8, rows: u8) -> Self {
+        Self {
+            columns: columns.clamp(1, LINE_LENGTH),
+            rows: rows.clamp(1, 4),
+            ddram: [b' '; 128],
+            cgram: [0; 64],
+            address: 0,
+            cgram_selected: false,
+            increment: true,
+            shift_on_write: false,
+            display_on: false,
+            cursor_on: false,
+            blink_on: false,
+            two_lines: false,
+            eight_bit: true,
+            shift: 0,
+            write_high: None,
+            read_low: None,
+        }
+    }
+
+    pub fn is_display_on(&self) -> bool {
+        self.display_on
+    }
+
+    /// (커서, 깜빡임) 표시 여부
+    pub fn cursor(&self) -> (bool, bool) {
+        (self.cursor_on, self.blink_on)
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// 화면에 보이는 행 - 표시가 꺼져 있으면 공백, CGRAM 문자와 ASCII 밖의 문자는 '?'
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.rows)
+            .map(|row| {
+                (0..self.columns)
+                    .map(|column| match self.display_on {
+                        true => {
+                            Self::decode(self.ddram[self.visible_address(row, column) as usize])
+                        }
+                        false => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // 4줄 표시는 3, 4번째 줄이 1, 2번째 줄 DDRAM에 이어짐
+    fn visible_address(&self, row: u8, column: u8) -> u8 {
+        if !self.two_lines {
+            return (row * self.columns + column + self.shift) % (LINE_LENGTH * 2);
+        }
+        let line = if row.is_multiple_of(2) {
+            0
+        } else {
+            SECOND_LINE
+        };
+        let start = (row / 2) * self.columns;
+        line + (start + column + self.shift) % LINE_LENGTH
+    }
+
+    fn decode(value: u8) -> char {
+        match value {
+            0x20..=0x7D => value as char,
+            _ => '?',
+        }
+    }
+
+    fn advance(&mut self) {
+        if self.cgram_selected {
+            self.address = match self.increment {
+                true => self.address.wrapping_add(1),
+                false => self.address.wrapping_sub(1),
+            } & 0x3F;
+            return;
+        }
+        self.address = match (self.two_lines, self.increment) {
+            (true, true) => match self.address {
+                0x27 => SECOND_LINE,
+                0x67 => 0x00,
+                address => address + 1,
+            },
+            (true, false) => match self.address {
+                0x00 => 0x67,
+                SECOND_LINE => 0x27,
+                address => address - 1,
+            },
+            (false, true) => (self.address + 1) % (LINE_LENGTH * 2),
+            (false, false) => (self.address + LINE_LENGTH * 2 - 1) % (LINE_LENGTH * 2),
+        };
+    }
+
+    fn shift_display(&mut self, right: bool) {
+        let length = if self.two_lines {
+            LINE_LENGTH
+        } else {
+            LINE_LENGTH * 2
+        };
+        self.shift = match right {
+            true => (self.shift + length - 1) % length,
+            false => (self.shift + 1) % length,
+        };
+    }
+
+    fn instruction(&mut self, value: u8) {
+        match value.leading_zeros() {
+            0 => {
+                self.address = value & 0x7F;
+                self.cgram_selected = false;
+            }
+            1 => {
+                self.address = value & 0x3F;
+                self.cgram_selected = true;
+            }
+            2 => {
+                self.eight_bit = value & 0x10 != 0;
+                self.two_lines = value & 0x08 != 0;
+                self.write_high = None;
+                self.read_low = None;
+            }
+            3 => {
+                let right = value & 0x04 != 0;
+                if value & 0x08 != 0 {
+                    self.shift_display(right);
+                } else {
+                    let increment = self.increment;
+                    self.increment = right;
+                    self.advance();
+                    self.increment = increment;
+                }
+            }
+            4 => {
+                self.display_on = value & 0x04 != 0;
+                self.cursor_on = value & 0x02 != 0;
+                self.blink_on = value & 0x01 != 0;
+            }
+            5 => {
+                self.increment = value & 0x02 != 0;
+                self.shift_on_write = value & 0x01 != 0;
+            }
+            6 => {
+                self.address = 0;
+                self.cgram_selected = false;
+                self.shift = 0;
+            }
+            7 => {
+                self.ddram = [b' '; 128];
+                self.address = 0;
+                self.cgram_selected = false;
+                self.increment = true;
+                self.shift = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn write_data(&mut self, value: u8) {
+        if self.cgram_selected {
+            self.cgram[self.address as usize] = value;
+        } else {
+            self.ddram[self.address as usize] = value;
+            if self.shift_on_write {
+                self.shift_display(!self.increment);
+            }
+        }
+        self.advance();
+    }
+
+    fn register(&self, offset: u16) -> u8 {
+        match offset & 0x01 {
+            // busy 플래그(비트 7)는 항상 0
+            INSTRUCTION => self.address & 0x7F,
+            _ if self.cgram_selected => self.cgram[self.address as usize],
+            _ => self.ddram[self.address as usize],
+        }
+    }
+}
+
+impl Device for Hd44780 {
+    fn peek(&self, offset: u16) -> u8 {
+        match (self.eight_bit, self.read_low) {
+            (true, _) => self.register(offset),
+            (false, Some(low)) => low,
+            (false, None) => self.register(offset) & 0xF0,
+        }
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        if !self.eight_bit
+            && let Some(low) = self.read_low.take()
+        {
+            return low;
+        }
+        let value = self.register(offset);
+        if offset & 0x01 == DATA {
+            self.advance();
+        }
+        if self.eight_bit {
+            return value;
+        }
+        self.read_low = Some(value << 4);
+        value & 0xF0
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        let value = match self.eight_bit {
+            true => value,
+            // 4비트 모드는 D7-D4로 상위 니블, 하위 니블 순서
+            false => match self.write_high.take() {
+                None => {
+                    self.write_high = Some(value & 0xF0);
+                    return;
+                }
+                Some(high) => high | value >> 4,
+            },
+        };
+        match offset & 0x01 {
+            INSTRUCTION => self.instruction(value),
+            _ => self.write_data(value),
+        }
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = Vec::with_capacity(self.ddram.len() + self.cgram.len() + 8);
+        state.extend_from_slice(&self.ddram);
+        state.extend_from_slice(&self.cgram);
+        state.push(self.address);
+        state.push(self.shift);
+        state.push(
+            [
+                self.cgram_selected,
+                self.increment,
+                self.shift_on_write,
+                self.display_on,
+                self.cursor_on,
+                self.blink_on,
+                self.two_lines,
+                self.eight_bit,
+            ]
+            .iter()
+            .enumerate()
+            .fold(0, |flags, (bit, &on)| flags | (on as u8) << bit),
+        );
+        for nibble in [self.write_high, self.read_low] {
+            state.extend_from_slice(&[nibble.is_some() as u8, nibble.unwrap_or(0)]);
+        }
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        if state.len() != 128 + 64 + 7 {
+            return Err(Error::InvalidSaveState);
+        }
+        let (ddram, rest) = state.split_at(128);
+        let (cgram, rest) = rest.split_at(64);
+        self.ddram.copy_from_slice(ddram);
+        self.cgram.copy_from_slice(cgram);
+        self.address = rest[0];
+        self.shift = rest[1];
+        let flag = |bit: u8| rest[2] >> bit & 1 != 0;
+        self.cgram_selected = flag(0);
+        self.increment = flag(1);
+        self.shift_on_write = flag(2);
+        self.display_on = flag(3);
+        self.cursor_on = flag(4);
+        self.blink_on = flag(5);
+        self.two_lines = flag(6);
+        self.eight_bit = flag(7);
+        self.write_high = (rest[3] != 0).then_some(rest[4]);
+        self.read_low = (rest[5] != 0).then_some(rest[6]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_text(lcd: &mut Hd44780, text: &str) {
+        for byte in text.bytes() {
+            lcd.write(DATA, byte);
+        }
+    }
+
+    #[test]
+    fn test_eight_bit_two_lines() {
+        let mut lcd = Hd44780::new(16, 2);
+        // 8비트 2줄, 표시 켬, 증가 모드, 지우기
+        for instruction in [0x38, 0x0E, 0x06, 0x01] {
+            lcd.write(INSTRUCTION, instruction);
+        }
+        write_text(&mut lcd, "Hello, world!");
+        lcd.write(INSTRUCTION, 0x80 | 0x40);
+        write_text(&mut lcd, "6502");
+        assert_eq!(lcd.lines(), ["Hello, world!   ", "6502            "]);
+        assert_eq!(lcd.read(INSTRUCTION), 0x44, "ready, address counter");
+        assert_eq!(lcd.cursor(), (true, false));
+
+        // 표시를 왼쪽으로 이동
+        lcd.write(INSTRUCTION, 0x18);
+        assert_eq!(lcd.lines()[0], "ello, world!    ");
+
+        lcd.write(INSTRUCTION, 0x80);
+        assert_eq!(lcd.read(DATA), b'H');
+        assert_eq!(lcd.read(DATA), b'e', "address advanced by read");
+    }
+
+    #[test]
+    fn test_four_bit_interface() {
+        let mut lcd = Hd44780::new(16, 2);
+        // 8비트 상태에서 4비트로 전환, 이후 니블 두 번씩
+        lcd.write(INSTRUCTION, 0x20);
+        for instruction in [0x28u8, 0x0C, 0x06, 0x01] {
+            lcd.write(INSTRUCTION, instruction & 0xF0);
+            lcd.write(INSTRUCTION, instruction << 4);
+        }
+        for byte in *b"4-bit" {
+            lcd.write(DATA, byte & 0xF0);
+            lcd.write(DATA, byte << 4);
+        }
+        assert_eq!(lcd.lines()[0], "4-bit           ");
+
+        let high = lcd.read(INSTRUCTION);
+        let low = lcd.read(INSTRUCTION);
+        assert_eq!(high | low >> 4, 0x05);
+    }
+
+    #[test]
+    fn test_save_state() {
+        let mut lcd = Hd44780::new(20, 4);
+        lcd.write(INSTRUCTION, 0x3C);
+        lcd.write(INSTRUCTION, 0x0F);
+        write_text(&mut lcd, "RIOT");
+        let state = lcd.save_state();
+
+        let mut restored = Hd44780::new(20, 4);
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored, lcd);
+        assert!(restored.load_state(&state[1..]).is_err());
+    }
+}
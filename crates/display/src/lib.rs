@@ -1,14 +1,5 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+//! 표시 장치 모델 - 문자 LCD 등
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub mod hd44780;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use hd44780::Hd44780;
@@ -17,10 +17,20 @@ pub enum Error {
     // -- Memory errors
     InvalidMemoryAddress(u16),
     InvalidMemoryRange(u16),
+    /// 보호된 영역 쓰기 - CPU가 디버거 이벤트로 바꿔 전달
+    WriteProtected(u16),
     // -- Breadboard errors
     DuplicateComponentId(String),
     ComponentNotFound(String),
     FailedToLockComponent(String),
+    UnknownMachine(String),
+    /// ROM 이미지가 머신의 ROM 창보다 큼
+    RomTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    /// 머신 기술 파일 오류 (문법, 겹치는 장치 이름 등)
+    InvalidMachine(String),
     // -- Assembler errors
     InvalidNumber {
         line: usize,
@@ -51,6 +61,20 @@ pub enum Error {
     DebuggerNotAttached,
     DebuggerNoCPU,
 
+    // -- Symbol errors
+    InvalidSymbolFile {
+        line: usize,
+    },
+    UnknownSymbol(String),
+
+    // -- Script errors
+    Script(String),
+
+    // -- Session errors
+    InvalidSession(String),
+    InvalidSaveState,
+    InvalidRecording,
+
     #[from]
     Io(io::Error),
     // -- External errors
@@ -1,9 +1,17 @@
 //! Memory 모듈
 //! 6502 CPU의 메모리 기능을 구현한 모듈
 
+pub mod mapper;
+pub mod mappers;
 pub mod memory;
+pub mod memory_map;
+pub mod snapshot;
 
-pub use memory::Memory;
+pub use mapper::{Bank, BankedMemory, Mapper};
+pub use mappers::{BankedCartridge, C64Pla, Mmc1, UxRom};
+pub use memory::{Memory, WritePolicy};
+pub use memory_map::{InterruptLine, Mapping, MemoryMap, OpenBus, SharedDevice};
+pub use snapshot::{Change, MemoryDiff, MemorySnapshot};
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
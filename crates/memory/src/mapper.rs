@@ -0,0 +1,220 @@
+//! 뱅크 전환 - 매퍼가 CPU 주소를 64KB보다 큰 ROM/RAM의 물리 위치로 변환
+use common::Result;
+use common::device::Device;
+use common::memory_bus::MemoryBus;
+use error::Error;
+use std::fmt::Debug;
+
+/// CPU 주소가 가리키는 물리 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bank {
+    /// ROM 내 오프셋
+    Rom(usize),
+    /// RAM 내 오프셋
+    Ram(usize),
+    /// 연결 없음 - 읽으면 $FF, 쓰기는 무시
+    None,
+}
+
+/// 뱅크 전환 로직 - 주소는 CPU 주소
+pub trait Mapper: Debug + Send {
+    /// 읽기 위치
+    fn read_bank(&self, address: u16) -> Bank;
+
+    /// 쓰기 위치 - 기본은 ROM 쓰기 무시
+    fn write_bank(&self, address: u16) -> Bank {
+        match self.read_bank(address) {
+            Bank::Rom(_) => Bank::None,
+            bank => bank,
+        }
+    }
+
+    /// 제어 레지스터 쓰기 - 레지스터에서 처리했으면 true (메모리에는 쓰지 않음)
+    fn write_register(&mut self, address: u16, value: u8) -> bool;
+
+    /// 제어 레지스터 읽기 - 레지스터가 아니면 None
+    fn read_register(&self, _address: u16) -> Option<u8> {
+        None
+    }
+
+    /// 세이브 스테이트용 레지스터 값 - 저장할 것이 없으면 비어 있음
+    fn save_state(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// save_state로 저장한 레지스터 복원
+    fn load_state(&mut self, _state: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 매퍼와 ROM/RAM 저장소
+/// 버스에 직접 연결하거나 주소 디코더에 장치로 매핑 (base: 장치 오프셋 0의 CPU 주소)
+#[derive(Debug)]
+pub struct BankedMemory<M: Mapper> {
+    base: u16,
+    mapper: M,
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+}
+
+impl<M: Mapper> BankedMemory<M> {
+    pub fn new(mapper: M, rom: Vec<u8>, ram_size: usize) -> Self {
+        Self {
+            base: 0,
+            mapper,
+            rom,
+            ram: vec![0; ram_size],
+        }
+    }
+
+    /// 주소 디코더에 $0000이 아닌 곳부터 매핑할 때 시작 주소 지정
+    pub fn with_base(mut self, base: u16) -> Self {
+        self.base = base;
+        self
+    }
+
+    pub fn mapper(&self) -> &M {
+        &self.mapper
+    }
+
+    pub fn mapper_mut(&mut self) -> &mut M {
+        &mut self.mapper
+    }
+
+    pub fn rom(&self) -> &[u8] {
+        &self.rom
+    }
+
+    pub fn ram(&self) -> &[u8] {
+        &self.ram
+    }
+
+    pub fn ram_mut(&mut self) -> &mut [u8] {
+        &mut self.ram
+    }
+
+    /// CPU 주소 읽기
+    pub fn read_at(&self, address: u16) -> u8 {
+        if let Some(value) = self.mapper.read_register(address) {
+            return value;
+        }
+        match self.mapper.read_bank(address) {
+            Bank::Rom(offset) => mirrored(&self.rom, offset),
+            Bank::Ram(offset) => mirrored(&self.ram, offset),
+            Bank::None => 0xFF,
+        }
+    }
+
+    /// CPU 주소 쓰기
+    pub fn write_at(&mut self, address: u16, value: u8) {
+        if self.mapper.write_register(address, value) {
+            return;
+        }
+        if let Bank::Ram(offset) = self.mapper.write_bank(address)
+            && !self.ram.is_empty()
+        {
+            let len = self.ram.len();
+            self.ram[offset % len] = value;
+        }
+    }
+}
+
+// 저장소보다 큰 오프셋은 반복 (작은 ROM을 큰 창에 넣은 경우)
+fn mirrored(data: &[u8], offset: usize) -> u8 {
+    if data.is_empty() {
+        0xFF
+    } else {
+        data[offset % data.len()]
+    }
+}
+
+impl<M: Mapper> Device for BankedMemory<M> {
+    fn peek(&self, offset: u16) -> u8 {
+        self.read_at(self.base.wrapping_add(offset))
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        self.write_at(self.base.wrapping_add(offset), value);
+    }
+
+    /// 매퍼 상태 길이(u16), 매퍼 상태, RAM - ROM은 바뀌지 않으므로 제외
+    fn save_state(&self) -> Vec<u8> {
+        let mapper = self.mapper.save_state();
+        let mut state = (mapper.len() as u16).to_le_bytes().to_vec();
+        state.extend_from_slice(&mapper);
+        state.extend_from_slice(&self.ram);
+        state
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let (len, rest) = state.split_at_checked(2).ok_or(Error::InvalidSaveState)?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        let (mapper, ram) = rest.split_at_checked(len).ok_or(Error::InvalidSaveState)?;
+        if ram.len() != self.ram.len() {
+            return Err(Error::InvalidSaveState);
+        }
+        self.mapper.load_state(mapper)?;
+        self.ram.copy_from_slice(ram);
+        Ok(())
+    }
+}
+
+impl<M: Mapper + Sync> MemoryBus for BankedMemory<M> {
+    fn peek(&self, address: u16) -> u8 {
+        self.read_at(address)
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.write_at(address, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryMap;
+    use std::sync::{Arc, Mutex};
+
+    /// $8000-$80FF: 256바이트 ROM 뱅크, $80FF 쓰기로 전환, 나머지는 RAM
+    #[derive(Debug, Default)]
+    struct PageMapper {
+        bank: usize,
+    }
+
+    impl Mapper for PageMapper {
+        fn read_bank(&self, address: u16) -> Bank {
+            match address {
+                0x8000..=0x80FF => Bank::Rom(self.bank * 0x100 + (address as usize & 0xFF)),
+                _ => Bank::Ram(address as usize),
+            }
+        }
+
+        fn write_register(&mut self, address: u16, value: u8) -> bool {
+            if address == 0x80FF {
+                self.bank = value as usize;
+                return true;
+            }
+            false
+        }
+    }
+
+    #[test]
+    fn test_banked_device() {
+        let rom: Vec<u8> = (0..4).flat_map(|bank| vec![bank as u8; 0x100]).collect();
+        let banked = BankedMemory::new(PageMapper::default(), rom, 0x10000).with_base(0x8000);
+        let banked = Arc::new(Mutex::new(banked));
+        let mut map = MemoryMap::new();
+        map.map("cart", 0x8000..=0x80FF, banked.clone()).unwrap();
+
+        assert_eq!(map.read(0x8010), 0);
+        map.write(0x80FF, 2);
+        assert_eq!(map.read(0x8010), 2);
+        // 뱅크 수보다 큰 번호는 반복
+        map.write(0x80FF, 5);
+        assert_eq!(map.read(0x8010), 1);
+        map.write(0x8010, 0x42);
+        assert_eq!(map.read(0x8010), 1, "ROM write ignored");
+        assert_eq!(banked.lock().unwrap().mapper().bank, 5);
+    }
+}
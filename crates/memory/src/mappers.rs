@@ -0,0 +1,488 @@
+//! 참조용 매퍼 - 16KB 뱅크 카트리지, NES UxROM/MMC1, C64 PLA
+use crate::mapper::{Bank, Mapper};
+use common::Result;
+use error::Error;
+
+const BANK_16K: usize = 0x4000;
+
+/// 16KB 창 하나를 전환하는 카트리지 - 레지스터에 뱅크 번호를 씀
+/// 예: C64 카트리지는 창 $8000, 레지스터 $DE00
+#[derive(Debug, Clone)]
+pub struct BankedCartridge {
+    window: u16,
+    register: u16,
+    bank: u8,
+}
+
+impl BankedCartridge {
+    pub fn new(window: u16, register: u16) -> Self {
+        Self {
+            window,
+            register,
+            bank: 0,
+        }
+    }
+
+    pub fn bank(&self) -> u8 {
+        self.bank
+    }
+}
+
+impl Mapper for BankedCartridge {
+    fn read_bank(&self, address: u16) -> Bank {
+        let offset = address.wrapping_sub(self.window) as usize;
+        if offset < BANK_16K {
+            Bank::Rom(self.bank as usize * BANK_16K + offset)
+        } else {
+            Bank::None
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) -> bool {
+        if address == self.register {
+            self.bank = value;
+            return true;
+        }
+        false
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.bank]
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let &[bank] = state else {
+            return Err(Error::InvalidSaveState);
+        };
+        self.bank = bank;
+        Ok(())
+    }
+}
+
+/// NES UxROM - $8000-$BFFF 전환, $C000-$FFFF 마지막 뱅크 고정
+#[derive(Debug, Clone)]
+pub struct UxRom {
+    banks: usize,
+    bank: usize,
+}
+
+impl UxRom {
+    /// rom_size: PRG ROM 크기 (16KB 단위)
+    pub fn new(rom_size: usize) -> Self {
+        Self {
+            banks: (rom_size / BANK_16K).max(1),
+            bank: 0,
+        }
+    }
+
+    pub fn bank(&self) -> usize {
+        self.bank
+    }
+}
+
+impl Mapper for UxRom {
+    fn read_bank(&self, address: u16) -> Bank {
+        let offset = address as usize & (BANK_16K - 1);
+        match address {
+            0x8000..=0xBFFF => Bank::Rom(self.bank * BANK_16K + offset),
+            0xC000..=0xFFFF => Bank::Rom((self.banks - 1) * BANK_16K + offset),
+            _ => Bank::None,
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) -> bool {
+        if address >= 0x8000 {
+            self.bank = value as usize % self.banks;
+            return true;
+        }
+        false
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.bank as u8]
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let &[bank] = state else {
+            return Err(Error::InvalidSaveState);
+        };
+        self.bank = bank as usize % self.banks;
+        Ok(())
+    }
+}
+
+/// NES MMC1 - 5번 쓰기로 채우는 직렬 레지스터, $6000-$7FFF PRG RAM
+#[derive(Debug, Clone)]
+pub struct Mmc1 {
+    banks: usize,
+    shift: u8,
+    count: u8,
+    control: u8,
+    chr: [u8; 2],
+    prg: u8,
+}
+
+impl Mmc1 {
+    /// rom_size: PRG ROM 크기 (16KB 단위), RAM은 8KB 사용
+    pub fn new(rom_size: usize) -> Self {
+        Self {
+            banks: (rom_size / BANK_16K).max(1),
+            shift: 0,
+            count: 0,
+            // 전원 투입 시 마지막 뱅크 고정 모드
+            control: 0x0C,
+            chr: [0; 2],
+            prg: 0,
+        }
+    }
+
+    pub fn control(&self) -> u8 {
+        self.control
+    }
+
+    pub fn chr_banks(&self) -> [u8; 2] {
+        self.chr
+    }
+
+    pub fn prg_bank(&self) -> u8 {
+        self.prg
+    }
+
+    fn prg_bank_at(&self, address: u16) -> usize {
+        let prg = (self.prg & 0x0F) as usize;
+        let high = address >= 0xC000;
+        match (self.control >> 2) & 0x03 {
+            // 32KB 전환 - 하위 비트 무시
+            0 | 1 => (prg & !1) + high as usize,
+            // $8000 첫 뱅크 고정
+            2 => {
+                if high {
+                    prg
+                } else {
+                    0
+                }
+            }
+            // $C000 마지막 뱅크 고정
+            _ => {
+                if high {
+                    self.banks - 1
+                } else {
+                    prg
+                }
+            }
+        }
+    }
+}
+
+impl Mapper for Mmc1 {
+    fn read_bank(&self, address: u16) -> Bank {
+        match address {
+            0x6000..=0x7FFF => Bank::Ram(address as usize - 0x6000),
+            0x8000..=0xFFFF => {
+                let bank = self.prg_bank_at(address) % self.banks;
+                Bank::Rom(bank * BANK_16K + (address as usize & (BANK_16K - 1)))
+            }
+            _ => Bank::None,
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) -> bool {
+        if address < 0x8000 {
+            return false;
+        }
+        if value & 0x80 != 0 {
+            self.shift = 0;
+            self.count = 0;
+            self.control |= 0x0C;
+            return true;
+        }
+        self.shift |= (value & 1) << self.count;
+        self.count += 1;
+        if self.count == 5 {
+            match (address >> 13) & 0x03 {
+                0 => self.control = self.shift,
+                1 => self.chr[0] = self.shift,
+                2 => self.chr[1] = self.shift,
+                _ => self.prg = self.shift,
+            }
+            self.shift = 0;
+            self.count = 0;
+        }
+        true
+    }
+
+    /// 직렬 레지스터(값, 비트 수), 제어, CHR 뱅크 2개, PRG 뱅크
+    fn save_state(&self) -> Vec<u8> {
+        vec![
+            self.shift,
+            self.count,
+            self.control,
+            self.chr[0],
+            self.chr[1],
+            self.prg,
+        ]
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let &[shift, count, control, chr0, chr1, prg] = state else {
+            return Err(Error::InvalidSaveState);
+        };
+        if count >= 5 {
+            return Err(Error::InvalidSaveState);
+        }
+        self.shift = shift;
+        self.count = count;
+        self.control = control;
+        self.chr = [chr0, chr1];
+        self.prg = prg;
+        Ok(())
+    }
+}
+
+/// C64 PLA - $00(방향)/$01(데이터) 프로세서 포트의 LORAM/HIRAM/CHAREN으로 ROM 배치
+/// ROM 구성: BASIC 8KB, KERNAL 8KB, 문자 4KB 순서, RAM 64KB
+#[derive(Debug, Clone)]
+pub struct C64Pla {
+    direction: u8,
+    data: u8,
+}
+
+/// ROM 저장소 내 위치
+pub const C64_BASIC: usize = 0x0000;
+pub const C64_KERNAL: usize = 0x2000;
+pub const C64_CHARACTERS: usize = 0x4000;
+
+const LORAM: u8 = 0b001;
+const HIRAM: u8 = 0b010;
+const CHAREN: u8 = 0b100;
+
+impl Default for C64Pla {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl C64Pla {
+    /// KERNAL 리셋 후 값 ($2F/$37 - BASIC, KERNAL, I/O 보임)
+    pub fn new() -> Self {
+        Self {
+            direction: 0x2F,
+            data: 0x37,
+        }
+    }
+
+    /// 입력으로 설정된 비트 0-5는 풀업되어 1, 연결 없는 비트 6-7은 0으로 읽힘
+    pub fn port(&self) -> u8 {
+        (self.data & self.direction) | (!self.direction & 0x3F)
+    }
+
+    fn lines(&self) -> u8 {
+        self.port() & (LORAM | HIRAM | CHAREN)
+    }
+}
+
+impl Mapper for C64Pla {
+    fn read_bank(&self, address: u16) -> Bank {
+        let lines = self.lines();
+        let ram = Bank::Ram(address as usize);
+        match address {
+            0xA000..=0xBFFF if lines & (LORAM | HIRAM) == LORAM | HIRAM => {
+                Bank::Rom(C64_BASIC + (address as usize - 0xA000))
+            }
+            0xD000..=0xDFFF if lines & (LORAM | HIRAM) != 0 => {
+                if lines & CHAREN != 0 {
+                    // I/O 영역 - 칩은 주소 디코더에서 위에 매핑
+                    Bank::None
+                } else {
+                    Bank::Rom(C64_CHARACTERS + (address as usize - 0xD000))
+                }
+            }
+            0xE000..=0xFFFF if lines & HIRAM != 0 => {
+                Bank::Rom(C64_KERNAL + (address as usize - 0xE000))
+            }
+            _ => ram,
+        }
+    }
+
+    /// ROM이 보이는 곳에 써도 아래 RAM에 기록됨
+    fn write_bank(&self, address: u16) -> Bank {
+        match self.read_bank(address) {
+            Bank::Rom(_) => Bank::Ram(address as usize),
+            bank => bank,
+        }
+    }
+
+    /// 포트 값을 바꾸고 RAM에도 기록 (VIC-II는 RAM 값을 봄)
+    fn write_register(&mut self, address: u16, value: u8) -> bool {
+        match address {
+            0x0000 => self.direction = value,
+            0x0001 => self.data = value,
+            _ => {}
+        }
+        false
+    }
+
+    fn read_register(&self, address: u16) -> Option<u8> {
+        match address {
+            0x0000 => Some(self.direction),
+            0x0001 => Some(self.port()),
+            _ => None,
+        }
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.direction, self.data]
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let &[direction, data] = state else {
+            return Err(Error::InvalidSaveState);
+        };
+        self.direction = direction;
+        self.data = data;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryMap;
+    use crate::mapper::BankedMemory;
+    use common::MemoryBus;
+    use common::device::Device;
+    use std::sync::{Arc, Mutex};
+
+    // 16KB 뱅크마다 첫 바이트에 뱅크 번호
+    fn numbered_rom(banks: usize) -> Vec<u8> {
+        (0..banks)
+            .flat_map(|bank| {
+                let mut data = vec![0xEA; BANK_16K];
+                data[0] = bank as u8;
+                data
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_banked_cartridge() {
+        let mut cart = BankedMemory::new(BankedCartridge::new(0x8000, 0xDE00), numbered_rom(4), 0);
+        cart.write_at(0xDE00, 3);
+        assert_eq!(cart.read_at(0x8000), 3);
+        assert_eq!(cart.read_at(0xC000), 0xFF);
+    }
+
+    #[test]
+    fn test_uxrom() {
+        let mut cart = BankedMemory::new(UxRom::new(8 * BANK_16K), numbered_rom(8), 0);
+        assert_eq!((cart.read_at(0x8000), cart.read_at(0xC000)), (0, 7));
+        cart.write_at(0x8123, 5);
+        assert_eq!((cart.read_at(0x8000), cart.read_at(0xC000)), (5, 7));
+    }
+
+    #[test]
+    fn test_mmc1() {
+        let mut cart = BankedMemory::new(Mmc1::new(8 * BANK_16K), numbered_rom(8), 0x2000);
+        assert_eq!((cart.read_at(0x8000), cart.read_at(0xC000)), (0, 7));
+
+        // PRG 레지스터에 %00011 -> 뱅크 3
+        fn load(cart: &mut BankedMemory<Mmc1>, address: u16, value: u8) {
+            for bit in 0..5 {
+                cart.write_at(address, (value >> bit) & 1);
+            }
+        }
+        load(&mut cart, 0xE000, 3);
+        assert_eq!((cart.read_at(0x8000), cart.read_at(0xC000)), (3, 7));
+
+        // 32KB 모드: 뱅크 2, 3
+        load(&mut cart, 0x8000, 0x00);
+        load(&mut cart, 0xE000, 2);
+        assert_eq!((cart.read_at(0x8000), cart.read_at(0xC000)), (2, 3));
+
+        // 리셋 비트는 마지막 뱅크 고정 모드로
+        cart.write_at(0x8000, 0x80);
+        assert_eq!(cart.mapper().control() & 0x0C, 0x0C);
+
+        cart.write_at(0x6000, 0x42);
+        assert_eq!(cart.read_at(0x6000), 0x42);
+    }
+
+    #[test]
+    fn test_c64_pla() {
+        let mut rom = vec![0; 0x5000];
+        rom[C64_BASIC] = 0xBA;
+        rom[C64_KERNAL] = 0xCE;
+        rom[C64_CHARACTERS] = 0xC4;
+        let mut c64 = BankedMemory::new(C64Pla::new(), rom, 0x10000);
+
+        assert_eq!(c64.read_at(0xA000), 0xBA);
+        assert_eq!(c64.read_at(0xE000), 0xCE);
+        assert_eq!(c64.read_at(0xD000), 0xFF, "I/O");
+        assert_eq!(c64.read_at(0x0001), 0x37);
+
+        // ROM 아래 RAM에 쓰기
+        c64.write_at(0xA000, 0x11);
+        assert_eq!(c64.read_at(0xA000), 0xBA);
+
+        // CHAREN=0: 문자 ROM
+        c64.write_at(0x0001, 0x33);
+        assert_eq!(c64.read_at(0xD000), 0xC4);
+
+        // LORAM=0: BASIC 자리에 RAM, KERNAL은 유지
+        c64.write_at(0x0001, 0x36);
+        assert_eq!(c64.read_at(0xA000), 0x11);
+        assert_eq!(c64.read_at(0xE000), 0xCE);
+
+        // 모두 0: 전부 RAM
+        c64.write_at(0x0001, 0x30);
+        assert_eq!(c64.read_at(0xE000), 0x00);
+        assert_eq!(c64.read_at(0xD000), 0x00);
+    }
+
+    #[test]
+    fn test_save_state_round_trip() {
+        fn mmc1_map() -> MemoryMap {
+            let cart = BankedMemory::new(Mmc1::new(8 * BANK_16K), numbered_rom(8), 0x2000);
+            let mut map = MemoryMap::new();
+            map.map(
+                "cart",
+                0x6000..=0xFFFF,
+                Arc::new(Mutex::new(cart.with_base(0x6000))),
+            )
+            .unwrap();
+            map
+        }
+        let mut map = mmc1_map();
+        // PRG 뱅크 3, 다음 값은 두 비트만 씀
+        for bit in 0..5 {
+            map.write(0xE000, (3 >> bit) & 1);
+        }
+        map.write(0xE000, 1);
+        map.write(0xE000, 0);
+        map.write(0x6000, 0x42);
+        let states = map.save_states();
+
+        let mut restored = mmc1_map();
+        restored.load_states(&states).unwrap();
+        assert_eq!(restored.save_states(), states);
+        assert_eq!(restored.read(0x8000), 3);
+        assert_eq!(restored.read(0x6000), 0x42, "PRG RAM");
+        // 남은 세 비트로 PRG 뱅크 %00101
+        for value in [1, 0, 0] {
+            restored.write(0xE000, value);
+        }
+        assert_eq!(restored.read(0x8000), 5, "serial register resumed");
+
+        let mut c64 = BankedMemory::new(C64Pla::new(), vec![0; 0x5000], 0x10000);
+        c64.write_at(0x0001, 0x30);
+        c64.write_at(0xE000, 0x11);
+        let state = c64.save_state();
+        let mut restored = BankedMemory::new(C64Pla::new(), vec![0; 0x5000], 0x10000);
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored.read_at(0x0001), 0x30);
+        assert_eq!(restored.read_at(0xE000), 0x11, "banked RAM under KERNAL");
+
+        let mut small = BankedMemory::new(C64Pla::new(), vec![0; 0x5000], 0x100);
+        assert!(small.load_state(&state).is_err(), "RAM size differs");
+    }
+}
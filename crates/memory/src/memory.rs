@@ -1,10 +1,32 @@
+use crate::snapshot::{MemoryDiff, MemorySnapshot};
 use common::Result;
+use common::block::{BytePattern, crc32};
+use common::device::Device;
+use common::memory_bus::{MemoryBus, range_len};
+use error::Error;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// ROM 영역 쓰기 처리 방법 - 어느 경우든 값은 바뀌지 않음
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WritePolicy {
+    /// 조용히 무시
+    #[default]
+    Ignore,
+    /// 디버거 이벤트로 알림 (디버거가 있으면 CPU 일시 정지)
+    Trap,
+    /// CPU 실행 오류
+    Error,
+}
 
 /// 메모리 구조체
 #[derive(Debug)]
 pub struct Memory {
     /// 메모리 데이터 (64KB)
     pub data: [u8; 65536],
+    /// 쓰기 보호된 영역
+    rom: Vec<RangeInclusive<u16>>,
+    write_policy: WritePolicy,
 }
 
 impl Default for Memory {
@@ -13,21 +35,206 @@ impl Default for Memory {
     }
 }
 
+/// 단독 메모리를 CPU 버스로 바로 연결할 수 있도록 구현
+impl MemoryBus for Memory {
+    fn peek(&self, address: u16) -> u8 {
+        self.data[address as usize]
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        let _ = self.set(address, value);
+    }
+
+    fn try_write(&mut self, address: u16, value: u8) -> Result<()> {
+        self.set(address, value)
+    }
+
+    // 블록 연산은 주소가 넘치지 않고 ROM과 겹치지 않으면 슬라이스로 처리
+
+    fn peek_block(&self, address: u16, count: usize) -> Vec<u8> {
+        let start = address as usize;
+        if start + count <= self.data.len() {
+            return self.data[start..start + count].to_vec();
+        }
+        (0..count)
+            .map(|i| self.data[address.wrapping_add(i as u16) as usize])
+            .collect()
+    }
+
+    fn write_block(&mut self, address: u16, data: &[u8]) {
+        let start = address as usize;
+        if self.is_writable(start, data.len()) {
+            self.data[start..start + data.len()].copy_from_slice(data);
+            return;
+        }
+        for (i, &value) in data.iter().enumerate() {
+            let _ = self.set(address.wrapping_add(i as u16), value);
+        }
+    }
+
+    fn fill(&mut self, range: RangeInclusive<u16>, pattern: &[u8]) {
+        let (start, len) = (*range.start() as usize, range_len(&range));
+        if pattern.is_empty() || len == 0 {
+            return;
+        }
+        if !self.is_writable(start, len) {
+            for (i, address) in range.enumerate() {
+                let _ = self.set(address, pattern[i % pattern.len()]);
+            }
+        } else if let [value] = pattern {
+            self.data[start..start + len].fill(*value);
+        } else {
+            for chunk in self.data[start..start + len].chunks_mut(pattern.len()) {
+                chunk.copy_from_slice(&pattern[..chunk.len()]);
+            }
+        }
+    }
+
+    fn copy(&mut self, range: RangeInclusive<u16>, dest: u16) {
+        let (start, len) = (*range.start() as usize, range_len(&range));
+        if self.is_writable(dest as usize, len) {
+            self.data.copy_within(start..start + len, dest as usize);
+        } else {
+            let data = self.peek_block(start as u16, len);
+            self.write_block(dest, &data);
+        }
+    }
+
+    fn find(&self, range: RangeInclusive<u16>, pattern: &BytePattern) -> Vec<u16> {
+        let start = *range.start();
+        let len = range_len(&range);
+        pattern
+            .find_in(&self.data[start as usize..start as usize + len])
+            .into_iter()
+            .map(|offset| start + offset as u16)
+            .collect()
+    }
+
+    fn checksum(&self, range: RangeInclusive<u16>) -> u32 {
+        let start = *range.start() as usize;
+        crc32(&self.data[start..start + range_len(&range)])
+    }
+}
+
+/// 주소 디코더에 RAM으로 연결 - 오프셋을 그대로 주소로 사용
+impl Device for Memory {
+    fn peek(&self, offset: u16) -> u8 {
+        self.data[offset as usize]
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        let _ = self.set(offset, value);
+    }
+
+    fn try_write(&mut self, offset: u16, value: u8) -> Result<()> {
+        self.set(offset, value)
+    }
+}
+
 impl Memory {
     /// 새로운 메모리 인스턴스 생성
     pub fn new() -> Self {
-        Self { data: [0; 65536] }
+        Self {
+            data: [0; 65536],
+            rom: Vec::new(),
+            write_policy: WritePolicy::default(),
+        }
     }
 
     pub fn get(&self, address: u16) -> Result<u8> {
         Ok(self.data[address as usize])
     }
 
+    /// 쓰기 - ROM 영역이면 값은 그대로 두고 정책에 따라 처리
     pub fn set(&mut self, address: u16, value: u8) -> Result<()> {
+        if self.is_rom(address) {
+            return match self.write_policy {
+                WritePolicy::Ignore => Ok(()),
+                WritePolicy::Trap => Err(Error::WriteProtected(address)),
+                WritePolicy::Error => Err(Error::InvalidMemoryAddress(address)),
+            };
+        }
         self.data[address as usize] = value;
         Ok(())
     }
 
+    /// 보호 여부와 관계없이 address부터 data 복사
+    pub fn load(&mut self, address: u16, data: &[u8]) -> Result<()> {
+        let start = address as usize;
+        let end = start + data.len();
+        if end > self.data.len() {
+            return Err(Error::InvalidMemoryRange(address));
+        }
+        self.data[start..end].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// data를 적재하고 그 영역을 쓰기 보호
+    pub fn load_rom(&mut self, address: u16, data: &[u8]) -> Result<()> {
+        if data.is_empty() {
+            return Err(Error::InvalidMemoryRange(address));
+        }
+        self.load(address, data)?;
+        self.protect(address..=address + (data.len() - 1) as u16);
+        Ok(())
+    }
+
+    /// 파일을 ROM으로 적재
+    pub fn load_rom_file(&mut self, address: u16, path: impl AsRef<Path>) -> Result<()> {
+        let data = std::fs::read(path)?;
+        self.load_rom(address, &data)
+    }
+
+    /// 파일 내용을 address부터 적재 (보호 무시) - 적재한 바이트 수
+    pub fn load_file(&mut self, address: u16, path: impl AsRef<Path>) -> Result<usize> {
+        let data = std::fs::read(path)?;
+        self.load(address, &data)?;
+        Ok(data.len())
+    }
+
+    /// 범위를 파일로 저장
+    pub fn save_file(&self, range: RangeInclusive<u16>, path: impl AsRef<Path>) -> Result<()> {
+        let start = *range.start() as usize;
+        std::fs::write(path, &self.data[start..start + range_len(&range)])?;
+        Ok(())
+    }
+
+    /// 영역 쓰기 보호
+    pub fn protect(&mut self, range: RangeInclusive<u16>) {
+        self.rom.push(range);
+    }
+
+    /// 모든 쓰기 보호 해제
+    pub fn clear_rom(&mut self) {
+        self.rom.clear();
+    }
+
+    pub fn rom_regions(&self) -> &[RangeInclusive<u16>] {
+        &self.rom
+    }
+
+    pub fn is_rom(&self, address: u16) -> bool {
+        self.rom.iter().any(|range| range.contains(&address))
+    }
+
+    // start부터 len바이트가 주소 공간 안에 있고 ROM과 겹치지 않는지
+    fn is_writable(&self, start: usize, len: usize) -> bool {
+        start + len <= self.data.len()
+            && self.rom.iter().all(|range| {
+                len == 0
+                    || (*range.end() as usize) < start
+                    || (*range.start() as usize) >= start + len
+            })
+    }
+
+    pub fn write_policy(&self) -> WritePolicy {
+        self.write_policy
+    }
+
+    pub fn set_write_policy(&mut self, policy: WritePolicy) {
+        self.write_policy = policy;
+    }
+
     /// 메모리 내용 가져오기
     pub fn get_memory_content(&self, address: u16) -> u8 {
         self.data[address as usize]
@@ -43,41 +250,101 @@ impl Memory {
     }
 
     pub fn dump(&self, addr: u16, size: usize) -> String {
-        let mut result = String::new();
-
-        for i in (0..size).step_by(16) {
-            let current_addr = addr.wrapping_add(i as u16);
-
-            // 주소 출력
-            result.push_str(&format!("{:04x}: ", current_addr));
-
-            // 16진수 값 출력
-            for j in 0..16 {
-                if i + j < size {
-                    let value = self.data[current_addr.wrapping_add(j as u16) as usize];
-                    result.push_str(&format!("{:02x} ", value));
-                } else {
-                    result.push_str("   ");
-                }
-            }
+        MemoryBus::dump(self, addr, size)
+    }
 
-            // ASCII 문자 출력
-            result.push_str(" |");
-            for j in 0..16 {
-                if i + j < size {
-                    let value = self.data[current_addr.wrapping_add(j as u16) as usize];
-                    // 출력 가능한 ASCII 문자만 표시
-                    let ch = if (0x20..=0x7e).contains(&value) {
-                        value as char
-                    } else {
-                        '.'
-                    };
-                    result.push(ch);
-                }
-            }
-            result.push_str("|\n");
-        }
+    /// 현재 내용 스냅샷
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot::capture(self)
+    }
+
+    /// snapshot 이후 바뀐 곳
+    pub fn diff(&self, snapshot: &MemorySnapshot) -> MemoryDiff {
+        snapshot.diff_live(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rom_write_policy() {
+        let mut memory = Memory::new();
+        memory.load_rom(0xE000, &[0xEA, 0x60]).unwrap();
+        assert!(memory.is_rom(0xE001));
+        assert!(!memory.is_rom(0xE002));
+        assert!(memory.load_rom(0xFFFF, &[0, 0]).is_err());
+
+        memory.set(0xE000, 0x00).unwrap();
+        memory.set_write_policy(WritePolicy::Trap);
+        assert!(matches!(
+            MemoryBus::try_write(&mut memory, 0xE000, 0x00),
+            Err(Error::WriteProtected(0xE000))
+        ));
+        memory.set_write_policy(WritePolicy::Error);
+        assert!(matches!(
+            memory.set(0xE001, 0x00),
+            Err(Error::InvalidMemoryAddress(0xE001))
+        ));
+        assert_eq!(&memory.data[0xE000..0xE002], &[0xEA, 0x60]);
+
+        // 보호 영역 밖과 적재는 그대로 동작
+        memory.set(0xE002, 0x42).unwrap();
+        memory.load(0xE000, &[0x00]).unwrap();
+        assert_eq!(&memory.data[0xE000..0xE003], &[0x00, 0x60, 0x42]);
+    }
+
+    #[test]
+    fn test_block_operations() {
+        let mut memory = Memory::new();
+        memory.fill(0x1000..=0x1006, &[0xA9, 0x01, 0x8D]);
+        assert_eq!(
+            &memory.data[0x1000..0x1008],
+            &[0xA9, 0x01, 0x8D, 0xA9, 0x01, 0x8D, 0xA9, 0x00]
+        );
+
+        // 겹치는 복사
+        memory.copy(0x1000..=0x1005, 0x1002);
+        assert_eq!(
+            &memory.data[0x1002..0x1008],
+            &[0xA9, 0x01, 0x8D, 0xA9, 0x01, 0x8D]
+        );
+        assert_eq!(memory.compare(0x1002..=0x1004, 0x1005), Vec::<u16>::new());
+        assert_eq!(
+            memory.compare(0x1000..=0x1001, 0x1001),
+            vec![0x1000, 0x1001]
+        );
+
+        let pattern = BytePattern::parse("A9 ?? 8D").unwrap();
+        assert_eq!(memory.find(0x0000..=0xFFFF, &pattern), vec![0x1002, 0x1005]);
+        assert_eq!(
+            memory.checksum(0x1002..=0x1007),
+            crc32(&memory.data[0x1002..0x1008])
+        );
+
+        // ROM 영역은 블록 쓰기에서도 보호
+        memory.load_rom(0x2001, &[0x60]).unwrap();
+        memory.fill(0x2000..=0x2002, &[0xEA]);
+        assert_eq!(&memory.data[0x2000..0x2003], &[0xEA, 0x60, 0xEA]);
+        memory.write_block(0xFFFF, &[0x11, 0x22]);
+        assert_eq!((memory.data[0xFFFF], memory.data[0x0000]), (0x11, 0x22));
+    }
+
+    #[test]
+    fn test_load_and_save_file() {
+        let path = std::env::temp_dir().join(format!("memory-{}.bin", std::process::id()));
+        let mut memory = Memory::new();
+        memory.fill(0x0200..=0x02FF, &[0x00, 0x01, 0x02, 0x03]);
+        memory.save_file(0x0200..=0x02FF, &path).unwrap();
 
-        result
+        let mut loaded = Memory::new();
+        assert_eq!(loaded.load_file(0xC000, &path).unwrap(), 0x100);
+        assert_eq!(
+            loaded.checksum(0xC000..=0xC0FF),
+            memory.checksum(0x0200..=0x02FF)
+        );
+        assert!(loaded.load_file(0xFF80, &path).is_err());
+        std::fs::remove_file(path).unwrap();
     }
 }
@@ -0,0 +1,417 @@
+//! 주소 디코더 - 주소 범위를 장치에 연결 (미러링/마스크, 오픈 버스)
+use common::Result;
+use common::device::Device;
+use common::memory_bus::MemoryBus;
+use error::Error;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+
+/// 여러 매핑과 외부 코드가 함께 쓰는 장치 참조
+pub type SharedDevice = Arc<Mutex<dyn Device>>;
+
+/// 매핑되지 않은 주소를 읽을 때의 값
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenBus {
+    /// 항상 같은 값
+    Value(u8),
+    /// 마지막으로 데이터 버스에 실린 값 (NMOS 6502 동작)
+    LastValue,
+}
+
+/// 장치 인터럽트 출력을 연결할 CPU 핀
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterruptLine {
+    #[default]
+    Irq,
+    Nmi,
+    /// 연결하지 않음 (장치는 플래그만 세움)
+    None,
+}
+
+/// 주소 범위 하나와 연결된 장치
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    pub name: String,
+    pub start: u16,
+    pub end: u16,
+    /// 오프셋 마스크 - 장치 크기보다 넓은 범위에 반복(미러링)되도록 함
+    pub mask: u16,
+    /// 마스크를 적용한 오프셋에 더하는 값
+    pub base: u16,
+    /// 장치 인터럽트 출력 연결 - 기본 IRQ
+    pub line: InterruptLine,
+    device: SharedDevice,
+}
+
+impl Mapping {
+    pub fn contains(&self, address: u16) -> bool {
+        (self.start..=self.end).contains(&address)
+    }
+
+    /// 장치에 전달할 오프셋
+    pub fn offset(&self, address: u16) -> u16 {
+        self.base
+            .wrapping_add(address.wrapping_sub(self.start) & self.mask)
+    }
+
+    pub fn device(&self) -> SharedDevice {
+        self.device.clone()
+    }
+}
+
+/// 주소 디코더 - 나중에 매핑한 범위가 앞의 매핑을 가림
+#[derive(Debug)]
+pub struct MemoryMap {
+    mappings: Vec<Mapping>,
+    open_bus: OpenBus,
+    /// 마지막으로 데이터 버스에 실린 값
+    last_value: u8,
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryMap {
+    /// 빈 주소 공간 - 매핑되지 않은 곳은 $FF
+    pub fn new() -> Self {
+        Self {
+            mappings: Vec::new(),
+            open_bus: OpenBus::Value(0xFF),
+            last_value: 0,
+        }
+    }
+
+    /// 범위 시작을 오프셋 0으로 하여 장치 연결
+    pub fn map(
+        &mut self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        device: SharedDevice,
+    ) -> Result<()> {
+        self.map_mirrored(name, range, 0xFFFF, device)
+    }
+
+    /// 오프셋에 mask를 적용하여 장치 연결 - 예: $0000-$1FFF에 2KB RAM은 mask $07FF
+    pub fn map_mirrored(
+        &mut self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        mask: u16,
+        device: SharedDevice,
+    ) -> Result<()> {
+        self.map_at(name, range, mask, 0, device)
+    }
+
+    /// 범위 시작을 오프셋 base로 하여 장치 연결
+    /// 예: A9를 RS로 쓰는 6532는 RAM과 I/O가 떨어져 있어 I/O 범위를 base $80으로 매핑
+    pub fn map_at(
+        &mut self,
+        name: &str,
+        range: RangeInclusive<u16>,
+        mask: u16,
+        base: u16,
+        device: SharedDevice,
+    ) -> Result<()> {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return Err(Error::InvalidMemoryRange(start));
+        }
+        self.mappings.push(Mapping {
+            name: name.to_string(),
+            start,
+            end,
+            mask,
+            base,
+            line: InterruptLine::default(),
+            device,
+        });
+        Ok(())
+    }
+
+    /// 이름이 같은 매핑의 인터럽트 출력을 line에 연결
+    pub fn set_interrupt_line(&mut self, name: &str, line: InterruptLine) -> Result<()> {
+        let mut found = false;
+        for mapping in self.mappings.iter_mut().filter(|m| m.name == name) {
+            mapping.line = line;
+            found = true;
+        }
+        match found {
+            true => Ok(()),
+            false => Err(Error::ComponentNotFound(name.to_string())),
+        }
+    }
+
+    // line에 연결된 장치 중 하나라도 요청하면 true (와이어드 OR)
+    fn line_active(&self, line: InterruptLine) -> bool {
+        self.mappings
+            .iter()
+            .filter(|mapping| mapping.line == line)
+            .any(|mapping| mapping.device.lock().unwrap().irq())
+    }
+
+    /// 이름이 같은 매핑 모두 제거
+    pub fn unmap(&mut self, name: &str) {
+        self.mappings.retain(|mapping| mapping.name != name);
+    }
+
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// address를 처리하는 매핑
+    pub fn find(&self, address: u16) -> Option<&Mapping> {
+        self.mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.contains(address))
+    }
+
+    pub fn open_bus(&self) -> OpenBus {
+        self.open_bus
+    }
+
+    pub fn set_open_bus(&mut self, open_bus: OpenBus) {
+        self.open_bus = open_bus;
+    }
+
+    fn open_bus_value(&self) -> u8 {
+        match self.open_bus {
+            OpenBus::Value(value) => value,
+            OpenBus::LastValue => self.last_value,
+        }
+    }
+
+    /// 연결된 모든 장치에 사이클 경과 전달 (여러 범위에 매핑된 장치도 한 번만)
+    pub fn tick(&mut self, cycles: u64) {
+        for mapping in self.unique_mappings() {
+            mapping.device.lock().unwrap().tick(cycles);
+        }
+    }
+
+    /// 상태가 있는 장치의 (매핑 이름, 상태) 목록
+    pub fn save_states(&self) -> Vec<(String, Vec<u8>)> {
+        self.unique_mappings()
+            .into_iter()
+            .filter_map(|mapping| {
+                let state = mapping.device.lock().unwrap().save_state();
+                (!state.is_empty()).then(|| (mapping.name.clone(), state))
+            })
+            .collect()
+    }
+
+    /// save_states로 저장한 상태를 같은 이름의 매핑에 복원
+    pub fn load_states(&mut self, states: &[(String, Vec<u8>)]) -> Result<()> {
+        for (name, state) in states {
+            let mapping = self
+                .mappings
+                .iter()
+                .find(|mapping| &mapping.name == name)
+                .ok_or_else(|| Error::ComponentNotFound(name.clone()))?;
+            mapping.device.lock().unwrap().load_state(state)?;
+        }
+        Ok(())
+    }
+
+    // 장치마다 첫 매핑 하나 (여러 범위에 매핑된 장치 중복 제거)
+    fn unique_mappings(&self) -> Vec<&Mapping> {
+        let mut unique: Vec<&Mapping> = Vec::new();
+        for mapping in &self.mappings {
+            if !unique
+                .iter()
+                .any(|seen| Arc::ptr_eq(&seen.device, &mapping.device))
+            {
+                unique.push(mapping);
+            }
+        }
+        unique
+    }
+}
+
+impl MemoryBus for MemoryMap {
+    fn peek(&self, address: u16) -> u8 {
+        match self.find(address) {
+            Some(mapping) => mapping.device.lock().unwrap().peek(mapping.offset(address)),
+            None => self.open_bus_value(),
+        }
+    }
+
+    fn read(&mut self, address: u16) -> u8 {
+        let value = match self.find(address) {
+            Some(mapping) => mapping.device.lock().unwrap().read(mapping.offset(address)),
+            None => self.open_bus_value(),
+        };
+        self.last_value = value;
+        value
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.last_value = value;
+        if let Some(mapping) = self.find(address) {
+            mapping
+                .device
+                .lock()
+                .unwrap()
+                .write(mapping.offset(address), value);
+        }
+    }
+
+    fn irq(&self) -> bool {
+        self.line_active(InterruptLine::Irq)
+    }
+
+    fn nmi(&self) -> bool {
+        self.line_active(InterruptLine::Nmi)
+    }
+
+    fn try_write(&mut self, address: u16, value: u8) -> Result<()> {
+        self.last_value = value;
+        match self.find(address) {
+            Some(mapping) => {
+                let offset = mapping.offset(address);
+                mapping
+                    .device
+                    .lock()
+                    .unwrap()
+                    .try_write(offset, value)
+                    // 장치 오프셋이 아닌 CPU 주소로 보고
+                    .map_err(|e| match e {
+                        Error::WriteProtected(_) => Error::WriteProtected(address),
+                        Error::InvalidMemoryAddress(_) => Error::InvalidMemoryAddress(address),
+                        e => e,
+                    })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Memory;
+
+    #[derive(Debug, Default)]
+    struct Timer {
+        cycles: u64,
+    }
+
+    impl Device for Timer {
+        fn peek(&self, offset: u16) -> u8 {
+            (self.cycles >> (offset * 8)) as u8
+        }
+
+        fn write(&mut self, _offset: u16, _value: u8) {
+            self.cycles = 0;
+        }
+
+        fn tick(&mut self, cycles: u64) {
+            self.cycles += cycles;
+        }
+    }
+
+    #[test]
+    fn test_mirroring_and_overlay() {
+        let ram = Arc::new(Mutex::new(Memory::new()));
+        let timer = Arc::new(Mutex::new(Timer::default()));
+        let mut map = MemoryMap::new();
+        // 2KB RAM을 $0000-$1FFF에 4번 반복
+        map.map_mirrored("ram", 0x0000..=0x1FFF, 0x07FF, ram.clone())
+            .unwrap();
+        map.map("timer", 0x1000..=0x1001, timer.clone()).unwrap();
+        map.map("timer", 0x1800..=0x1801, timer.clone()).unwrap();
+        let (start, end) = (0x2000, 0x1000);
+        assert!(map.map("bad", start..=end, timer.clone()).is_err());
+
+        map.write(0x0801, 0x42);
+        assert_eq!(map.read(0x0001), 0x42);
+        assert_eq!(ram.lock().unwrap().data[0x0001], 0x42);
+
+        map.tick(0x1234);
+        assert_eq!((map.read(0x1000), map.read(0x1801)), (0x34, 0x12));
+        assert_eq!(map.find(0x1002).unwrap().name, "ram");
+        // 상위 바이트만 따로 보이는 창
+        map.map_at("timer-high", 0x2000..=0x2000, 0x0000, 1, timer.clone())
+            .unwrap();
+        assert_eq!(map.read(0x2000), 0x12);
+
+        map.unmap("timer");
+        assert_eq!(map.read(0x1001), 0x42, "RAM mirror visible again");
+    }
+
+    /// 읽으면 지워지는 상태 레지스터
+    #[derive(Debug)]
+    struct Status {
+        flags: u8,
+    }
+
+    impl Device for Status {
+        fn peek(&self, _offset: u16) -> u8 {
+            self.flags
+        }
+
+        fn read(&mut self, _offset: u16) -> u8 {
+            std::mem::take(&mut self.flags)
+        }
+
+        fn write(&mut self, _offset: u16, value: u8) {
+            self.flags = value;
+        }
+
+        fn irq(&self) -> bool {
+            self.flags != 0
+        }
+    }
+
+    #[test]
+    fn test_peek_has_no_side_effects() {
+        let mut map = MemoryMap::new();
+        map.map(
+            "status",
+            0xD000..=0xD000,
+            Arc::new(Mutex::new(Status { flags: 0x80 })),
+        )
+        .unwrap();
+
+        assert_eq!(map.peek(0xD000), 0x80);
+        assert!(map.dump(0xD000, 1).starts_with("d000: 80 "));
+        assert_eq!(map.peek_block(0xD000, 1), [0x80]);
+        assert_eq!(map.read(0xD000), 0x80);
+        assert_eq!(map.peek(0xD000), 0x00, "cleared by CPU read");
+
+        map.write(0xD000, 0x40);
+        assert_eq!(map.read_block(0xD000, 1), [0x40]);
+        assert_eq!(map.peek(0xD000), 0x00, "cleared by block read");
+    }
+
+    #[test]
+    fn test_open_bus() {
+        let mut map = MemoryMap::new();
+        map.map("ram", 0x0000..=0x00FF, Arc::new(Mutex::new(Memory::new())))
+            .unwrap();
+        assert_eq!(map.read(0x8000), 0xFF);
+
+        map.set_open_bus(OpenBus::LastValue);
+        map.write(0x0010, 0x5A);
+        assert_eq!(map.read(0x0010), 0x5A);
+        assert_eq!(map.read(0x8000), 0x5A);
+    }
+
+    #[test]
+    fn test_interrupt_lines() {
+        let status = Arc::new(Mutex::new(Status { flags: 0x80 }));
+        let mut map = MemoryMap::new();
+        map.map("status", 0xD000..=0xD000, status.clone()).unwrap();
+        assert!(map.irq() && !map.nmi());
+
+        map.set_interrupt_line("status", InterruptLine::Nmi)
+            .unwrap();
+        assert!(!map.irq() && map.nmi());
+        map.set_interrupt_line("status", InterruptLine::None)
+            .unwrap();
+        assert!(!map.irq() && !map.nmi());
+        assert!(map.set_interrupt_line("via", InterruptLine::Irq).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! 메모리 스냅샷과 비교 - 서브루틴 실행 전후에 무엇이 바뀌었는지 확인
+use common::memory_bus::MemoryBus;
+use std::fmt;
+
+const SIZE: usize = 0x10000;
+
+/// 64KB 주소 공간 전체 복사본
+#[derive(Clone, PartialEq, Eq)]
+pub struct MemorySnapshot {
+    data: Box<[u8]>,
+}
+
+impl fmt::Debug for MemorySnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemorySnapshot")
+            .field("crc32", &format_args!("{:08X}", common::crc32(&self.data)))
+            .finish()
+    }
+}
+
+impl MemorySnapshot {
+    /// 버스 전체를 peek으로 복사 - 장치 상태는 바뀌지 않음
+    pub fn capture(bus: &(impl MemoryBus + ?Sized)) -> Self {
+        Self {
+            data: bus.peek_block(0, SIZE).into_boxed_slice(),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn get(&self, address: u16) -> u8 {
+        self.data[address as usize]
+    }
+
+    /// self(이전)에서 newer(이후)로 바뀐 곳
+    pub fn diff(&self, newer: &MemorySnapshot) -> MemoryDiff {
+        let mut changes: Vec<Change> = Vec::new();
+        for (address, (&old, &new)) in self.data.iter().zip(newer.data.iter()).enumerate() {
+            if old == new {
+                continue;
+            }
+            match changes.last_mut() {
+                Some(change) if change.end() as usize + 1 == address => {
+                    change.old.push(old);
+                    change.new.push(new);
+                }
+                _ => changes.push(Change {
+                    start: address as u16,
+                    old: vec![old],
+                    new: vec![new],
+                }),
+            }
+        }
+        MemoryDiff { changes }
+    }
+
+    /// 스냅샷 이후 현재 버스 내용과의 차이
+    pub fn diff_live(&self, bus: &(impl MemoryBus + ?Sized)) -> MemoryDiff {
+        self.diff(&Self::capture(bus))
+    }
+}
+
+/// 연속으로 바뀐 영역 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub start: u16,
+    pub old: Vec<u8>,
+    pub new: Vec<u8>,
+}
+
+impl Change {
+    /// 마지막 주소 (포함)
+    pub fn end(&self) -> u16 {
+        self.start + (self.new.len() - 1) as u16
+    }
+
+    pub fn len(&self) -> usize {
+        self.new.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty()
+    }
+}
+
+/// 두 스냅샷의 차이 - 주소 순서
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryDiff {
+    changes: Vec<Change>,
+}
+
+impl MemoryDiff {
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// 바뀐 바이트 수
+    pub fn changed_bytes(&self) -> usize {
+        self.changes.iter().map(Change::len).sum()
+    }
+}
+
+/// 줄마다 최대 8바이트 - `0200: 00 00 -> 48 45`
+impl fmt::Display for MemoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut first = true;
+        for change in &self.changes {
+            for (i, (old, new)) in change.old.chunks(8).zip(change.new.chunks(8)).enumerate() {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+                let address = change.start.wrapping_add(i as u16 * 8);
+                write!(f, "{:04x}: {} -> {}", address, hex(old), hex(new))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Memory;
+
+    #[test]
+    fn test_diff_groups_contiguous_changes() {
+        let mut memory = Memory::new();
+        memory.load(0x0200, b"HELLO").unwrap();
+        let before = memory.snapshot();
+
+        memory.load(0x0200, b"JELLY, WORLD").unwrap();
+        memory.write(0xFFFF, 0x01);
+        let diff = memory.diff(&before);
+
+        assert_eq!(diff.changes().len(), 3);
+        assert_eq!(diff.changes()[0].start, 0x0200);
+        assert_eq!(diff.changes()[0].new, b"J");
+        assert_eq!(
+            (diff.changes()[1].start, diff.changes()[1].end()),
+            (0x0204, 0x020B)
+        );
+        assert_eq!(diff.changed_bytes(), 10);
+        assert_eq!(
+            diff.to_string(),
+            "0200: 48 -> 4a\n\
+             0204: 4f 00 00 00 00 00 00 00 -> 59 2c 20 57 4f 52 4c 44\n\
+             ffff: 00 -> 01"
+        );
+        assert!(memory.diff(&memory.snapshot()).is_empty());
+    }
+}
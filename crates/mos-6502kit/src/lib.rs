@@ -1 +1,15 @@
+//! MOS 6502 개발 도구 모음
+//! 애플리케이션에서 사용할 수 있도록 각 구성 요소 크레이트를 다시 내보냄
 
+pub use assembler;
+pub use breadboard;
+pub use common;
+pub use cpu;
+pub use debugger;
+pub use disassembler;
+pub use display;
+pub use error;
+pub use input;
+pub use memory;
+pub use peripherals;
+pub use script;
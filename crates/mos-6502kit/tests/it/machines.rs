@@ -0,0 +1,231 @@
+use breadboard::{InputEvent, Machine, MachineDescription, Preset};
+use peripherals::serial::BufferPort;
+
+/// 창 크기의 ROM - 코드는 창 시작, 리셋 벡터는 창 시작 주소
+fn rom(preset: Preset, code: &[u8]) -> Vec<u8> {
+    let window = preset.rom_window();
+    let start = *window.start();
+    let mut rom = vec![0xFF; window.len()];
+    rom[..code.len()].copy_from_slice(code);
+    let vector = rom.len() - 4;
+    rom[vector..vector + 2].copy_from_slice(&start.to_le_bytes());
+    rom
+}
+
+#[test]
+fn test_apple1_terminal_echo() {
+    #[rustfmt::skip]
+    let code = [
+        0xA9, 0x7F,       // $FF00 LDA #$7F
+        0x8D, 0x12, 0xD0, // $FF02 STA DSP     ; DDRB, PB7 입력
+        0xA9, 0xA7,       // $FF05 LDA #$A7
+        0x8D, 0x11, 0xD0, // $FF07 STA KBDCR
+        0x8D, 0x13, 0xD0, // $FF0A STA DSPCR
+        0xAD, 0x11, 0xD0, // $FF0D LDA KBDCR
+        0x10, 0xFB,       // $FF10 BPL $FF0D
+        0xAD, 0x10, 0xD0, // $FF12 LDA KBD
+        0xAC, 0x12, 0xD0, // $FF15 LDY DSP
+        0x30, 0xFB,       // $FF18 BMI $FF15
+        0x8D, 0x12, 0xD0, // $FF1A STA DSP
+        0x4C, 0x0D, 0xFF, // $FF1D JMP $FF0D
+    ];
+    let mut machine = Machine::with_rom(Preset::Apple1, &rom(Preset::Apple1, &code)).unwrap();
+    let port = BufferPort::new();
+    machine.attach_console(port.clone()).unwrap();
+    port.push_input(b"run\n");
+
+    machine.board.run_until(2_000).unwrap();
+    assert_eq!(port.take_output(), b"RUN\r\n");
+}
+
+#[test]
+fn test_ben_eater_lcd() {
+    #[rustfmt::skip]
+    let code = [
+        0xA2, 0xFF,       // $8000 LDX #$FF
+        0x9A,             // $8002 TXS
+        0xA9, 0xFF,       // $8003 LDA #$FF
+        0x8D, 0x02, 0x60, // $8005 STA DDRB
+        0xA9, 0xE0,       // $8008 LDA #$E0
+        0x8D, 0x03, 0x60, // $800A STA DDRA    ; E, RW, RS
+        0xA9, 0x38,       // $800D LDA #$38    ; 8비트 2줄
+        0x20, 0x2E, 0x80, // $800F JSR LCDINST
+        0xA9, 0x0E,       // $8012 LDA #$0E    ; 표시, 커서 켬
+        0x20, 0x2E, 0x80, // $8014 JSR LCDINST
+        0xA9, 0x06,       // $8017 LDA #$06    ; 증가 모드
+        0x20, 0x2E, 0x80, // $8019 JSR LCDINST
+        0xA9, 0x01,       // $801C LDA #$01    ; 지우기
+        0x20, 0x2E, 0x80, // $801E JSR LCDINST
+        0xA9, 0x48,       // $8021 LDA #'H'
+        0x20, 0x44, 0x80, // $8023 JSR PRINT
+        0xA9, 0x69,       // $8026 LDA #'i'
+        0x20, 0x44, 0x80, // $8028 JSR PRINT
+        0x4C, 0x2B, 0x80, // $802B JMP $802B
+        // LCDINST
+        0x20, 0x5A, 0x80, // $802E JSR LCDWAIT
+        0x8D, 0x00, 0x60, // $8031 STA PORTB
+        0xA9, 0x00,       // $8034 LDA #0
+        0x8D, 0x01, 0x60, // $8036 STA PORTA
+        0xA9, 0x80,       // $8039 LDA #E
+        0x8D, 0x01, 0x60, // $803B STA PORTA
+        0xA9, 0x00,       // $803E LDA #0
+        0x8D, 0x01, 0x60, // $8040 STA PORTA
+        0x60,             // $8043 RTS
+        // PRINT
+        0x20, 0x5A, 0x80, // $8044 JSR LCDWAIT
+        0x8D, 0x00, 0x60, // $8047 STA PORTB
+        0xA9, 0x20,       // $804A LDA #RS
+        0x8D, 0x01, 0x60, // $804C STA PORTA
+        0xA9, 0xA0,       // $804F LDA #(RS | E)
+        0x8D, 0x01, 0x60, // $8051 STA PORTA
+        0xA9, 0x20,       // $8054 LDA #RS
+        0x8D, 0x01, 0x60, // $8056 STA PORTA
+        0x60,             // $8059 RTS
+        // LCDWAIT
+        0x48,             // $805A PHA
+        0xA9, 0x00,       // $805B LDA #0
+        0x8D, 0x02, 0x60, // $805D STA DDRB    ; 포트 B 입력
+        0xA9, 0x40,       // $8060 LDA #RW
+        0x8D, 0x01, 0x60, // $8062 STA PORTA
+        0xA9, 0xC0,       // $8065 LDA #(RW | E)
+        0x8D, 0x01, 0x60, // $8067 STA PORTA
+        0xAD, 0x00, 0x60, // $806A LDA PORTB
+        0x29, 0x80,       // $806D AND #$80    ; busy 플래그
+        0xD0, 0xEF,       // $806F BNE $8060
+        0xA9, 0x40,       // $8071 LDA #RW
+        0x8D, 0x01, 0x60, // $8073 STA PORTA
+        0xA9, 0xFF,       // $8076 LDA #$FF
+        0x8D, 0x02, 0x60, // $8078 STA DDRB
+        0x68,             // $807B PLA
+        0x60,             // $807C RTS
+    ];
+    let rom = rom(Preset::BenEater, &code);
+    let mut machine = Machine::with_rom(Preset::BenEater, &rom).unwrap();
+    machine.board.run_until(2_000).unwrap();
+    let lines = machine.lcd_lines().unwrap();
+    assert_eq!(lines[0], format!("{:<16}", "Hi"));
+
+    // LCD 내용도 세이브 스테이트에 포함
+    let snapshot = machine.board.snapshot();
+    let mut restored = Machine::with_rom(Preset::BenEater, &rom).unwrap();
+    restored.board.restore(&snapshot).unwrap();
+    assert_eq!(restored.lcd_lines().unwrap(), lines);
+}
+
+/// 수신한 바이트를 그대로 송신
+#[rustfmt::skip]
+const ACIA_ECHO: [u8; 33] = [
+    0xA9, 0x1E,       // $C000 LDA #$1E
+    0x8D, 0x03, 0x80, // $C002 STA CONTROL ; 9600 8N1
+    0xA9, 0x0B,       // $C005 LDA #$0B
+    0x8D, 0x02, 0x80, // $C007 STA COMMAND
+    0xAD, 0x01, 0x80, // $C00A LDA STATUS
+    0x29, 0x08,       // $C00D AND #RDRF
+    0xF0, 0xF9,       // $C00F BEQ $C00A
+    0xAE, 0x00, 0x80, // $C011 LDX DATA
+    0xAD, 0x01, 0x80, // $C014 LDA STATUS
+    0x29, 0x10,       // $C017 AND #TDRE
+    0xF0, 0xF9,       // $C019 BEQ $C014
+    0x8E, 0x00, 0x80, // $C01B STX DATA
+    0x4C, 0x0A, 0xC0, // $C01E JMP $C00A
+];
+
+#[test]
+fn test_acia_sbc_echo() {
+    let mut machine =
+        Machine::with_rom(Preset::AciaSbc, &rom(Preset::AciaSbc, &ACIA_ECHO)).unwrap();
+    let port = BufferPort::new();
+    machine.attach_console(port.clone()).unwrap();
+    port.push_input(b"OK");
+
+    machine.board.run_until(5_000).unwrap();
+    assert_eq!(port.take_output(), b"OK");
+}
+
+/// 호스트 포트 입력도 보드 입력으로 기록되어 호스트 입력 없이 재생됨
+#[test]
+fn test_console_input_recorded() {
+    let rom = rom(Preset::AciaSbc, &ACIA_ECHO);
+    let mut machine = Machine::with_rom(Preset::AciaSbc, &rom).unwrap();
+    let port = BufferPort::new();
+    machine.attach_console(port.clone()).unwrap();
+    machine.board.start_recording();
+    port.push_input(b"OK");
+    machine.board.run_until(5_000).unwrap();
+    assert_eq!(port.take_output(), b"OK");
+    let recording = machine.board.stop_recording().unwrap();
+    let events: Vec<_> = recording.inputs.iter().map(|input| input.event).collect();
+    assert_eq!(events, [InputEvent::Serial(b'O'), InputEvent::Serial(b'K')]);
+
+    let mut replayed = Machine::with_rom(Preset::AciaSbc, &rom).unwrap();
+    let replay_port = BufferPort::new();
+    replayed.attach_console(replay_port.clone()).unwrap();
+    replayed.board.replay(&recording).unwrap();
+    replayed.board.run_until(5_000).unwrap();
+    assert_eq!(replay_port.take_output(), b"OK");
+    assert_eq!(replayed.board.snapshot(), machine.board.snapshot());
+}
+
+/// VIA T1 인터럽트를 기술 파일에서 NMI에 연결 - SEI 상태에서도 처리되어야 함
+#[test]
+fn test_description_file_nmi_wiring() {
+    #[rustfmt::skip]
+    let program = [
+        0x78,             // $F000 SEI
+        0xA9, 0x40,       // $F001 LDA #$40
+        0x8D, 0x0B, 0x60, // $F003 STA ACR     ; T1 프리런
+        0xA9, 0xC0,       // $F006 LDA #$C0
+        0x8D, 0x0E, 0x60, // $F008 STA IER     ; T1 인터럽트 허용
+        0xA9, 0x62,       // $F00B LDA #98
+        0x8D, 0x04, 0x60, // $F00D STA T1C_L
+        0xA9, 0x00,       // $F010 LDA #0
+        0x8D, 0x05, 0x60, // $F012 STA T1C_H   ; 100사이클 주기로 시작
+        0x4C, 0x15, 0xF0, // $F015 JMP $F015
+    ];
+    #[rustfmt::skip]
+    let handler = [
+        0xE6, 0x10,       // $F100 INC $10
+        0xAD, 0x04, 0x60, // $F102 LDA T1C_L   ; 플래그 클리어 (NMI 선 내려감)
+        0x40,             // $F105 RTI
+    ];
+    let mut rom = vec![0xFF; 0x1000];
+    rom[..program.len()].copy_from_slice(&program);
+    rom[0x100..0x100 + handler.len()].copy_from_slice(&handler);
+    rom[0xFFA..].copy_from_slice(&[0x00, 0xF1, 0x00, 0xF0, 0x00, 0xF1]);
+
+    let dir = std::env::temp_dir().join(format!("machine-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("nmi.rom"), &rom).unwrap();
+
+    for (interrupt, expected) in [("nmi", 98..=100), ("none", 0..=0)] {
+        let path = dir.join(format!("{}.toml", interrupt));
+        let text = format!(
+            r#"
+            [[ram]]
+            start = "$0000"
+            end = "$3FFF"
+
+            [[rom]]
+            start = "$F000"
+            end = "$FFFF"
+            image = "nmi.rom"
+
+            [[device]]
+            name = "via"
+            type = "via"
+            start = "$6000"
+            end = "$7FFF"
+            mask = "$000F"
+            interrupt = "{}"
+            "#,
+            interrupt
+        );
+        std::fs::write(&path, text).unwrap();
+
+        let mut board = MachineDescription::load(&path).unwrap().build().unwrap();
+        board.run_until(10_000).unwrap();
+        let count = board.memory().read().unwrap().get(0x10).unwrap();
+        assert!(expected.contains(&count), "{}: {} NMIs", interrupt, count);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -1 +1,3 @@
 pub mod cpu_memory;
+pub mod machines;
+pub mod peripherals;
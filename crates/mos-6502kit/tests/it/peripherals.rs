@@ -0,0 +1,135 @@
+use breadboard::BreadBoard;
+use common::Device;
+use peripherals::serial::BufferPort;
+use peripherals::via::{IFR, IRQ_T1};
+use peripherals::{Acia, Riot, Via, riot};
+use std::sync::{Arc, Mutex};
+
+/// VIA T1 프리런 인터럽트로 $10을 증가시키는 프로그램
+fn via_timer_board() -> (BreadBoard, Arc<Mutex<Via>>) {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x40,       // $0200 LDA #$40
+        0x8D, 0x0B, 0x60, // $0202 STA ACR     ; T1 프리런
+        0xA9, 0xC0,       // $0205 LDA #$C0
+        0x8D, 0x0E, 0x60, // $0207 STA IER     ; T1 인터럽트 허용
+        0xA9, 0x62,       // $020A LDA #98
+        0x8D, 0x04, 0x60, // $020C STA T1C_L
+        0xA9, 0x00,       // $020F LDA #0
+        0x8D, 0x05, 0x60, // $0211 STA T1C_H   ; 100사이클 주기로 시작
+        0x58,             // $0214 CLI
+        0x4C, 0x15, 0x02, // $0215 JMP $0215
+    ];
+    #[rustfmt::skip]
+    let handler = [
+        0xE6, 0x10,       // $0300 INC $10
+        0xAD, 0x04, 0x60, // $0302 LDA T1C_L   ; 플래그 클리어
+        0x40,             // $0305 RTI
+    ];
+    let mut board = BreadBoard::new();
+    board.load_program(0x0200, &program).unwrap();
+    board.load_program(0x0300, &handler).unwrap();
+    board.load_program(0xFFFE, &[0x00, 0x03]).unwrap();
+    let via = Arc::new(Mutex::new(Via::new()));
+    board
+        .map_device("via", 0x6000..=0x600F, 0x000F, via.clone())
+        .unwrap();
+    board.set_pc(0x0200);
+    (board, via)
+}
+
+#[test]
+fn test_via_timer_irq() {
+    let (mut board, via) = via_timer_board();
+    board.run_until(10_000).unwrap();
+
+    let count = board.memory().read().unwrap().get(0x10).unwrap();
+    // 시작 설정 약 20사이클 이후 100사이클마다 한 번
+    assert!((98..=100).contains(&count), "{} interrupts", count);
+    assert_eq!(via.lock().unwrap().peek(IFR) & IRQ_T1, 0, "acknowledged");
+}
+
+#[test]
+fn test_via_state_in_snapshot() {
+    let (mut board, via) = via_timer_board();
+    board.run_until(1_000).unwrap();
+    let snapshot = board.snapshot();
+    assert_eq!(snapshot.devices.len(), 1);
+    board.run_until(5_000).unwrap();
+    let counter = via.lock().unwrap().t1_counter();
+
+    let (mut restored, restored_via) = via_timer_board();
+    restored.restore(&snapshot).unwrap();
+    restored.run_until(5_000).unwrap();
+    assert_eq!(restored_via.lock().unwrap().t1_counter(), counter);
+    assert_eq!(restored.snapshot(), board.snapshot());
+}
+
+#[test]
+fn test_acia_echo() {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x1E,       // $0200 LDA #$1E
+        0x8D, 0x03, 0x50, // $0202 STA CONTROL ; 9600 8N1
+        0xA9, 0x0B,       // $0205 LDA #$0B
+        0x8D, 0x02, 0x50, // $0207 STA COMMAND ; DTR, IRQ 없음
+        0xAD, 0x01, 0x50, // $020A LDA STATUS
+        0x29, 0x08,       // $020D AND #RDRF
+        0xF0, 0xF9,       // $020F BEQ $020A
+        0xAE, 0x00, 0x50, // $0211 LDX DATA
+        0xAD, 0x01, 0x50, // $0214 LDA STATUS
+        0x29, 0x10,       // $0217 AND #TDRE
+        0xF0, 0xF9,       // $0219 BEQ $0214
+        0x8E, 0x00, 0x50, // $021B STX DATA
+        0x4C, 0x0A, 0x02, // $021E JMP $020A
+    ];
+    let port = BufferPort::new();
+    let mut board = BreadBoard::new();
+    board.load_program(0x0200, &program).unwrap();
+    let acia = Arc::new(Mutex::new(Acia::new().with_port(port.clone())));
+    // 콘솔로 등록하지 않은 ACIA - 호스트 포트 대신 수신 대기열로 입력
+    acia.lock().unwrap().receive(b"HI\r");
+    board
+        .map_device("acia", 0x5000..=0x5003, 0x0003, acia)
+        .unwrap();
+    board.set_pc(0x0200);
+
+    board.run_until(3_000).unwrap();
+    let early = port.take_output();
+    assert!(early.len() < 3, "limited by baud rate: {:?}", early);
+    board.run_until(10_000).unwrap();
+    assert_eq!([early, port.take_output()].concat(), b"HI\r");
+}
+
+#[test]
+fn test_riot_split_mapping() {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x42,       // $0200 LDA #$42
+        0x85, 0x80,       // $0202 STA $80     ; RIOT RAM
+        0xA9, 0x02,       // $0204 LDA #2
+        0x8D, 0x96, 0x02, // $0206 STA TIM64T
+        0xAD, 0x84, 0x02, // $0209 LDA INTIM
+        0xD0, 0xFB,       // $020C BNE $0209
+        0xA9, 0xAA,       // $020E LDA #$AA
+        0x85, 0x81,       // $0210 STA $81
+        0x4C, 0x12, 0x02, // $0212 JMP $0212
+    ];
+    let mut board = BreadBoard::new();
+    board.load_program(0x0200, &program).unwrap();
+    // Atari 2600 배치 - RAM $80-$FF, I/O $280-$29F
+    let riot = Arc::new(Mutex::new(Riot::new()));
+    board
+        .map_device("riot", 0x0080..=0x00FF, 0x007F, riot.clone())
+        .unwrap();
+    board
+        .map_device_at("riot-io", 0x0280..=0x029F, 0x001F, riot::IO, riot.clone())
+        .unwrap();
+    board.set_pc(0x0200);
+
+    board.run_until(100).unwrap();
+    assert_eq!(riot.lock().unwrap().ram()[..2], [0x42, 0x00]);
+    board.run_until(300).unwrap();
+    assert_eq!(riot.lock().unwrap().ram()[1], 0xAA, "timer reached zero");
+    assert_eq!(board.snapshot().devices.len(), 1, "one state per chip");
+}
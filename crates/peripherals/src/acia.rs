@@ -0,0 +1,386 @@
+//! 6551 ACIA (Asynchronous Communications Interface Adapter)
+//! 송수신 데이터, 상태/명령/제어 레지스터, CPU 사이클 기준 보드 레이트, 송수신 IRQ
+use crate::serial::SerialPort;
+use crate::state::{StateReader, StateWriter};
+use common::Result;
+use common::device::Device;
+use std::collections::VecDeque;
+
+/// 레지스터 번호 (RS1-RS0)
+pub const DATA: u16 = 0x0;
+/// 읽기는 상태, 쓰기는 프로그램 리셋
+pub const STATUS: u16 = 0x1;
+pub const COMMAND: u16 = 0x2;
+pub const CONTROL: u16 = 0x3;
+
+/// 상태 레지스터 비트
+pub const STATUS_PARITY: u8 = 0x01;
+pub const STATUS_FRAMING: u8 = 0x02;
+pub const STATUS_OVERRUN: u8 = 0x04;
+pub const STATUS_RDRF: u8 = 0x08;
+pub const STATUS_TDRE: u8 = 0x10;
+pub const STATUS_IRQ: u8 = 0x80;
+
+// 명령 레지스터 비트
+const COMMAND_DTR: u8 = 0x01;
+const COMMAND_RX_IRQ_DISABLE: u8 = 0x02;
+const COMMAND_TIC: u8 = 0x0C;
+const TIC_TX_IRQ: u8 = 0x04;
+const COMMAND_ECHO: u8 = 0x10;
+const COMMAND_PARITY: u8 = 0x20;
+
+// 제어 레지스터 비트
+const CONTROL_BAUD: u8 = 0x0F;
+const CONTROL_WORD_LENGTH: u8 = 0x60;
+const CONTROL_STOP_BITS: u8 = 0x80;
+
+/// 제어 레지스터 하위 4비트별 보드 레이트 - 0은 외부 16배 클럭 (115200으로 취급)
+const BAUD_RATES: [u64; 16] = [
+    115200, 50, 75, 110, 135, 150, 300, 600, 1200, 1800, 2400, 3600, 4800, 7200, 9600, 19200,
+];
+
+/// 6551 ACIA - 4개 레지스터, 오프셋 하위 2비트만 사용
+/// 문자 한 개의 송수신은 (시작 + 데이터 + 패리티 + 정지 비트) × 클럭 / 보드 레이트 사이클
+/// 수신은 RDRF가 비어 있을 때만 다음 바이트를 가져오므로 오버런 없이 흐름 제어됨
+/// 호스트 입력은 장치가 직접 읽지 않음 - 보드가 `poll_port`로 꺼내 `receive`로 넣음 (기록 가능)
+#[derive(Debug)]
+pub struct Acia {
+    clock_hz: u64,
+    status: u8,
+    command: u8,
+    control: u8,
+    rx_data: u8,
+    /// 송신 데이터 레지스터 - 비어 있으면 TDRE
+    tx_data: Option<u8>,
+    /// 송신 중인 바이트와 남은 사이클
+    tx_shift: Option<u8>,
+    tx_timer: u64,
+    /// 다음 문자를 받을 수 있을 때까지 남은 사이클
+    rx_timer: u64,
+    /// `receive`로 넣은 수신 대기 바이트
+    rx_queue: VecDeque<u8>,
+    port: Option<Box<dyn SerialPort>>,
+}
+
+impl Default for Acia {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Acia {
+    /// 하드웨어 리셋 상태, 1MHz 클럭, 포트 없음
+    pub fn new() -> Self {
+        Self {
+            clock_hz: 1_000_000,
+            status: 0,
+            command: COMMAND_RX_IRQ_DISABLE,
+            control: 0,
+            rx_data: 0,
+            tx_data: None,
+            tx_shift: None,
+            tx_timer: 0,
+            rx_timer: 0,
+            rx_queue: VecDeque::new(),
+            port: None,
+        }
+    }
+
+    /// 보드 레이트 계산에 쓰는 CPU 클럭
+    pub fn with_clock(mut self, clock_hz: u64) -> Self {
+        self.clock_hz = clock_hz.max(1);
+        self
+    }
+
+    pub fn with_port(mut self, port: impl SerialPort + 'static) -> Self {
+        self.set_port(port);
+        self
+    }
+
+    /// 호스트 쪽 연결 교체
+    pub fn set_port(&mut self, port: impl SerialPort + 'static) {
+        self.port = Some(Box::new(port));
+    }
+
+    /// 수신할 바이트를 대기열에 넣음
+    pub fn receive(&mut self, bytes: &[u8]) {
+        self.rx_queue.extend(bytes);
+    }
+
+    /// 호스트 포트에 도착한 바이트 - 수신 대기열이 비었을 때만 한 바이트씩
+    pub fn poll_port(&mut self) -> Option<u8> {
+        if !self.rx_queue.is_empty() {
+            return None;
+        }
+        self.port.as_mut().and_then(|port| port.receive())
+    }
+
+    pub fn baud_rate(&self) -> u64 {
+        BAUD_RATES[(self.control & CONTROL_BAUD) as usize]
+    }
+
+    /// 한 문자 프레임의 비트 수
+    pub fn frame_bits(&self) -> u64 {
+        let data = 8 - ((self.control & CONTROL_WORD_LENGTH) >> 5) as u64;
+        let parity = (self.command & COMMAND_PARITY != 0) as u64;
+        let stop = if self.control & CONTROL_STOP_BITS != 0 {
+            2
+        } else {
+            1
+        };
+        1 + data + parity + stop
+    }
+
+    /// 한 문자를 송수신하는 데 걸리는 CPU 사이클
+    pub fn character_cycles(&self) -> u64 {
+        (self.frame_bits() * self.clock_hz / self.baud_rate()).max(1)
+    }
+
+    fn tx_irq_enabled(&self) -> bool {
+        self.command & COMMAND_TIC == TIC_TX_IRQ
+    }
+
+    fn send(&mut self, byte: u8) {
+        if let Some(port) = &mut self.port {
+            port.send(byte);
+        }
+    }
+
+    fn tick_transmitter(&mut self, mut cycles: u64) {
+        loop {
+            if self.tx_shift.is_none() {
+                let Some(byte) = self.tx_data.take() else {
+                    return;
+                };
+                // 데이터 레지스터가 시프트 레지스터로 옮겨지면 TDRE
+                self.tx_shift = Some(byte);
+                self.tx_timer = self.character_cycles();
+                if self.tx_irq_enabled() {
+                    self.status |= STATUS_IRQ;
+                }
+            }
+            if cycles < self.tx_timer {
+                self.tx_timer -= cycles;
+                return;
+            }
+            cycles -= self.tx_timer;
+            self.tx_timer = 0;
+            if let Some(byte) = self.tx_shift.take() {
+                self.send(byte);
+            }
+        }
+    }
+
+    fn tick_receiver(&mut self, cycles: u64) {
+        self.rx_timer = self.rx_timer.saturating_sub(cycles);
+        if self.command & COMMAND_DTR == 0 || self.rx_timer > 0 || self.status & STATUS_RDRF != 0 {
+            return;
+        }
+        let Some(byte) = self.rx_queue.pop_front() else {
+            return;
+        };
+        self.rx_data = byte;
+        self.status |= STATUS_RDRF;
+        self.rx_timer = self.character_cycles();
+        if self.command & COMMAND_RX_IRQ_DISABLE == 0 {
+            self.status |= STATUS_IRQ;
+        }
+        if self.command & COMMAND_ECHO != 0 && self.command & COMMAND_TIC == 0 {
+            self.send(byte);
+        }
+    }
+}
+
+impl Device for Acia {
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x03 {
+            DATA => self.rx_data,
+            STATUS => match self.tx_data {
+                Some(_) => self.status,
+                None => self.status | STATUS_TDRE,
+            },
+            COMMAND => self.command,
+            _ => self.control,
+        }
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        let value = self.peek(offset);
+        match offset & 0x03 {
+            DATA => self.status &= !(STATUS_RDRF | STATUS_OVERRUN | STATUS_FRAMING | STATUS_PARITY),
+            STATUS => self.status &= !STATUS_IRQ,
+            _ => {}
+        }
+        value
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        match offset & 0x03 {
+            DATA => self.tx_data = Some(value),
+            STATUS => {
+                // 프로그램 리셋 - 명령 하위 5비트와 오버런 클리어
+                self.command &= !0x1F;
+                self.status &= !STATUS_OVERRUN;
+            }
+            COMMAND => {
+                self.command = value;
+                if self.tx_irq_enabled() && self.tx_data.is_none() {
+                    self.status |= STATUS_IRQ;
+                }
+            }
+            _ => self.control = value,
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        self.tick_transmitter(cycles);
+        self.tick_receiver(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        self.status & STATUS_IRQ != 0
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut writer = StateWriter::default();
+        writer
+            .bytes(&[self.status, self.command, self.control, self.rx_data])
+            .bool(self.tx_data.is_some())
+            .u8(self.tx_data.unwrap_or(0))
+            .bool(self.tx_shift.is_some())
+            .u8(self.tx_shift.unwrap_or(0))
+            .u32(self.tx_timer as u32)
+            .u32(self.rx_timer as u32)
+            .u32(self.rx_queue.len() as u32);
+        for &byte in &self.rx_queue {
+            writer.u8(byte);
+        }
+        writer.finish()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let mut reader = StateReader::new(state);
+        let [status, command, control, rx_data] = reader.bytes(4)?.try_into().unwrap();
+        let tx_data = reader.bool()?.then_some(reader.u8()?);
+        let tx_shift = reader.bool()?.then_some(reader.u8()?);
+        let tx_timer = reader.u32()? as u64;
+        let rx_timer = reader.u32()? as u64;
+        let len = reader.u32()? as usize;
+        let rx_queue = reader.bytes(len)?.iter().copied().collect();
+        reader.finish()?;
+        self.status = status;
+        self.command = command;
+        self.control = control;
+        self.rx_data = rx_data;
+        self.tx_data = tx_data;
+        self.tx_shift = tx_shift;
+        self.tx_timer = tx_timer;
+        self.rx_timer = rx_timer;
+        self.rx_queue = rx_queue;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::BufferPort;
+
+    // 9600 보드, 8N1 - 1MHz에서 문자당 1041 사이클
+    fn acia_9600(port: &BufferPort) -> Acia {
+        let mut acia = Acia::new().with_port(port.clone());
+        acia.write(CONTROL, 0x1E);
+        acia.write(COMMAND, 0x0B);
+        acia
+    }
+
+    #[test]
+    fn test_transmit_timing() {
+        let port = BufferPort::new();
+        let mut acia = acia_9600(&port);
+        assert_eq!(acia.character_cycles(), 1041);
+
+        acia.write(DATA, b'H');
+        assert_eq!(acia.peek(STATUS) & STATUS_TDRE, 0);
+        acia.tick(1);
+        assert_ne!(
+            acia.peek(STATUS) & STATUS_TDRE,
+            0,
+            "moved to shift register"
+        );
+        acia.write(DATA, b'I');
+        acia.tick(1039);
+        assert!(port.take_output().is_empty());
+        acia.tick(1);
+        assert_eq!(port.take_output(), b"H");
+        acia.tick(2 * 1041);
+        assert_eq!(port.take_output(), b"I");
+    }
+
+    #[test]
+    fn test_receive_with_irq() {
+        let port = BufferPort::new();
+        let mut acia = acia_9600(&port);
+        // 수신 IRQ 허용
+        acia.write(COMMAND, 0x09);
+        port.push_input(b"AB");
+        acia.tick(1);
+        assert!(!acia.irq(), "port is polled by the board, not by tick");
+        let byte = acia.poll_port().unwrap();
+        acia.receive(&[byte]);
+        assert_eq!(acia.poll_port(), None, "one byte ahead");
+        acia.tick(1);
+        assert!(acia.irq());
+        assert_eq!(acia.read(STATUS) & STATUS_RDRF, STATUS_RDRF);
+        assert!(!acia.irq(), "status read clears IRQ");
+
+        acia.tick(5000);
+        let byte = acia.poll_port().unwrap();
+        acia.receive(&[byte]);
+        assert_eq!(acia.read(DATA), b'A', "held until read");
+        assert_eq!(acia.peek(STATUS) & STATUS_RDRF, 0);
+        acia.tick(1);
+        assert_eq!(acia.read(DATA), b'B', "character time already passed");
+
+        acia.receive(b"C");
+        acia.tick(1);
+        assert_eq!(
+            acia.peek(STATUS) & STATUS_RDRF,
+            0,
+            "one character per frame"
+        );
+        acia.tick(1040);
+        assert_eq!(acia.read(DATA), b'C');
+    }
+
+    #[test]
+    fn test_transmit_irq_and_programmed_reset() {
+        let port = BufferPort::new();
+        let mut acia = acia_9600(&port);
+        acia.write(COMMAND, 0x07);
+        assert!(acia.irq(), "transmitter empty");
+        acia.read(STATUS);
+        acia.write(DATA, 0x55);
+        acia.tick(1);
+        assert!(acia.irq());
+
+        acia.write(STATUS, 0);
+        assert_eq!(acia.peek(COMMAND), 0x00);
+        assert_eq!(acia.peek(CONTROL), 0x1E, "control kept");
+    }
+
+    #[test]
+    fn test_save_state() {
+        let mut acia = acia_9600(&BufferPort::new());
+        acia.receive(b"XYZ");
+        acia.tick(1);
+        acia.write(DATA, b'!');
+        let state = acia.save_state();
+
+        let mut restored = Acia::new();
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored.save_state(), state);
+        assert_eq!(restored.read(DATA), b'X');
+        assert!(restored.load_state(&state[..5]).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+//! 주변 장치 칩 - 주소 디코더에 매핑하는 I/O 장치 모델
+
+pub mod acia;
+pub mod pia;
+pub mod riot;
+pub mod serial;
+mod state;
+pub mod via;
+
+pub use acia::Acia;
+pub use pia::Pia;
+pub use riot::Riot;
+pub use serial::SerialPort;
+pub use via::Via;
@@ -0,0 +1,396 @@
+//! 6520/6821 PIA (Peripheral Interface Adapter)
+//! 포트 A/B와 DDR, 제어 레지스터, CA1/CA2/CB1/CB2 인터럽트와 핸드셰이크
+use crate::state::{StateReader, StateWriter};
+use common::Result;
+use common::device::Device;
+
+/// 레지스터 번호 (RS1-RS0) - 데이터 레지스터는 CR 비트 2가 0이면 DDR
+pub const PRA: u16 = 0x0;
+pub const CRA: u16 = 0x1;
+pub const PRB: u16 = 0x2;
+pub const CRB: u16 = 0x3;
+
+/// 제어 레지스터 비트
+pub const CR_C1_IRQ: u8 = 0x01;
+/// C1 상승 엣지에서 플래그 (0이면 하강 엣지)
+pub const CR_C1_RISING: u8 = 0x02;
+/// 1이면 출력 레지스터, 0이면 DDR 선택
+pub const CR_PORT: u8 = 0x04;
+/// C2 입력 모드의 인터럽트 허용
+pub const CR_C2_IRQ: u8 = 0x08;
+pub const CR_IRQ2: u8 = 0x40;
+pub const CR_IRQ1: u8 = 0x80;
+
+// C2 출력 모드 (CR 비트 5-3)
+const HANDSHAKE: u8 = 4;
+const PULSE: u8 = 5;
+const LOW: u8 = 6;
+const HIGH: u8 = 7;
+
+/// 포트 한쪽 - A와 B는 핸드셰이크 시점만 다름 (A는 읽기, B는 쓰기)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Side {
+    or: u8,
+    ddr: u8,
+    /// 외부에서 구동하는 핀 레벨
+    pins: u8,
+    /// 비트 5-0
+    cr: u8,
+    irq1: bool,
+    irq2: bool,
+    c1: bool,
+    /// C2 입력 핀 레벨
+    c2: bool,
+    /// 핸드셰이크/펄스 모드의 C2 출력
+    c2_out: bool,
+    c2_pulse: bool,
+}
+
+impl Side {
+    fn new() -> Self {
+        Self {
+            or: 0,
+            ddr: 0,
+            pins: 0xFF,
+            cr: 0,
+            irq1: false,
+            irq2: false,
+            c1: true,
+            c2: true,
+            c2_out: true,
+            c2_pulse: false,
+        }
+    }
+
+    fn port(&self) -> u8 {
+        (self.or & self.ddr) | (self.pins & !self.ddr)
+    }
+
+    fn c2_mode(&self) -> u8 {
+        (self.cr >> 3) & 0x07
+    }
+
+    fn c2_is_output(&self) -> bool {
+        self.cr & 0x20 != 0
+    }
+
+    fn control(&self) -> u8 {
+        self.cr | (self.irq1 as u8) << 7 | (self.irq2 as u8) << 6
+    }
+
+    fn data(&self) -> u8 {
+        match self.cr & CR_PORT {
+            0 => self.ddr,
+            _ => self.port(),
+        }
+    }
+
+    fn set_c1(&mut self, level: bool) {
+        if level != self.c1 && level == (self.cr & CR_C1_RISING != 0) {
+            self.irq1 = true;
+            if self.c2_mode() == HANDSHAKE {
+                self.c2_out = true;
+            }
+        }
+        self.c1 = level;
+    }
+
+    fn set_c2(&mut self, level: bool) {
+        if !self.c2_is_output() && level != self.c2 && level == (self.cr & 0x10 != 0) {
+            self.irq2 = true;
+        }
+        self.c2 = level;
+    }
+
+    fn c2(&self) -> bool {
+        match self.c2_mode() {
+            HANDSHAKE | PULSE => self.c2_out,
+            LOW => false,
+            HIGH => true,
+            _ => self.c2,
+        }
+    }
+
+    fn handshake(&mut self) {
+        match self.c2_mode() {
+            HANDSHAKE => self.c2_out = false,
+            PULSE => {
+                self.c2_out = false;
+                self.c2_pulse = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// 데이터 레지스터 읽기 - 출력 레지스터면 플래그 클리어
+    fn read_data(&mut self) -> u8 {
+        let value = self.data();
+        if self.cr & CR_PORT != 0 {
+            self.irq1 = false;
+            self.irq2 = false;
+        }
+        value
+    }
+
+    fn write_data(&mut self, value: u8) {
+        match self.cr & CR_PORT {
+            0 => self.ddr = value,
+            _ => self.or = value,
+        }
+    }
+
+    fn write_control(&mut self, value: u8) {
+        self.cr = value & 0x3F;
+        if self.c2_is_output() {
+            self.irq2 = false;
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.c2_pulse {
+            self.c2_pulse = false;
+            self.c2_out = true;
+        }
+    }
+
+    fn irq(&self) -> bool {
+        (self.irq1 && self.cr & CR_C1_IRQ != 0)
+            || (self.irq2 && self.cr & CR_C2_IRQ != 0 && !self.c2_is_output())
+    }
+
+    fn save(&self, writer: &mut StateWriter) {
+        writer
+            .bytes(&[self.or, self.ddr, self.pins, self.cr])
+            .bytes(&[
+                self.irq1 as u8,
+                self.irq2 as u8,
+                self.c1 as u8,
+                self.c2 as u8,
+                self.c2_out as u8,
+                self.c2_pulse as u8,
+            ]);
+    }
+
+    fn load(reader: &mut StateReader) -> Result<Self> {
+        let [or, ddr, pins, cr] = reader.bytes(4)?.try_into().unwrap();
+        Ok(Self {
+            or,
+            ddr,
+            pins,
+            cr,
+            irq1: reader.bool()?,
+            irq2: reader.bool()?,
+            c1: reader.bool()?,
+            c2: reader.bool()?,
+            c2_out: reader.bool()?,
+            c2_pulse: reader.bool()?,
+        })
+    }
+}
+
+/// 6520/6821 PIA - 4개 레지스터, 오프셋 하위 2비트만 사용
+/// IRQA/IRQB 두 출력은 `irq`에서 하나로 묶임
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pia {
+    a: Side,
+    b: Side,
+}
+
+impl Default for Pia {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pia {
+    pub fn new() -> Self {
+        Self {
+            a: Side::new(),
+            b: Side::new(),
+        }
+    }
+
+    /// 포트 A 핀 레벨 - 출력 핀은 ORA, 입력 핀은 외부 구동 값
+    pub fn port_a(&self) -> u8 {
+        self.a.port()
+    }
+
+    pub fn port_b(&self) -> u8 {
+        self.b.port()
+    }
+
+    pub fn set_port_a(&mut self, pins: u8) {
+        self.a.pins = pins;
+    }
+
+    pub fn set_port_b(&mut self, pins: u8) {
+        self.b.pins = pins;
+    }
+
+    pub fn set_ca1(&mut self, level: bool) {
+        self.a.set_c1(level);
+    }
+
+    pub fn set_ca2(&mut self, level: bool) {
+        self.a.set_c2(level);
+    }
+
+    pub fn set_cb1(&mut self, level: bool) {
+        self.b.set_c1(level);
+    }
+
+    pub fn set_cb2(&mut self, level: bool) {
+        self.b.set_c2(level);
+    }
+
+    /// CA2 레벨 - 입력 모드면 외부 구동 값
+    pub fn ca2(&self) -> bool {
+        self.a.c2()
+    }
+
+    pub fn cb2(&self) -> bool {
+        self.b.c2()
+    }
+
+    pub fn irqa(&self) -> bool {
+        self.a.irq()
+    }
+
+    pub fn irqb(&self) -> bool {
+        self.b.irq()
+    }
+}
+
+impl Device for Pia {
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x03 {
+            PRA => self.a.data(),
+            CRA => self.a.control(),
+            PRB => self.b.data(),
+            _ => self.b.control(),
+        }
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        match offset & 0x03 {
+            PRA => {
+                if self.a.cr & CR_PORT != 0 {
+                    self.a.handshake();
+                }
+                self.a.read_data()
+            }
+            PRB => self.b.read_data(),
+            _ => self.peek(offset),
+        }
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        match offset & 0x03 {
+            PRA => self.a.write_data(value),
+            CRA => self.a.write_control(value),
+            PRB => {
+                self.b.write_data(value);
+                if self.b.cr & CR_PORT != 0 {
+                    self.b.handshake();
+                }
+            }
+            _ => self.b.write_control(value),
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        if cycles > 0 {
+            self.a.tick();
+            self.b.tick();
+        }
+    }
+
+    fn irq(&self) -> bool {
+        self.irqa() || self.irqb()
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut writer = StateWriter::default();
+        self.a.save(&mut writer);
+        self.b.save(&mut writer);
+        writer.finish()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let mut reader = StateReader::new(state);
+        let a = Side::load(&mut reader)?;
+        let b = Side::load(&mut reader)?;
+        reader.finish()?;
+        *self = Self { a, b };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ddr_selection() {
+        let mut pia = Pia::new();
+        pia.write(PRA, 0x0F);
+        assert_eq!(pia.read(PRA), 0x0F, "DDR while CR bit 2 is clear");
+        pia.write(CRA, CR_PORT);
+        pia.write(PRA, 0x05);
+        pia.set_port_a(0xA0);
+        assert_eq!(pia.read(PRA), 0xA5);
+        assert_eq!(pia.port_a(), 0xA5);
+    }
+
+    #[test]
+    fn test_ca1_interrupt_and_ca2_handshake() {
+        let mut pia = Pia::new();
+        // CA1 상승 엣지 인터럽트, CA2 핸드셰이크 출력
+        pia.write(CRA, CR_PORT | CR_C1_IRQ | CR_C1_RISING | HANDSHAKE << 3);
+        pia.set_ca1(false);
+        assert!(!pia.irq(), "falling edge ignored");
+        pia.set_port_a(0xC1);
+        pia.set_ca1(true);
+        assert!(pia.irqa());
+        assert_eq!(pia.peek(CRA) & CR_IRQ1, CR_IRQ1);
+
+        assert_eq!(pia.read(PRA), 0xC1);
+        assert!(!pia.irq(), "reading PRA clears the flag");
+        assert!(!pia.ca2(), "data taken");
+        pia.set_ca1(false);
+        pia.set_ca1(true);
+        assert!(pia.ca2(), "next data ready");
+    }
+
+    #[test]
+    fn test_cb2_pulse_on_write_and_c2_input() {
+        let mut pia = Pia::new();
+        pia.write(CRB, CR_PORT | PULSE << 3);
+        pia.write(PRB, 0x41);
+        assert!(!pia.cb2());
+        pia.tick(1);
+        assert!(pia.cb2());
+
+        // CB2 입력, 하강 엣지 인터럽트
+        pia.write(CRB, CR_PORT | CR_C2_IRQ);
+        pia.set_cb2(false);
+        assert!(pia.irqb());
+        assert_eq!(pia.peek(CRB) & CR_IRQ2, CR_IRQ2);
+        pia.read(PRB);
+        assert!(!pia.irq());
+    }
+
+    #[test]
+    fn test_save_state() {
+        let mut pia = Pia::new();
+        pia.write(CRA, CR_PORT | CR_C1_IRQ);
+        pia.set_ca1(false);
+        pia.write(PRB, 0xFF);
+        let state = pia.save_state();
+
+        let mut restored = Pia::new();
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored, pia);
+        assert!(restored.load_state(&state[..3]).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! 6532 RIOT (RAM-I/O-Timer)
+//! 128바이트 RAM, 포트 A/B와 DDR, 분주 인터벌 타이머, PA7 엣지 인터럽트
+use crate::state::{StateReader, StateWriter};
+use common::Result;
+use common::device::Device;
+
+pub const RAM_SIZE: usize = 128;
+
+/// RS 선택 - 오프셋 비트 7이 0이면 RAM (A6-A0), 1이면 I/O (A4-A0)
+/// RAM과 I/O가 떨어진 기판(Atari 2600의 $80/$280)은 I/O 범위를 base $80으로 매핑
+pub const IO: u16 = 0x80;
+
+/// I/O 레지스터 (Atari 2600 이름)
+pub const SWCHA: u16 = 0x80;
+pub const SWACNT: u16 = 0x81;
+pub const SWCHB: u16 = 0x82;
+pub const SWBCNT: u16 = 0x83;
+/// 타이머 읽기 (A3=1이면 타이머 인터럽트 허용)
+pub const INTIM: u16 = 0x84;
+/// 인터럽트 플래그 읽기 - PA7 플래그 클리어
+pub const INSTAT: u16 = 0x85;
+/// 타이머 쓰기 - 1/8/64/1024 사이클마다 감소, A3=1이면 인터럽트 허용
+pub const TIM1T: u16 = 0x94;
+pub const TIM8T: u16 = 0x95;
+pub const TIM64T: u16 = 0x96;
+pub const T1024T: u16 = 0x97;
+/// PA7 엣지 설정 쓰기 - A0=1 상승 엣지, A1=1 인터럽트 허용
+pub const EDGE_CTRL: u16 = 0x84;
+
+/// INSTAT 비트
+pub const IRQ_TIMER: u8 = 0x80;
+pub const IRQ_PA7: u8 = 0x40;
+
+const PRESCALERS: [u16; 4] = [1, 8, 64, 1024];
+
+/// 6532 RIOT
+/// 타이머는 값이 0을 지나 $FF가 되면 플래그를 세우고 그 뒤로는 매 사이클 감소 (다시 쓸 때까지)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Riot {
+    ram: [u8; RAM_SIZE],
+    ora: u8,
+    orb: u8,
+    ddra: u8,
+    ddrb: u8,
+    /// 외부에서 구동하는 핀 레벨 (입력 핀, 풀업으로 기본 $FF)
+    pins_a: u8,
+    pins_b: u8,
+
+    timer: u8,
+    interval: u16,
+    /// 다음 감소까지 남은 사이클
+    prescale: u16,
+    /// 0을 지나 매 사이클 감소 중
+    expired: bool,
+    timer_irq: bool,
+
+    /// INSTAT (IRQ_TIMER | IRQ_PA7)
+    flags: u8,
+    edge_rising: bool,
+    pa7_irq: bool,
+}
+
+impl Default for Riot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Riot {
+    pub fn new() -> Self {
+        Self {
+            ram: [0; RAM_SIZE],
+            ora: 0,
+            orb: 0,
+            ddra: 0,
+            ddrb: 0,
+            pins_a: 0xFF,
+            pins_b: 0xFF,
+            timer: 0,
+            interval: 1024,
+            prescale: 1024,
+            expired: false,
+            timer_irq: false,
+            flags: 0,
+            edge_rising: false,
+            pa7_irq: false,
+        }
+    }
+
+    /// 포트 A 핀 레벨 - 출력 핀은 ORA, 입력 핀은 외부 구동 값
+    pub fn port_a(&self) -> u8 {
+        (self.ora & self.ddra) | (self.pins_a & !self.ddra)
+    }
+
+    pub fn port_b(&self) -> u8 {
+        (self.orb & self.ddrb) | (self.pins_b & !self.ddrb)
+    }
+
+    /// 포트 A 입력 핀 구동 - PA7 엣지 검출
+    pub fn set_port_a(&mut self, pins: u8) {
+        self.update_port_a(|riot| riot.pins_a = pins);
+    }
+
+    pub fn set_port_b(&mut self, pins: u8) {
+        self.pins_b = pins;
+    }
+
+    pub fn timer(&self) -> u8 {
+        self.timer
+    }
+
+    pub fn ram(&self) -> &[u8; RAM_SIZE] {
+        &self.ram
+    }
+
+    // 포트 A 변경 전후의 PA7로 엣지 플래그 설정
+    fn update_port_a(&mut self, change: impl FnOnce(&mut Self)) {
+        let before = self.port_a() & 0x80 != 0;
+        change(self);
+        let after = self.port_a() & 0x80 != 0;
+        if before != after && after == self.edge_rising {
+            self.flags |= IRQ_PA7;
+        }
+    }
+
+    fn write_timer(&mut self, offset: u16, value: u8) {
+        self.timer = value;
+        self.interval = PRESCALERS[(offset & 0x03) as usize];
+        self.prescale = self.interval;
+        self.expired = false;
+        self.timer_irq = offset & 0x08 != 0;
+        self.flags &= !IRQ_TIMER;
+    }
+
+    fn clock(&mut self) {
+        if !self.expired {
+            self.prescale -= 1;
+            if self.prescale > 0 {
+                return;
+            }
+            self.prescale = self.interval;
+        }
+        if self.timer == 0 {
+            self.expired = true;
+            self.flags |= IRQ_TIMER;
+        }
+        self.timer = self.timer.wrapping_sub(1);
+    }
+}
+
+impl Device for Riot {
+    fn peek(&self, offset: u16) -> u8 {
+        if offset & IO == 0 {
+            return self.ram[(offset & 0x7F) as usize];
+        }
+        if offset & 0x04 == 0 {
+            return match offset & 0x03 {
+                0 => self.port_a(),
+                1 => self.ddra,
+                2 => self.port_b(),
+                _ => self.ddrb,
+            };
+        }
+        match offset & 0x01 {
+            0 => self.timer,
+            _ => self.flags,
+        }
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        let value = self.peek(offset);
+        if offset & IO != 0 && offset & 0x04 != 0 {
+            if offset & 0x01 == 0 {
+                self.timer_irq = offset & 0x08 != 0;
+                self.flags &= !IRQ_TIMER;
+            } else {
+                self.flags &= !IRQ_PA7;
+            }
+        }
+        value
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        if offset & IO == 0 {
+            self.ram[(offset & 0x7F) as usize] = value;
+            return;
+        }
+        if offset & 0x04 == 0 {
+            match offset & 0x03 {
+                0 => self.update_port_a(|riot| riot.ora = value),
+                1 => self.update_port_a(|riot| riot.ddra = value),
+                2 => self.orb = value,
+                _ => self.ddrb = value,
+            }
+        } else if offset & 0x10 != 0 {
+            self.write_timer(offset, value);
+        } else {
+            self.edge_rising = offset & 0x01 != 0;
+            self.pa7_irq = offset & 0x02 != 0;
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        for _ in 0..cycles {
+            self.clock();
+        }
+    }
+
+    fn irq(&self) -> bool {
+        (self.timer_irq && self.flags & IRQ_TIMER != 0)
+            || (self.pa7_irq && self.flags & IRQ_PA7 != 0)
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        StateWriter::default()
+            .bytes(&self.ram)
+            .bytes(&[self.ora, self.orb, self.ddra, self.ddrb])
+            .bytes(&[self.pins_a, self.pins_b])
+            .u8(self.timer)
+            .u16(self.interval)
+            .u16(self.prescale)
+            .bool(self.expired)
+            .bool(self.timer_irq)
+            .u8(self.flags)
+            .bool(self.edge_rising)
+            .bool(self.pa7_irq)
+            .finish()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let mut reader = StateReader::new(state);
+        let mut riot = Riot::new();
+        riot.ram.copy_from_slice(reader.bytes(RAM_SIZE)?);
+        [riot.ora, riot.orb, riot.ddra, riot.ddrb] = reader.bytes(4)?.try_into().unwrap();
+        [riot.pins_a, riot.pins_b] = reader.bytes(2)?.try_into().unwrap();
+        riot.timer = reader.u8()?;
+        riot.interval = reader.u16()?;
+        riot.prescale = reader.u16()?;
+        riot.expired = reader.bool()?;
+        riot.timer_irq = reader.bool()?;
+        riot.flags = reader.u8()?;
+        riot.edge_rising = reader.bool()?;
+        riot.pa7_irq = reader.bool()?;
+        reader.finish()?;
+        *self = riot;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ram_and_ports() {
+        let mut riot = Riot::new();
+        riot.write(0x7F, 0x42);
+        assert_eq!(riot.read(0x7F), 0x42);
+        assert_eq!(riot.read(SWCHA), 0xFF, "inputs pulled up");
+
+        riot.write(SWACNT, 0x0F);
+        riot.write(SWCHA, 0x05);
+        riot.set_port_a(0xA0);
+        assert_eq!(riot.port_a(), 0xA5);
+        riot.write(SWBCNT, 0xFF);
+        riot.write(SWCHB, 0x3C);
+        assert_eq!(riot.read(SWCHB), 0x3C);
+    }
+
+    #[test]
+    fn test_timer_prescaler_and_irq() {
+        let mut riot = Riot::new();
+        // TIM8T에 3, 인터럽트 허용
+        riot.write(TIM8T | 0x08, 3);
+        riot.tick(8);
+        assert_eq!(riot.peek(INTIM), 2);
+        riot.tick(2 * 8);
+        assert_eq!(riot.peek(INTIM), 0);
+        assert!(!riot.irq());
+        riot.tick(8);
+        assert!(riot.irq());
+        assert_eq!(riot.peek(INTIM), 0xFF);
+        riot.tick(5);
+        assert_eq!(riot.peek(INTIM), 0xFA, "one per cycle after expiry");
+
+        assert_eq!(riot.read(INSTAT) & IRQ_TIMER, IRQ_TIMER);
+        riot.read(INTIM);
+        assert!(!riot.irq(), "reading INTIM with A3=0 clears and disables");
+    }
+
+    #[test]
+    fn test_pa7_edge_interrupt() {
+        let mut riot = Riot::new();
+        // 상승 엣지, 인터럽트 허용
+        riot.write(EDGE_CTRL | 0x03, 0);
+        riot.set_port_a(0x7F);
+        assert!(!riot.irq(), "falling edge ignored");
+        riot.set_port_a(0xFF);
+        assert!(riot.irq());
+        assert_eq!(riot.read(INSTAT), IRQ_PA7);
+        assert!(!riot.irq());
+
+        // 출력으로 바꿔 구동해도 엣지
+        riot.write(SWACNT, 0x80);
+        riot.write(SWCHA, 0x00);
+        riot.write(SWCHA, 0x80);
+        assert!(riot.irq());
+    }
+
+    #[test]
+    fn test_save_state() {
+        let mut riot = Riot::new();
+        riot.write(0x10, 0x99);
+        riot.write(T1024T | 0x08, 2);
+        riot.tick(100);
+        let state = riot.save_state();
+
+        let mut restored = Riot::new();
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored, riot);
+        assert!(restored.load_state(&state[1..]).is_err());
+    }
+}
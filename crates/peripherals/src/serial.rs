@@ -0,0 +1,260 @@
+//! 시리얼 장치와 호스트를 잇는 포트 - 표준 입출력, 파일, 외부 프로세스, 가상 터미널, 메모리 버퍼
+use common::Result;
+use error::Error;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// 시리얼 장치의 바깥쪽 끝 - 장치가 보드 레이트에 맞춰 호출
+pub trait SerialPort: Debug + Send {
+    /// 도착한 바이트 하나 (없으면 None, 대기하지 않음)
+    fn receive(&mut self) -> Option<u8>;
+
+    /// 장치가 송신한 바이트
+    fn send(&mut self, byte: u8);
+}
+
+// 읽기 스레드가 보낸 바이트를 비차단으로 꺼냄
+fn spawn_reader(mut reader: impl Read + Send + 'static) -> Receiver<u8> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = [0u8; 256];
+        while let Ok(count) = reader.read(&mut buffer) {
+            if count == 0 || buffer[..count].iter().any(|&byte| tx.send(byte).is_err()) {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// 호스트 표준 입출력 - 입력의 LF는 CR로 바꿔 전달 (터미널 Enter 키)
+#[derive(Debug)]
+pub struct StdioPort {
+    input: Receiver<u8>,
+}
+
+impl Default for StdioPort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdioPort {
+    pub fn new() -> Self {
+        Self {
+            input: spawn_reader(std::io::stdin()),
+        }
+    }
+}
+
+impl SerialPort for StdioPort {
+    fn receive(&mut self) -> Option<u8> {
+        self.input
+            .try_recv()
+            .ok()
+            .map(|byte| if byte == b'\n' { b'\r' } else { byte })
+    }
+
+    fn send(&mut self, byte: u8) {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(&[byte]);
+        let _ = stdout.flush();
+    }
+}
+
+/// 파일 입출력 - 입력 파일 내용을 차례로 수신하고 송신은 출력 파일에 기록
+#[derive(Debug)]
+pub struct FilePort {
+    input: VecDeque<u8>,
+    output: Option<File>,
+}
+
+impl FilePort {
+    pub fn open(input: Option<&Path>, output: Option<&Path>) -> Result<Self> {
+        let input = match input {
+            Some(path) => std::fs::read(path)?.into(),
+            None => VecDeque::new(),
+        };
+        let output = output.map(File::create).transpose()?;
+        Ok(Self { input, output })
+    }
+}
+
+impl SerialPort for FilePort {
+    fn receive(&mut self) -> Option<u8> {
+        self.input.pop_front()
+    }
+
+    fn send(&mut self, byte: u8) {
+        if let Some(file) = &mut self.output {
+            let _ = file.write_all(&[byte]);
+        }
+    }
+}
+
+/// 외부 프로세스의 표준 입출력에 연결 - 프로세스는 포트와 함께 종료
+#[derive(Debug)]
+pub struct ProcessPort {
+    child: Child,
+    stdin: ChildStdin,
+    output: Receiver<u8>,
+}
+
+impl ProcessPort {
+    pub fn spawn(mut command: Command) -> Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let output = spawn_reader(child.stdout.take().expect("piped stdout"));
+        Ok(Self {
+            child,
+            stdin,
+            output,
+        })
+    }
+}
+
+impl SerialPort for ProcessPort {
+    fn receive(&mut self) -> Option<u8> {
+        self.output.try_recv().ok()
+    }
+
+    fn send(&mut self, byte: u8) {
+        let _ = self.stdin.write_all(&[byte]);
+        let _ = self.stdin.flush();
+    }
+}
+
+impl Drop for ProcessPort {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// 가상 터미널 - `socat`이 만든 pty를 screen/minicom 등으로 열어 사용
+#[derive(Debug)]
+pub struct PtyPort {
+    process: ProcessPort,
+    path: PathBuf,
+}
+
+impl PtyPort {
+    /// pty를 만들고 경로를 알아냄 (`socat` 필요)
+    pub fn open() -> Result<Self> {
+        let mut command = Command::new("socat");
+        command
+            .args(["-d", "-d", "pty,raw,echo=0", "stdio"])
+            .stderr(Stdio::piped());
+        let mut process = ProcessPort::spawn(command)?;
+        let stderr = process.child.stderr.take().expect("piped stderr");
+        // socat은 준비되면 "N PTY is /dev/pts/3"을 출력
+        let path = BufReader::new(stderr)
+            .lines()
+            .map_while(std::result::Result::ok)
+            .find_map(|line| {
+                line.split_once("PTY is ")
+                    .map(|(_, path)| PathBuf::from(path.trim()))
+            })
+            .ok_or_else(|| Error::Internal("socat did not report a pty".to_string()))?;
+        Ok(Self { process, path })
+    }
+
+    /// 터미널 프로그램으로 열 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl SerialPort for PtyPort {
+    fn receive(&mut self) -> Option<u8> {
+        self.process.receive()
+    }
+
+    fn send(&mut self, byte: u8) {
+        self.process.send(byte);
+    }
+}
+
+/// 메모리 버퍼 - 복제본끼리 버퍼를 공유하므로 장치에 넘긴 뒤에도 입출력 가능
+#[derive(Debug, Clone, Default)]
+pub struct BufferPort {
+    input: Arc<Mutex<VecDeque<u8>>>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl BufferPort {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 장치가 받을 바이트 추가
+    pub fn push_input(&self, bytes: &[u8]) {
+        self.input.lock().unwrap().extend(bytes);
+    }
+
+    /// 장치가 보낸 바이트를 꺼냄
+    pub fn take_output(&self) -> Vec<u8> {
+        std::mem::take(&mut self.output.lock().unwrap())
+    }
+}
+
+impl SerialPort for BufferPort {
+    fn receive(&mut self) -> Option<u8> {
+        self.input.lock().unwrap().pop_front()
+    }
+
+    fn send(&mut self, byte: u8) {
+        self.output.lock().unwrap().push(byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_file_port() {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("serial-in-{}.txt", std::process::id()));
+        let output = dir.join(format!("serial-out-{}.txt", std::process::id()));
+        std::fs::write(&input, b"RUN\r").unwrap();
+
+        let mut port = FilePort::open(Some(&input), Some(&output)).unwrap();
+        let received: Vec<u8> = std::iter::from_fn(|| port.receive()).collect();
+        assert_eq!(received, b"RUN\r");
+        port.send(b'O');
+        port.send(b'K');
+        drop(port);
+        assert_eq!(std::fs::read(&output).unwrap(), b"OK");
+        std::fs::remove_file(input).unwrap();
+        std::fs::remove_file(output).unwrap();
+    }
+
+    #[test]
+    fn test_process_port() {
+        let mut port = ProcessPort::spawn(Command::new("cat")).unwrap();
+        port.send(b'A');
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let echoed = loop {
+            if let Some(byte) = port.receive() {
+                break Some(byte);
+            }
+            if Instant::now() > deadline {
+                break None;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        assert_eq!(echoed, Some(b'A'));
+    }
+}
@@ -0,0 +1,756 @@
+//! 6522 VIA (Versatile Interface Adapter)
+//! 포트 A/B와 DDR, 타이머 T1/T2, 시프트 레지스터, CA1/CA2/CB1/CB2 핸드셰이크, IRQ
+use crate::state::{StateReader, StateWriter};
+use common::Result;
+use common::device::Device;
+
+/// 레지스터 번호 (RS3-RS0)
+pub const ORB: u16 = 0x0;
+pub const ORA: u16 = 0x1;
+pub const DDRB: u16 = 0x2;
+pub const DDRA: u16 = 0x3;
+pub const T1C_L: u16 = 0x4;
+pub const T1C_H: u16 = 0x5;
+pub const T1L_L: u16 = 0x6;
+pub const T1L_H: u16 = 0x7;
+pub const T2C_L: u16 = 0x8;
+pub const T2C_H: u16 = 0x9;
+pub const SR: u16 = 0xA;
+pub const ACR: u16 = 0xB;
+pub const PCR: u16 = 0xC;
+pub const IFR: u16 = 0xD;
+pub const IER: u16 = 0xE;
+/// 핸드셰이크 없는 ORA
+pub const ORA_NH: u16 = 0xF;
+
+/// IFR/IER 비트
+pub const IRQ_CA2: u8 = 0x01;
+pub const IRQ_CA1: u8 = 0x02;
+pub const IRQ_SR: u8 = 0x04;
+pub const IRQ_CB2: u8 = 0x08;
+pub const IRQ_CB1: u8 = 0x10;
+pub const IRQ_T2: u8 = 0x20;
+pub const IRQ_T1: u8 = 0x40;
+
+// ACR 비트
+const ACR_PA_LATCH: u8 = 0x01;
+const ACR_PB_LATCH: u8 = 0x02;
+const ACR_T2_COUNT: u8 = 0x20;
+const ACR_T1_FREE_RUN: u8 = 0x40;
+const ACR_T1_PB7: u8 = 0x80;
+
+// CA2/CB2 출력 모드 (PCR 3비트)
+const HANDSHAKE: u8 = 4;
+const PULSE: u8 = 5;
+const LOW: u8 = 6;
+const HIGH: u8 = 7;
+
+// 시프트 레지스터 모드 (ACR 비트 2-4)
+const SR_DISABLED: u8 = 0;
+const SR_IN_T2: u8 = 1;
+const SR_IN_PHI2: u8 = 2;
+const SR_IN_CB1: u8 = 3;
+const SR_OUT_FREE: u8 = 4;
+const SR_OUT_T2: u8 = 5;
+const SR_OUT_PHI2: u8 = 6;
+const SR_OUT_CB1: u8 = 7;
+
+/// 6522 VIA - 16개 레지스터, 오프셋 하위 4비트만 사용
+/// 외부 회로는 `set_port_a`/`set_ca1` 등으로 핀을 구동하고 `port_a`/`ca2` 등으로 출력을 읽음
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Via {
+    ora: u8,
+    orb: u8,
+    ddra: u8,
+    ddrb: u8,
+    /// 외부에서 구동하는 핀 레벨 (입력 핀)
+    pins_a: u8,
+    pins_b: u8,
+    /// CA1/CB1 엣지에서 래치한 입력
+    latch_a: u8,
+    latch_b: u8,
+
+    t1_counter: u16,
+    t1_latch: u16,
+    /// 원샷 모드에서 아직 타임아웃 전
+    t1_armed: bool,
+    /// 프리런 타임아웃 다음 사이클에 래치 값 다시 적재
+    t1_reload: bool,
+    pb7: bool,
+    t2_counter: u16,
+    t2_latch_low: u8,
+    t2_armed: bool,
+
+    sr: u8,
+    /// 이번 전송에서 옮긴 비트 수 (8이면 정지)
+    sr_bits: u8,
+    /// 다음 CB1 클럭 반주기까지 남은 사이클
+    sr_timer: u16,
+    /// 시프트 클럭 출력 레벨
+    sr_clock: bool,
+
+    acr: u8,
+    pcr: u8,
+    ifr: u8,
+    ier: u8,
+
+    /// 제어선 입력 레벨
+    ca1: bool,
+    ca2: bool,
+    cb1: bool,
+    cb2: bool,
+    /// 핸드셰이크/펄스 모드의 CA2/CB2 출력
+    ca2_out: bool,
+    cb2_out: bool,
+    /// 펄스 출력이 다음 사이클에 복귀
+    ca2_pulse: bool,
+    cb2_pulse: bool,
+}
+
+impl Default for Via {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Via {
+    /// 리셋 상태 - 레지스터는 0, 입력 핀과 제어선은 풀업되어 high
+    pub fn new() -> Self {
+        Self {
+            ora: 0,
+            orb: 0,
+            ddra: 0,
+            ddrb: 0,
+            pins_a: 0xFF,
+            pins_b: 0xFF,
+            latch_a: 0,
+            latch_b: 0,
+            t1_counter: 0xFFFF,
+            t1_latch: 0xFFFF,
+            t1_armed: false,
+            t1_reload: false,
+            pb7: true,
+            t2_counter: 0xFFFF,
+            t2_latch_low: 0xFF,
+            t2_armed: false,
+            sr: 0,
+            sr_bits: 8,
+            sr_timer: 0,
+            sr_clock: true,
+            acr: 0,
+            pcr: 0,
+            ifr: 0,
+            ier: 0,
+            ca1: true,
+            ca2: true,
+            cb1: true,
+            cb2: true,
+            ca2_out: true,
+            cb2_out: true,
+            ca2_pulse: false,
+            cb2_pulse: false,
+        }
+    }
+
+    /// 포트 A 핀 레벨 - 출력 핀은 ORA, 입력 핀은 외부 값
+    pub fn port_a(&self) -> u8 {
+        (self.ora & self.ddra) | (self.pins_a & !self.ddra)
+    }
+
+    /// 포트 B 핀 레벨 - T1이 PB7을 구동하면 그 값
+    pub fn port_b(&self) -> u8 {
+        let value = (self.orb & self.ddrb) | (self.pins_b & !self.ddrb);
+        if self.acr & ACR_T1_PB7 != 0 {
+            (value & 0x7F) | ((self.pb7 as u8) << 7)
+        } else {
+            value
+        }
+    }
+
+    /// 포트 A 입력 핀 구동
+    pub fn set_port_a(&mut self, value: u8) {
+        self.pins_a = value;
+    }
+
+    /// 포트 B 입력 핀 구동 - T2 펄스 카운트 모드에서는 PB6 하강 엣지를 셈
+    pub fn set_port_b(&mut self, value: u8) {
+        let falling = self.pins_b & 0x40 != 0 && value & 0x40 == 0;
+        self.pins_b = value;
+        if falling && self.acr & ACR_T2_COUNT != 0 {
+            self.t2_counter = self.t2_counter.wrapping_sub(1);
+            if self.t2_counter == 0 && self.t2_armed {
+                self.ifr |= IRQ_T2;
+                self.t2_armed = false;
+            }
+        }
+    }
+
+    /// CA1 입력 - PCR 비트 0이 고른 엣지에서 인터럽트와 래치
+    pub fn set_ca1(&mut self, level: bool) {
+        if level == self.ca1 {
+            return;
+        }
+        self.ca1 = level;
+        if level == (self.pcr & 0x01 != 0) {
+            self.ifr |= IRQ_CA1;
+            if self.acr & ACR_PA_LATCH != 0 {
+                self.latch_a = self.port_a();
+            }
+            if self.ca2_mode() == HANDSHAKE {
+                self.ca2_out = true;
+            }
+        }
+    }
+
+    /// CA2 입력 (입력 모드일 때)
+    pub fn set_ca2(&mut self, level: bool) {
+        if level == self.ca2 {
+            return;
+        }
+        self.ca2 = level;
+        let mode = self.ca2_mode();
+        if mode < HANDSHAKE && level == (mode & 0x02 != 0) {
+            self.ifr |= IRQ_CA2;
+        }
+    }
+
+    /// CB1 입력 - 외부 시프트 클럭으로도 사용
+    pub fn set_cb1(&mut self, level: bool) {
+        if level == self.cb1 {
+            return;
+        }
+        self.cb1 = level;
+        if level == (self.pcr & 0x10 != 0) {
+            self.ifr |= IRQ_CB1;
+            if self.acr & ACR_PB_LATCH != 0 {
+                self.latch_b = self.port_b();
+            }
+            if self.cb2_mode() == HANDSHAKE {
+                self.cb2_out = true;
+            }
+        }
+        if level && matches!(self.sr_mode(), SR_IN_CB1 | SR_OUT_CB1) && self.sr_bits < 8 {
+            self.shift_bit();
+        }
+    }
+
+    /// CB2 입력 (입력 모드일 때, 시프트 입력 데이터)
+    pub fn set_cb2(&mut self, level: bool) {
+        if level == self.cb2 {
+            return;
+        }
+        self.cb2 = level;
+        let mode = self.cb2_mode();
+        if mode < HANDSHAKE && level == (mode & 0x02 != 0) {
+            self.ifr |= IRQ_CB2;
+        }
+    }
+
+    /// CA2 출력 레벨 - 입력 모드면 입력 레벨
+    pub fn ca2(&self) -> bool {
+        match self.ca2_mode() {
+            HANDSHAKE | PULSE => self.ca2_out,
+            LOW => false,
+            HIGH => true,
+            _ => self.ca2,
+        }
+    }
+
+    /// CB2 출력 레벨 - 시프트 출력 중이면 데이터 비트
+    pub fn cb2(&self) -> bool {
+        if self.sr_mode() >= SR_OUT_FREE {
+            // 마지막으로 내보낸 비트 (회전했으므로 비트 0)
+            return self.sr & 0x01 != 0;
+        }
+        match self.cb2_mode() {
+            HANDSHAKE | PULSE => self.cb2_out,
+            LOW => false,
+            HIGH => true,
+            _ => self.cb2,
+        }
+    }
+
+    /// 시프트 클럭 출력 (내부 클럭 모드일 때 CB1)
+    pub fn cb1(&self) -> bool {
+        match self.sr_mode() {
+            SR_DISABLED | SR_IN_CB1 | SR_OUT_CB1 => self.cb1,
+            _ => self.sr_clock,
+        }
+    }
+
+    pub fn t1_counter(&self) -> u16 {
+        self.t1_counter
+    }
+
+    pub fn t2_counter(&self) -> u16 {
+        self.t2_counter
+    }
+
+    /// IFR 값 - 비트 7은 허용된 플래그가 하나라도 있으면 1
+    pub fn ifr(&self) -> u8 {
+        let pending = self.ifr & self.ier & 0x7F != 0;
+        self.ifr | ((pending as u8) << 7)
+    }
+
+    fn ca2_mode(&self) -> u8 {
+        (self.pcr >> 1) & 0x07
+    }
+
+    fn cb2_mode(&self) -> u8 {
+        (self.pcr >> 5) & 0x07
+    }
+
+    fn sr_mode(&self) -> u8 {
+        (self.acr >> 2) & 0x07
+    }
+
+    // ORA 접근 - CA1/CA2 플래그 클리어, CA2 핸드셰이크
+    fn port_a_access(&mut self) {
+        self.ifr &= !IRQ_CA1;
+        let mode = self.ca2_mode();
+        // 독립 인터럽트 입력 모드(001, 011)는 클리어하지 않음
+        if !(mode < HANDSHAKE && mode & 0x01 != 0) {
+            self.ifr &= !IRQ_CA2;
+        }
+        if mode == HANDSHAKE || mode == PULSE {
+            self.ca2_out = false;
+            self.ca2_pulse = mode == PULSE;
+        }
+    }
+
+    // ORB 접근 - CB1/CB2 플래그 클리어, 쓰기일 때만 CB2 핸드셰이크
+    fn port_b_access(&mut self, write: bool) {
+        self.ifr &= !IRQ_CB1;
+        let mode = self.cb2_mode();
+        if !(mode < HANDSHAKE && mode & 0x01 != 0) {
+            self.ifr &= !IRQ_CB2;
+        }
+        if write && (mode == HANDSHAKE || mode == PULSE) {
+            self.cb2_out = false;
+            self.cb2_pulse = mode == PULSE;
+        }
+    }
+
+    fn read_port_a(&self) -> u8 {
+        if self.acr & ACR_PA_LATCH != 0 {
+            self.latch_a
+        } else {
+            self.port_a()
+        }
+    }
+
+    fn read_port_b(&self) -> u8 {
+        if self.acr & ACR_PB_LATCH != 0 {
+            self.latch_b
+        } else {
+            self.port_b()
+        }
+    }
+
+    // SR 읽기/쓰기 - 플래그 클리어 후 8비트 전송 시작
+    fn start_shift(&mut self) {
+        self.ifr &= !IRQ_SR;
+        if self.sr_mode() != SR_DISABLED {
+            self.sr_bits = 0;
+            self.sr_clock = true;
+            self.sr_timer = self.shift_half_period();
+        }
+    }
+
+    // CB1 클럭 반주기 - φ2는 1사이클, T2는 래치 하위 바이트 + 2사이클
+    fn shift_half_period(&self) -> u16 {
+        match self.sr_mode() {
+            SR_IN_PHI2 | SR_OUT_PHI2 => 1,
+            _ => self.t2_latch_low as u16 + 2,
+        }
+    }
+
+    // 클럭 상승 엣지마다 1비트 - 출력은 비트 7을 내보내고 회전, 입력은 CB2를 비트 0으로
+    fn shift_bit(&mut self) {
+        let mode = self.sr_mode();
+        if mode >= SR_OUT_FREE {
+            self.sr = self.sr.rotate_left(1);
+        } else {
+            self.sr = (self.sr << 1) | self.cb2 as u8;
+        }
+        self.sr_bits += 1;
+        if self.sr_bits == 8 {
+            if mode == SR_OUT_FREE {
+                // 프리런 출력은 인터럽트 없이 계속 순환
+                self.sr_bits = 0;
+            } else {
+                self.ifr |= IRQ_SR;
+            }
+        }
+    }
+
+    // 1사이클 진행
+    fn clock(&mut self) {
+        if std::mem::take(&mut self.ca2_pulse) {
+            self.ca2_out = true;
+        }
+        if std::mem::take(&mut self.cb2_pulse) {
+            self.cb2_out = true;
+        }
+
+        // T1: N을 쓰면 N+1사이클 뒤 타임아웃, 프리런 주기는 N+2
+        if self.t1_reload {
+            self.t1_reload = false;
+            self.t1_counter = self.t1_latch;
+        } else {
+            self.t1_counter = self.t1_counter.wrapping_sub(1);
+            if self.t1_counter == 0xFFFF {
+                if self.acr & ACR_T1_FREE_RUN != 0 {
+                    self.ifr |= IRQ_T1;
+                    self.t1_reload = true;
+                    self.pb7 = !self.pb7;
+                } else if self.t1_armed {
+                    self.ifr |= IRQ_T1;
+                    self.t1_armed = false;
+                    self.pb7 = true;
+                }
+            }
+        }
+
+        if self.acr & ACR_T2_COUNT == 0 {
+            self.t2_counter = self.t2_counter.wrapping_sub(1);
+            if self.t2_counter == 0xFFFF && self.t2_armed {
+                self.ifr |= IRQ_T2;
+                self.t2_armed = false;
+            }
+        }
+
+        let internal_clock = matches!(
+            self.sr_mode(),
+            SR_IN_T2 | SR_IN_PHI2 | SR_OUT_FREE | SR_OUT_T2 | SR_OUT_PHI2
+        );
+        if internal_clock && self.sr_bits < 8 {
+            self.sr_timer = self.sr_timer.saturating_sub(1);
+            if self.sr_timer == 0 {
+                self.sr_clock = !self.sr_clock;
+                self.sr_timer = self.shift_half_period();
+                if self.sr_clock {
+                    self.shift_bit();
+                }
+            }
+        }
+    }
+}
+
+impl Device for Via {
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x0F {
+            ORB => self.read_port_b(),
+            ORA | ORA_NH => self.read_port_a(),
+            DDRB => self.ddrb,
+            DDRA => self.ddra,
+            T1C_L => self.t1_counter as u8,
+            T1C_H => (self.t1_counter >> 8) as u8,
+            T1L_L => self.t1_latch as u8,
+            T1L_H => (self.t1_latch >> 8) as u8,
+            T2C_L => self.t2_counter as u8,
+            T2C_H => (self.t2_counter >> 8) as u8,
+            SR => self.sr,
+            ACR => self.acr,
+            PCR => self.pcr,
+            IFR => self.ifr(),
+            _ => self.ier | 0x80,
+        }
+    }
+
+    fn read(&mut self, offset: u16) -> u8 {
+        let value = self.peek(offset);
+        match offset & 0x0F {
+            ORB => self.port_b_access(false),
+            ORA => self.port_a_access(),
+            T1C_L => self.ifr &= !IRQ_T1,
+            T2C_L => self.ifr &= !IRQ_T2,
+            SR => self.start_shift(),
+            _ => {}
+        }
+        value
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        match offset & 0x0F {
+            ORB => {
+                self.orb = value;
+                self.port_b_access(true);
+            }
+            ORA => {
+                self.ora = value;
+                self.port_a_access();
+            }
+            ORA_NH => self.ora = value,
+            DDRB => self.ddrb = value,
+            DDRA => self.ddra = value,
+            T1C_L | T1L_L => self.t1_latch = (self.t1_latch & 0xFF00) | value as u16,
+            T1C_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | (value as u16) << 8;
+                self.t1_counter = self.t1_latch;
+                self.t1_reload = false;
+                self.t1_armed = true;
+                self.ifr &= !IRQ_T1;
+                if self.acr & ACR_T1_PB7 != 0 {
+                    self.pb7 = false;
+                }
+            }
+            T1L_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | (value as u16) << 8;
+                self.ifr &= !IRQ_T1;
+            }
+            T2C_L => self.t2_latch_low = value,
+            T2C_H => {
+                self.t2_counter = (value as u16) << 8 | self.t2_latch_low as u16;
+                self.t2_armed = true;
+                self.ifr &= !IRQ_T2;
+            }
+            SR => {
+                self.sr = value;
+                self.start_shift();
+            }
+            ACR => self.acr = value,
+            PCR => self.pcr = value,
+            IFR => self.ifr &= !(value & 0x7F),
+            _ => {
+                if value & 0x80 != 0 {
+                    self.ier |= value & 0x7F;
+                } else {
+                    self.ier &= !value;
+                }
+            }
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        for _ in 0..cycles {
+            self.clock();
+        }
+    }
+
+    fn irq(&self) -> bool {
+        self.ifr & self.ier & 0x7F != 0
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        StateWriter::default()
+            .bytes(&[self.ora, self.orb, self.ddra, self.ddrb])
+            .bytes(&[self.pins_a, self.pins_b, self.latch_a, self.latch_b])
+            .u16(self.t1_counter)
+            .u16(self.t1_latch)
+            .bool(self.t1_armed)
+            .bool(self.t1_reload)
+            .bool(self.pb7)
+            .u16(self.t2_counter)
+            .u8(self.t2_latch_low)
+            .bool(self.t2_armed)
+            .u8(self.sr)
+            .u8(self.sr_bits)
+            .u16(self.sr_timer)
+            .bool(self.sr_clock)
+            .bytes(&[self.acr, self.pcr, self.ifr, self.ier])
+            .bytes(&[
+                self.ca1 as u8,
+                self.ca2 as u8,
+                self.cb1 as u8,
+                self.cb2 as u8,
+            ])
+            .bytes(&[self.ca2_out as u8, self.cb2_out as u8])
+            .bytes(&[self.ca2_pulse as u8, self.cb2_pulse as u8])
+            .finish()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<()> {
+        let mut reader = StateReader::new(state);
+        let mut via = Via::new();
+        [via.ora, via.orb, via.ddra, via.ddrb] = reader.bytes(4)?.try_into().unwrap();
+        [via.pins_a, via.pins_b, via.latch_a, via.latch_b] = reader.bytes(4)?.try_into().unwrap();
+        via.t1_counter = reader.u16()?;
+        via.t1_latch = reader.u16()?;
+        via.t1_armed = reader.bool()?;
+        via.t1_reload = reader.bool()?;
+        via.pb7 = reader.bool()?;
+        via.t2_counter = reader.u16()?;
+        via.t2_latch_low = reader.u8()?;
+        via.t2_armed = reader.bool()?;
+        via.sr = reader.u8()?;
+        via.sr_bits = reader.u8()?;
+        via.sr_timer = reader.u16()?;
+        via.sr_clock = reader.bool()?;
+        [via.acr, via.pcr, via.ifr, via.ier] = reader.bytes(4)?.try_into().unwrap();
+        via.ca1 = reader.bool()?;
+        via.ca2 = reader.bool()?;
+        via.cb1 = reader.bool()?;
+        via.cb2 = reader.bool()?;
+        via.ca2_out = reader.bool()?;
+        via.cb2_out = reader.bool()?;
+        via.ca2_pulse = reader.bool()?;
+        via.cb2_pulse = reader.bool()?;
+        reader.finish()?;
+        *self = via;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ports_and_ddr() {
+        let mut via = Via::new();
+        via.write(DDRA, 0xF0);
+        via.write(ORA, 0xAA);
+        via.set_port_a(0x05);
+        assert_eq!(via.port_a(), 0xA5);
+        assert_eq!(via.read(ORA), 0xA5);
+
+        via.write(DDRB, 0xFF);
+        via.write(ORB, 0x3C);
+        assert_eq!(via.port_b(), 0x3C);
+    }
+
+    #[test]
+    fn test_t1_one_shot_and_free_run() {
+        let mut via = Via::new();
+        via.write(IER, 0x80 | IRQ_T1);
+        via.write(T1C_L, 10);
+        via.write(T1C_H, 0);
+        via.tick(10);
+        assert!(!via.irq());
+        via.tick(1);
+        assert!(via.irq(), "timeout N+1 cycles after writing T1C-H");
+        via.read(T1C_L);
+        assert!(!via.irq());
+        via.tick(0x10000);
+        assert!(!via.irq(), "one-shot fires once");
+
+        // 프리런: 주기 N+2, PB7 토글
+        via.write(ACR, ACR_T1_FREE_RUN | ACR_T1_PB7);
+        via.write(T1C_H, 0);
+        assert_eq!(via.port_b() & 0x80, 0);
+        via.tick(11);
+        assert!(via.irq());
+        assert_eq!(via.port_b() & 0x80, 0x80);
+        via.write(IFR, IRQ_T1);
+        via.tick(11);
+        assert!(!via.irq());
+        via.tick(1);
+        assert!(via.irq());
+    }
+
+    #[test]
+    fn test_t2_one_shot_and_pulse_count() {
+        let mut via = Via::new();
+        via.write(IER, 0x80 | IRQ_T2);
+        via.write(T2C_L, 5);
+        via.write(T2C_H, 0);
+        via.tick(6);
+        assert!(via.irq());
+        assert_eq!(via.read(T2C_L), 0xFF);
+        assert!(!via.irq());
+
+        via.write(ACR, ACR_T2_COUNT);
+        via.write(T2C_L, 2);
+        via.write(T2C_H, 0);
+        for _ in 0..2 {
+            via.set_port_b(0xBF);
+            via.set_port_b(0xFF);
+        }
+        assert!(via.irq(), "two PB6 pulses");
+    }
+
+    #[test]
+    fn test_ca1_interrupt_and_handshake() {
+        let mut via = Via::new();
+        // CA1 상승 엣지, CA2 핸드셰이크 출력, PA 래치
+        via.write(PCR, 0x01 | HANDSHAKE << 1);
+        via.write(ACR, ACR_PA_LATCH);
+        via.write(IER, 0x80 | IRQ_CA1);
+
+        via.set_port_a(0x42);
+        via.set_ca1(false);
+        assert!(!via.irq(), "falling edge ignored");
+        via.set_ca1(true);
+        assert!(via.irq());
+        assert!(via.ca2());
+        via.set_port_a(0x00);
+        assert_eq!(via.peek(ORA_NH), 0x42, "latched on CA1");
+        assert!(via.irq(), "ORA without handshake keeps flag");
+
+        assert_eq!(via.read(ORA), 0x42);
+        assert!(!via.irq());
+        assert!(!via.ca2(), "data taken");
+        via.set_ca1(false);
+        via.set_ca1(true);
+        assert!(via.ca2(), "data ready");
+
+        // 펄스 모드는 한 사이클만 low
+        via.write(PCR, PULSE << 1);
+        via.write(ORA, 0);
+        assert!(!via.ca2());
+        via.tick(1);
+        assert!(via.ca2());
+    }
+
+    #[test]
+    fn test_ier_ifr() {
+        let mut via = Via::new();
+        via.write(IER, 0x80 | IRQ_CB1 | IRQ_CA1);
+        assert_eq!(via.peek(IER), 0x80 | IRQ_CB1 | IRQ_CA1);
+        via.write(IER, IRQ_CA1);
+        assert_eq!(via.peek(IER), 0x80 | IRQ_CB1);
+
+        via.set_ca1(false);
+        assert_eq!(via.peek(IFR), IRQ_CA1, "flag set but disabled");
+        via.set_cb1(false);
+        assert_eq!(via.peek(IFR), 0x80 | IRQ_CB1 | IRQ_CA1);
+        via.write(IFR, IRQ_CB1);
+        assert_eq!(via.peek(IFR), IRQ_CA1);
+    }
+
+    #[test]
+    fn test_shift_register() {
+        let mut via = Via::new();
+        via.write(IER, 0x80 | IRQ_SR);
+
+        // φ2 출력: 비트당 2사이클
+        via.write(ACR, SR_OUT_PHI2 << 2);
+        via.write(SR, 0b1000_0001);
+        via.tick(2);
+        assert!(via.cb2(), "bit 7 first");
+        via.tick(2);
+        assert!(!via.cb2());
+        via.tick(11);
+        assert!(!via.irq());
+        via.tick(1);
+        assert!(via.irq());
+        assert_eq!(via.peek(SR), 0b1000_0001, "rotated back");
+
+        // 외부 CB1 클럭 입력
+        via.write(ACR, SR_IN_CB1 << 2);
+        via.read(SR);
+        for bit in [1, 0, 1, 0, 0, 1, 0, 1] {
+            via.set_cb2(bit == 1);
+            via.set_cb1(false);
+            via.set_cb1(true);
+        }
+        assert!(via.irq());
+        assert_eq!(via.peek(SR), 0b1010_0101);
+    }
+
+    #[test]
+    fn test_save_state() {
+        let mut via = Via::new();
+        via.write(DDRB, 0x0F);
+        via.write(T1C_L, 0x34);
+        via.write(T1C_H, 0x12);
+        via.tick(5);
+        let state = via.save_state();
+
+        let mut restored = Via::new();
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored, via);
+        assert!(restored.load_state(&state[1..]).is_err());
+    }
+}
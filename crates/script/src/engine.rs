@@ -0,0 +1,526 @@
+//! Rhai 스크립트 엔진 - 디버거/CPU/메모리 조작 함수를 스크립트에 등록
+use common::Result;
+use cpu::register::RegisterData;
+use cpu::{CPUState, InterruptType, RegisterType, StopReason};
+use debugger::{AccessType, Debugger};
+use disassembler::Disassembler;
+use error::Error;
+use rhai::{AST, Dynamic, Engine, EvalAltResult, FnPtr, INT, NativeCallContext, Scope};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+/// run()에 한도를 주지 않았을 때 최대 실행 명령어 수
+const RUN_LIMIT: INT = 10_000_000;
+
+type ScriptResult<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+/// 스크립트 함수들이 공유하는 상태
+struct Session {
+    debugger: Debugger,
+    // 브레이크포인트 ID별 동작 - true를 돌려주면 정지
+    actions: HashMap<usize, FnPtr>,
+    // 명령어마다 실행 주소로 호출 - 사용자 정의 트레이스 형식용
+    on_instruction: Option<FnPtr>,
+}
+
+pub struct ScriptEngine {
+    engine: Engine,
+    scope: Scope<'static>,
+    // 이전 스크립트에서 정의한 함수 (브레이크포인트 동작에서 호출 가능)
+    functions: AST,
+    session: Rc<RefCell<Session>>,
+    output: Rc<RefCell<Vec<String>>>,
+}
+
+impl ScriptEngine {
+    pub fn new(debugger: Debugger) -> Self {
+        let session = Rc::new(RefCell::new(Session {
+            debugger,
+            actions: HashMap::new(),
+            on_instruction: None,
+        }));
+        let output = Rc::new(RefCell::new(Vec::new()));
+
+        let mut engine = Engine::new();
+        let printed = output.clone();
+        engine.on_print(move |text| printed.borrow_mut().push(text.to_string()));
+        register_functions(&mut engine, &session);
+
+        Self {
+            engine,
+            scope: Scope::new(),
+            functions: AST::empty(),
+            session,
+            output,
+        }
+    }
+
+    /// 스크립트 실행 후 print 출력과 마지막 값(있으면)을 줄 단위로 반환
+    /// 변수와 함수 정의는 다음 실행에도 유지됨
+    pub fn eval(&mut self, script: &str) -> Result<String> {
+        let ast = self
+            .engine
+            .compile_with_scope(&self.scope, script)
+            .map_err(|e| Error::Script(e.to_string()))?;
+        let ast = self.functions.merge(&ast);
+        self.functions = ast.clone_functions_only();
+
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut self.scope, &ast);
+        let mut lines = std::mem::take(&mut *self.output.borrow_mut());
+        let value = result.map_err(|e| Error::Script(e.to_string()))?;
+        if !value.is_unit() {
+            lines.push(value.to_string());
+        }
+        Ok(lines.join("\n"))
+    }
+
+    pub fn eval_file(&mut self, path: impl AsRef<Path>) -> Result<String> {
+        let script = std::fs::read_to_string(path)?;
+        self.eval(&script)
+    }
+
+    /// 디버거 직접 조작
+    pub fn with_debugger<T>(&self, f: impl FnOnce(&mut Debugger) -> T) -> T {
+        f(&mut self.session.borrow_mut().debugger)
+    }
+
+    /// 엔진을 닫고 디버거 돌려받기 - 브레이크포인트 동작과 명령어 훅은 사라짐
+    /// 복제본은 이벤트 핸들러와 정지 채널을 잃으므로 돌려주지 않음
+    pub fn into_debugger(self) -> Result<Debugger> {
+        let Self {
+            engine,
+            scope,
+            functions,
+            session,
+            output: _,
+        } = self;
+        // 저장된 FnPtr이 엔진 함수(세션 참조)를 붙잡고 있을 수 있으므로 먼저 해제
+        {
+            let mut session = session.borrow_mut();
+            session.actions.clear();
+            session.on_instruction = None;
+        }
+        drop(scope);
+        drop(functions);
+        drop(engine);
+        Rc::try_unwrap(session)
+            .map(|session| session.into_inner().debugger)
+            .map_err(|_| Error::Internal("Script session is still in use".to_string()))
+    }
+}
+
+fn script_error(error: Error) -> Box<EvalAltResult> {
+    error.to_string().into()
+}
+
+fn to_u16(value: INT) -> ScriptResult<u16> {
+    u16::try_from(value).map_err(|_| format!("Address out of range: {}", value).into())
+}
+
+fn to_u8(value: INT) -> ScriptResult<u8> {
+    u8::try_from(value).map_err(|_| format!("Byte out of range: {}", value).into())
+}
+
+fn register_type(name: &str) -> ScriptResult<RegisterType> {
+    match name.to_ascii_lowercase().as_str() {
+        "a" => Ok(RegisterType::A),
+        "x" => Ok(RegisterType::X),
+        "y" => Ok(RegisterType::Y),
+        "sp" | "s" => Ok(RegisterType::S),
+        "p" => Ok(RegisterType::P),
+        "pc" => Ok(RegisterType::PC),
+        _ => Err(format!("Unknown register: {}", name).into()),
+    }
+}
+
+fn access_type(kind: &str) -> ScriptResult<AccessType> {
+    match kind.to_ascii_lowercase().as_str() {
+        "r" => Ok(AccessType::Read),
+        "w" => Ok(AccessType::Write),
+        "rw" => Ok(AccessType::Access),
+        _ => Err(format!("Unknown watch type: {} (use r, w or rw)", kind).into()),
+    }
+}
+
+/// 정지 사유를 스크립트용 문자열로
+fn describe_stop(debugger: &Debugger) -> ScriptResult<String> {
+    let state = debugger.get_cpu().map_err(script_error)?.get_state();
+    let description = match state {
+        CPUState::Paused(StopReason::Breakpoint(_)) => "break",
+        CPUState::Paused(StopReason::Watchpoint { .. }) => "watch",
+        CPUState::Paused(StopReason::StepComplete) => "step",
+        CPUState::Paused(StopReason::IllegalOpcode(_)) => "illegal",
+        CPUState::Paused(StopReason::UserPause) => "pause",
+        CPUState::Paused(StopReason::WriteProtected { .. }) => "rom",
+        CPUState::Halted(InterruptType::BRK) => "brk",
+        CPUState::Halted(_) => "halted",
+        CPUState::Running => "running",
+        CPUState::Ready => "ready",
+    };
+    Ok(description.to_string())
+}
+
+fn current_pc(debugger: &Debugger) -> ScriptResult<u16> {
+    let cpu = debugger.get_cpu().map_err(script_error)?;
+    Ok(cpu.get_value(RegisterType::PC).as_u16())
+}
+
+/// BRK 등으로 정지된 CPU를 다시 실행할 수 있게
+fn prepare_run(debugger: &mut Debugger) -> ScriptResult<()> {
+    let cpu = debugger.get_cpu_mut().map_err(script_error)?;
+    if let CPUState::Halted(_) = cpu.get_state() {
+        cpu.pause(StopReason::UserPause);
+    }
+    Ok(())
+}
+
+/// 최대 limit개 명령어 실행 - 동작이 있는 브레이크포인트는 동작이 true를 돌려줄 때만 정지
+fn run(
+    context: &NativeCallContext,
+    session: &Rc<RefCell<Session>>,
+    limit: INT,
+) -> ScriptResult<String> {
+    prepare_run(&mut session.borrow_mut().debugger)?;
+    for _ in 0..limit.max(0) {
+        let (address, reason, action, hook) = {
+            let mut session = session.borrow_mut();
+            let address = current_pc(&session.debugger)?;
+            let reason = session.debugger.step().map_err(script_error)?;
+            let action = match reason {
+                Some(StopReason::Breakpoint(id)) | Some(StopReason::Watchpoint { id, .. }) => {
+                    session.actions.get(&id).cloned()
+                }
+                _ => None,
+            };
+            (address, reason, action, session.on_instruction.clone())
+        };
+        // 훅의 반환값은 쓰지 않음
+        if let Some(hook) = hook {
+            let _ = hook.call_within_context::<Dynamic>(context, (address as INT,))?;
+        }
+        if reason == Some(StopReason::StepComplete) {
+            continue;
+        }
+        // 동작 실행 중에는 세션을 빌리지 않음 - 동작에서 read/reg 등을 호출하기 때문
+        if let Some(action) = action {
+            let stop = action.call_within_context::<Dynamic>(context, ())?;
+            if !stop.as_bool().unwrap_or(true) {
+                continue;
+            }
+        }
+        return describe_stop(&session.borrow().debugger);
+    }
+    Ok("running".to_string())
+}
+
+fn register_functions(engine: &mut Engine, session: &Rc<RefCell<Session>>) {
+    // -- 메모리
+    let s = session.clone();
+    engine.register_fn("read", move |address: INT| -> ScriptResult<INT> {
+        let value = s
+            .borrow()
+            .debugger
+            .read(to_u16(address)?)
+            .map_err(script_error)?;
+        Ok(value as INT)
+    });
+    let s = session.clone();
+    engine.register_fn(
+        "write",
+        move |address: INT, value: INT| -> ScriptResult<()> {
+            s.borrow_mut()
+                .debugger
+                .write(to_u16(address)?, to_u8(value)?)
+                .map_err(script_error)
+        },
+    );
+    let s = session.clone();
+    engine.register_fn(
+        "patch",
+        move |address: INT, bytes: rhai::Array| -> ScriptResult<()> {
+            let address = to_u16(address)?;
+            let mut session = s.borrow_mut();
+            for (offset, byte) in bytes.into_iter().enumerate() {
+                let byte = byte
+                    .as_int()
+                    .map_err(|_| Box::<EvalAltResult>::from("patch expects an array of bytes"))?;
+                session
+                    .debugger
+                    .write(address.wrapping_add(offset as u16), to_u8(byte)?)
+                    .map_err(script_error)?;
+            }
+            Ok(())
+        },
+    );
+
+    // -- 레지스터
+    let s = session.clone();
+    engine.register_fn("reg", move |name: &str| -> ScriptResult<INT> {
+        let register = register_type(name)?;
+        let session = s.borrow();
+        let cpu = session.debugger.get_cpu().map_err(script_error)?;
+        Ok(cpu.get_value(register).as_u16() as INT)
+    });
+    let s = session.clone();
+    engine.register_fn(
+        "set_reg",
+        move |name: &str, value: INT| -> ScriptResult<()> {
+            let register = register_type(name)?;
+            let value = match register {
+                RegisterType::PC => RegisterData::Bit16(to_u16(value)?),
+                _ => RegisterData::Bit8(to_u8(value)?),
+            };
+            let mut session = s.borrow_mut();
+            let cpu = session.debugger.get_cpu_mut().map_err(script_error)?;
+            cpu.set_value(register, value);
+            Ok(())
+        },
+    );
+    let s = session.clone();
+    engine.register_fn("cycles", move || -> ScriptResult<INT> {
+        let session = s.borrow();
+        let cpu = session.debugger.get_cpu().map_err(script_error)?;
+        Ok(cpu.total_cycles() as INT)
+    });
+
+    // -- 실행
+    let s = session.clone();
+    engine.register_fn(
+        "step",
+        move |context: NativeCallContext| -> ScriptResult<String> {
+            let (address, hook) = {
+                let mut session = s.borrow_mut();
+                prepare_run(&mut session.debugger)?;
+                let address = current_pc(&session.debugger)?;
+                session.debugger.step().map_err(script_error)?;
+                (address, session.on_instruction.clone())
+            };
+            if let Some(hook) = hook {
+                let _ = hook.call_within_context::<Dynamic>(&context, (address as INT,))?;
+            }
+            describe_stop(&s.borrow().debugger)
+        },
+    );
+    let s = session.clone();
+    engine.register_fn("run", move |context: NativeCallContext| {
+        run(&context, &s, RUN_LIMIT)
+    });
+    let s = session.clone();
+    engine.register_fn("run", move |context: NativeCallContext, limit: INT| {
+        run(&context, &s, limit)
+    });
+
+    // -- 명령어 훅 (step/run에서 명령어를 실행할 때마다 주소를 인자로 호출)
+    let s = session.clone();
+    engine.register_fn("on_instruction", move |hook: FnPtr| {
+        s.borrow_mut().on_instruction = Some(hook);
+    });
+    let s = session.clone();
+    engine.register_fn("clear_on_instruction", move || {
+        s.borrow_mut().on_instruction = None;
+    });
+
+    // -- 브레이크포인트
+    let s = session.clone();
+    engine.register_fn("break_at", move |address: INT| -> ScriptResult<INT> {
+        let address = to_u16(address)?;
+        Ok(s.borrow_mut()
+            .debugger
+            .add_breakpoint(address, AccessType::Access) as INT)
+    });
+    let s = session.clone();
+    engine.register_fn(
+        "break_at",
+        move |address: INT, action: FnPtr| -> ScriptResult<INT> {
+            let address = to_u16(address)?;
+            let mut session = s.borrow_mut();
+            let id = session.debugger.add_breakpoint(address, AccessType::Access);
+            session.actions.insert(id, action);
+            Ok(id as INT)
+        },
+    );
+    let s = session.clone();
+    engine.register_fn(
+        "watch",
+        move |address: INT, kind: &str| -> ScriptResult<INT> {
+            let (address, access) = (to_u16(address)?, access_type(kind)?);
+            Ok(s.borrow_mut().debugger.add_breakpoint(address, access) as INT)
+        },
+    );
+    let s = session.clone();
+    engine.register_fn(
+        "watch",
+        move |address: INT, kind: &str, action: FnPtr| -> ScriptResult<INT> {
+            let (address, access) = (to_u16(address)?, access_type(kind)?);
+            let mut session = s.borrow_mut();
+            let id = session.debugger.add_breakpoint(address, access);
+            session.actions.insert(id, action);
+            Ok(id as INT)
+        },
+    );
+    let s = session.clone();
+    engine.register_fn("delete", move |address: INT| -> ScriptResult<()> {
+        let address = to_u16(address)?;
+        let mut session = s.borrow_mut();
+        let ids: Vec<usize> = session
+            .debugger
+            .breakpoints()
+            .iter()
+            .filter(|bp| bp.address == address)
+            .map(|bp| bp.id)
+            .collect();
+        for id in ids {
+            session.actions.remove(&id);
+        }
+        session.debugger.remove_breakpoint(address);
+        Ok(())
+    });
+
+    // -- 심볼과 표시
+    let s = session.clone();
+    engine.register_fn("symbol", move |name: &str| -> ScriptResult<INT> {
+        s.borrow()
+            .debugger
+            .symbols()
+            .address_of(name)
+            .map(|address| address as INT)
+            .ok_or_else(|| script_error(Error::UnknownSymbol(name.to_string())))
+    });
+    let s = session.clone();
+    engine.register_fn("label", move |address: INT| -> ScriptResult<String> {
+        Ok(s.borrow().debugger.symbols().describe(to_u16(address)?))
+    });
+    let s = session.clone();
+    engine.register_fn("disasm", move |address: INT| -> ScriptResult<String> {
+        let address = to_u16(address)?;
+        let session = s.borrow();
+        let bytes = (0..3)
+            .map(|offset| session.debugger.read(address.wrapping_add(offset)))
+            .collect::<Result<Vec<u8>>>()
+            .map_err(script_error)?;
+        let mut disassembler = Disassembler::with_origin(bytes, address);
+        disassembler.set_symbols(session.debugger.symbols());
+        let instructions = disassembler.disassemble().map_err(script_error)?;
+        Ok(instructions
+            .first()
+            .map(|instruction| instruction.mnemonic.clone())
+            .unwrap_or_default())
+    });
+    engine.register_fn("hex", |value: INT, digits: INT| -> String {
+        format!("{:0width$X}", value, width = digits.max(0) as usize)
+    });
+    engine.register_fn(
+        "assert",
+        |condition: bool, message: &str| -> ScriptResult<()> {
+            if condition {
+                Ok(())
+            } else {
+                Err(format!("Assertion failed: {}", message).into())
+            }
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assembler::Assembler;
+    use cpu::CPU;
+    use memory::Memory;
+    use std::sync::{Arc, Mutex};
+
+    fn engine_with(source: &str) -> ScriptEngine {
+        let code = Assembler::new(0x0200).assemble(source).unwrap();
+        let mut memory = Memory::new();
+        memory.data[0x0200..0x0200 + code.len()].copy_from_slice(&code);
+        let mut cpu = CPU::new();
+        cpu.set_memory_bus(Arc::new(Mutex::new(memory)));
+        cpu.set_pc(0x0200);
+
+        let mut debugger = Debugger::new();
+        debugger.attach(cpu);
+        debugger.enabled().unwrap();
+        ScriptEngine::new(debugger)
+    }
+
+    #[test]
+    fn test_breakpoint_action_decides_stop() {
+        let mut engine = engine_with("LDX #$00\nLOOP:\nINX\nCPX #$05\nBNE LOOP\nBRK");
+        let output = engine
+            .eval(
+                r#"
+                let hits = 0;
+                fn at_five() { reg("x") == 5 }
+                break_at(0x0203, || { hits += 1; at_five() });
+                let reason = run();
+                print(`${reason} x=${hex(reg("x"), 2)} at ${hex(reg("pc"), 4)} after ${hits} hits`);
+                "#,
+            )
+            .unwrap();
+        assert_eq!(output, "break x=05 at 0203 after 5 hits");
+
+        // 변수와 함수는 다음 실행에도 유지
+        assert_eq!(engine.eval("at_five()").unwrap(), "true");
+        engine
+            .eval("delete(0x0203); break_at(0x0203, Fn(\"at_five\"))")
+            .unwrap();
+        assert_eq!(engine.eval("run()").unwrap(), "brk");
+    }
+
+    #[test]
+    fn test_on_instruction_trace() {
+        let mut engine = engine_with("LDX #$01\nINX\nBRK");
+        let output = engine
+            .eval(
+                r#"
+                on_instruction(|address| print(`${hex(address, 4)}  ${disasm(address)}  X=${hex(reg("x"), 2)}`));
+                step();
+                run();
+                clear_on_instruction();
+                "#,
+            )
+            .unwrap();
+        assert_eq!(
+            output,
+            "0200  LDX #$01  X=01\n0202  INX  X=02\n0203  BRK  X=02"
+        );
+    }
+
+    #[test]
+    fn test_into_debugger_keeps_event_handlers() {
+        let mut engine = engine_with("LDX #$00\nLOOP:\nINX\nCPX #$05\nBNE LOOP\nBRK");
+        // 변수와 동작에 남은 클로저가 세션을 붙잡아도 디버거를 그대로 돌려받음
+        engine
+            .eval(
+                r#"
+                let check = || reg("x") == 5;
+                break_at(0x0203, || check.call());
+                on_instruction(|address| check.call());
+                "#,
+            )
+            .unwrap();
+        let mut debugger = engine.into_debugger().unwrap();
+        assert_eq!(
+            debugger.continue_execution().unwrap(),
+            Some(StopReason::Breakpoint(1))
+        );
+    }
+
+    #[test]
+    fn test_memory_patch_and_assert() {
+        let mut engine = engine_with("LDA $10\nBRK");
+        engine
+            .eval("patch(0x10, [0x42]); run(); assert(reg(\"a\") == 0x42, \"A loaded\");")
+            .unwrap();
+        assert_eq!(engine.eval("disasm(0x0200)").unwrap(), "LDA $10");
+
+        let error = engine
+            .eval("assert(read(0x10) == 0, \"zero\")")
+            .unwrap_err();
+        assert!(matches!(error, Error::Script(message) if message.contains("zero")));
+    }
+}
@@ -0,0 +1,4 @@
+//! 디버거 자동화 스크립트 (Rhai)
+mod engine;
+
+pub use engine::ScriptEngine;